@@ -0,0 +1,95 @@
+//! Builds and writes a coordinate-sorted index (CSI) from a BAM file.
+//!
+//! The input BAM must be coordinate-sorted, i.e., `SO:coordinate`. The min shift and depth are
+//! optional and default to 14 and 5, respectively.
+//!
+//! This writes the output to stdout rather than `<src>.csi`.
+//!
+//! The output is similar to the output of `samtools index -c -m <min-shift> <src>`.
+
+use std::{env, fs::File, io};
+
+use noodles_bam as bam;
+use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+use noodles_sam::{self as sam, alignment::Record};
+
+fn is_coordinate_sorted(header: &sam::Header) -> bool {
+    use sam::header::record::value::map::header::SortOrder;
+
+    if let Some(hdr) = header.header() {
+        if let Some(sort_order) = hdr.sort_order() {
+            return sort_order == SortOrder::Coordinate;
+        }
+    }
+
+    false
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let min_shift = args.next().map(|s| s.parse()).transpose()?;
+    let depth = args.next().map(|s| s.parse()).transpose()?;
+
+    let mut reader = File::open(src).map(bam::Reader::new)?;
+    let header: sam::Header = reader.read_header()?.parse()?;
+    reader.read_reference_sequences()?;
+
+    if !is_coordinate_sorted(&header) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the input BAM must be coordinate-sorted to be indexed",
+        )
+        .into());
+    }
+
+    let mut record = Record::default();
+
+    let mut indexer = csi::Index::indexer();
+
+    if let Some(min_shift) = min_shift {
+        indexer.set_min_shift(min_shift);
+    }
+
+    if let Some(depth) = depth {
+        indexer.set_depth(depth);
+    }
+
+    let mut start_position = reader.virtual_position();
+
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let end_position = reader.virtual_position();
+        let chunk = Chunk::new(start_position, end_position);
+
+        let alignment_context = match (
+            record.reference_sequence_id(),
+            record.alignment_start(),
+            record.alignment_end(),
+        ) {
+            (Some(id), Some(start), Some(end)) => {
+                Some((id, start, end, !record.flags().is_unmapped()))
+            }
+            _ => None,
+        };
+
+        indexer.add_record(alignment_context, chunk)?;
+
+        start_position = end_position;
+    }
+
+    let index = indexer.build(header.reference_sequences().len());
+
+    let stdout = io::stdout().lock();
+    let mut writer = csi::Writer::new(stdout);
+
+    writer.write_index(&index)?;
+
+    Ok(())
+}
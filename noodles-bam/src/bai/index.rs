@@ -1,9 +1,11 @@
 //! BAM index and fields.
 
 mod builder;
+mod conversion;
+mod merge;
 pub mod reference_sequence;
 
-pub use self::{builder::Builder, reference_sequence::ReferenceSequence};
+pub use self::{builder::Builder, merge::merge, reference_sequence::ReferenceSequence};
 
 use std::io;
 
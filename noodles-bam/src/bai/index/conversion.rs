@@ -0,0 +1,133 @@
+use noodles_csi::{
+    self as csi,
+    binning_index::{bin_start, ReferenceSequenceExt},
+    index::reference_sequence::Bin,
+    BinningIndex,
+};
+
+use super::{Index, ReferenceSequence, DEPTH, MIN_SHIFT};
+
+impl From<Index> for csi::Index {
+    /// Converts a BAM index to a coordinate-sorted index (CSI).
+    ///
+    /// The CSI uses the same binning scheme as BAI (a min shift of 14 and depth of 5), so the
+    /// bins, chunks, and metadata are kept as is. The linear index is folded into each bin's
+    /// `loffset`, which is the linear index entry at the start of the bin.
+    ///
+    /// This conversion is lossy. CSI has no linear index, so entries for windows that are not at
+    /// the start of a bin in the index are dropped. Queries on the converted index still return
+    /// chunks covering every overlapping record, but they may start earlier than with the BAI,
+    /// i.e., more data may be read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::bai;
+    /// use noodles_csi::{self as csi, BinningIndex};
+    ///
+    /// let index = csi::Index::from(bai::Index::new(Vec::new(), Some(8)));
+    ///
+    /// assert_eq!(index.min_shift(), 14);
+    /// assert_eq!(index.depth(), 5);
+    /// assert_eq!(index.unplaced_unmapped_record_count(), Some(8));
+    /// ```
+    fn from(index: Index) -> Self {
+        let reference_sequences = index
+            .reference_sequences()
+            .iter()
+            .map(convert_reference_sequence)
+            .collect();
+
+        let builder = csi::Index::builder()
+            .set_min_shift(index.min_shift())
+            .set_depth(index.depth())
+            .set_reference_sequences(reference_sequences);
+
+        match index.unplaced_unmapped_record_count() {
+            Some(n) => builder.set_unplaced_unmapped_record_count(n).build(),
+            None => builder.build(),
+        }
+    }
+}
+
+fn convert_reference_sequence(
+    reference_sequence: &ReferenceSequence,
+) -> csi::index::ReferenceSequence {
+    let intervals = reference_sequence.intervals();
+
+    let bins = reference_sequence
+        .bins()
+        .iter()
+        .map(|bin| {
+            let loffset = bin_start(bin.id(), MIN_SHIFT, DEPTH)
+                .and_then(|start| intervals.get(start >> MIN_SHIFT))
+                .copied()
+                .unwrap_or_default();
+
+            Bin::new(bin.id(), loffset, bin.chunks().to_vec())
+        })
+        .collect();
+
+    csi::index::ReferenceSequence::new(bins, reference_sequence.metadata().cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf as bgzf;
+    use noodles_core::Position;
+    use noodles_csi::index::reference_sequence::{bin::Chunk, Metadata};
+
+    use super::*;
+    use crate::bai::index::reference_sequence;
+
+    #[test]
+    fn test_from_index_for_csi_index() -> Result<(), Box<dyn std::error::Error>> {
+        let chunk = Chunk::new(
+            bgzf::VirtualPosition::from(55),
+            bgzf::VirtualPosition::from(89),
+        );
+
+        let metadata = Metadata::new(
+            bgzf::VirtualPosition::from(55),
+            bgzf::VirtualPosition::from(89),
+            1,
+            0,
+        );
+
+        let index = Index::new(
+            vec![ReferenceSequence::new(
+                vec![
+                    reference_sequence::Bin::new(4682, vec![chunk]),
+                    reference_sequence::Bin::new(585, vec![chunk]),
+                ],
+                vec![
+                    bgzf::VirtualPosition::from(8),
+                    bgzf::VirtualPosition::from(13),
+                ],
+                Some(metadata.clone()),
+            )],
+            Some(21),
+        );
+
+        let start = Position::try_from(16385)?;
+        let expected_chunks = index.query(0, start..=start)?;
+
+        let actual = csi::Index::from(index);
+
+        let expected = csi::Index::builder()
+            .set_reference_sequences(vec![csi::index::ReferenceSequence::new(
+                vec![
+                    Bin::new(4682, bgzf::VirtualPosition::from(13), vec![chunk]),
+                    Bin::new(585, bgzf::VirtualPosition::from(8), vec![chunk]),
+                ],
+                Some(metadata),
+            )])
+            .set_unplaced_unmapped_record_count(21)
+            .build();
+
+        assert_eq!(actual, expected);
+        assert_eq!(actual.query(0, start..=start)?, expected_chunks);
+
+        Ok(())
+    }
+}
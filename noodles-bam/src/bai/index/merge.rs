@@ -0,0 +1,223 @@
+use std::{cmp, collections::BTreeMap, io};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{
+    binning_index::{
+        merge::{merge_metadata, shift, shift_chunks, sum_counts},
+        ReferenceSequenceExt,
+    },
+    index::reference_sequence::bin::Chunk,
+    BinningIndex,
+};
+
+use super::{reference_sequence::Bin, Index, ReferenceSequence};
+
+/// Merges BAM indexes of BGZF streams that were concatenated.
+///
+/// `offsets` is the compressed position at which each indexed stream starts in the concatenated
+/// stream, e.g., as returned by [`bgzf::concat`]. Chunk, linear index, and metadata virtual
+/// positions are shifted by their stream's offset, and record counts are summed.
+///
+/// The indexes must be given in the order their streams were concatenated. Reference sequences
+/// are matched by their position in the index, i.e., the streams are expected to share the same
+/// reference sequence dictionary (see [`noodles_csi::binning_index::merge`]).
+///
+/// Unset linear index entries are ignored when merging the linear indices.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bam::bai;
+///
+/// let indexes = [bai::Index::default(), bai::Index::default()];
+/// let index = bai::index::merge(&indexes, &[0, 8])?;
+///
+/// assert_eq!(index, bai::Index::default());
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn merge(indexes: &[Index], offsets: &[u64]) -> io::Result<Index> {
+    if indexes.len() != offsets.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "index and offset counts mismatch",
+        ));
+    }
+
+    let reference_sequence_count = indexes
+        .iter()
+        .map(|index| index.reference_sequences().len())
+        .max()
+        .unwrap_or_default();
+
+    let mut reference_sequences = Vec::with_capacity(reference_sequence_count);
+
+    for i in 0..reference_sequence_count {
+        let shards = indexes.iter().zip(offsets).filter_map(|(index, &offset)| {
+            index.reference_sequences().get(i).map(|rs| (rs, offset))
+        });
+
+        reference_sequences.push(merge_reference_sequences(shards)?);
+    }
+
+    let unplaced_unmapped_record_count = sum_counts(
+        indexes
+            .iter()
+            .map(|index| index.unplaced_unmapped_record_count()),
+    );
+
+    Ok(Index::new(
+        reference_sequences,
+        unplaced_unmapped_record_count,
+    ))
+}
+
+fn merge_reference_sequences<'a, I>(shards: I) -> io::Result<ReferenceSequence>
+where
+    I: Iterator<Item = (&'a ReferenceSequence, u64)>,
+{
+    let mut bins: BTreeMap<usize, Vec<Chunk>> = BTreeMap::new();
+    let mut intervals: Vec<Option<bgzf::VirtualPosition>> = Vec::new();
+    let mut metadata = None;
+
+    for (reference_sequence, offset) in shards {
+        // An empty reference sequence has no linear index entries that are set.
+        if reference_sequence.bins().is_empty() {
+            continue;
+        }
+
+        for bin in reference_sequence.bins() {
+            let chunks = shift_chunks(bin.chunks(), offset)?;
+            bins.entry(bin.id()).or_default().extend(chunks);
+        }
+
+        if intervals.len() < reference_sequence.intervals().len() {
+            intervals.resize(reference_sequence.intervals().len(), None);
+        }
+
+        let min_start = min_chunk_start(reference_sequence);
+
+        for (merged_interval, &interval) in intervals.iter_mut().zip(reference_sequence.intervals())
+        {
+            // Unset linear index entries are 0. They are dropped rather than shifted to the start
+            // of the stream, which would lower the minimum of the windows that are set in later
+            // streams.
+            if is_unset(interval, min_start) {
+                continue;
+            }
+
+            let interval = shift(interval, offset)?;
+
+            *merged_interval = Some(match *merged_interval {
+                Some(m) => cmp::min(m, interval),
+                None => interval,
+            });
+        }
+
+        if let Some(m) = reference_sequence.metadata() {
+            metadata = Some(merge_metadata(metadata, m, offset)?);
+        }
+    }
+
+    let bins = bins
+        .into_iter()
+        .map(|(id, chunks)| Bin::new(id, chunks))
+        .collect();
+
+    let intervals = intervals
+        .into_iter()
+        .map(|interval| interval.unwrap_or_default())
+        .collect();
+
+    Ok(ReferenceSequence::new(bins, intervals, metadata))
+}
+
+fn min_chunk_start(reference_sequence: &ReferenceSequence) -> Option<bgzf::VirtualPosition> {
+    reference_sequence
+        .bins()
+        .iter()
+        .flat_map(|bin| bin.chunks())
+        .map(|chunk| chunk.start())
+        .min()
+}
+
+// A linear index entry of 0 is only a record position when a chunk starts at the start of the
+// stream, i.e., the stream has no header. Otherwise, it is unset.
+fn is_unset(
+    interval: bgzf::VirtualPosition,
+    min_chunk_start: Option<bgzf::VirtualPosition>,
+) -> bool {
+    let start = bgzf::VirtualPosition::default();
+    interval == start && min_chunk_start != Some(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_csi::index::reference_sequence::Metadata;
+
+    use super::*;
+
+    fn vp(compressed: u64, uncompressed: u16) -> bgzf::VirtualPosition {
+        bgzf::VirtualPosition::try_from((compressed, uncompressed)).unwrap()
+    }
+
+    fn build_reference_sequence(
+        bin_id: usize,
+        intervals: Vec<bgzf::VirtualPosition>,
+        start: bgzf::VirtualPosition,
+        end: bgzf::VirtualPosition,
+    ) -> ReferenceSequence {
+        ReferenceSequence::new(
+            vec![Bin::new(bin_id, vec![Chunk::new(start, end)])],
+            intervals,
+            Some(Metadata::new(start, end, 2, 0)),
+        )
+    }
+
+    #[test]
+    fn test_merge() -> io::Result<()> {
+        let indexes = [
+            Index::new(
+                vec![
+                    build_reference_sequence(4681, vec![vp(0, 5)], vp(0, 5), vp(8, 0)),
+                    ReferenceSequence::default(),
+                ],
+                Some(0),
+            ),
+            Index::new(
+                vec![
+                    build_reference_sequence(4682, vec![vp(0, 0), vp(0, 5)], vp(0, 5), vp(5, 0)),
+                    ReferenceSequence::default(),
+                ],
+                Some(1),
+            ),
+            Index::new(
+                vec![
+                    ReferenceSequence::default(),
+                    build_reference_sequence(4681, vec![vp(0, 5)], vp(0, 5), vp(3, 0)),
+                ],
+                None,
+            ),
+        ];
+
+        let actual = merge(&indexes, &[0, 8, 13])?;
+
+        let expected = Index::new(
+            vec![
+                ReferenceSequence::new(
+                    vec![
+                        Bin::new(4681, vec![Chunk::new(vp(0, 5), vp(8, 0))]),
+                        Bin::new(4682, vec![Chunk::new(vp(8, 5), vp(13, 0))]),
+                    ],
+                    vec![vp(0, 5), vp(8, 5)],
+                    Some(Metadata::new(vp(0, 5), vp(13, 0), 4, 0)),
+                ),
+                build_reference_sequence(4681, vec![vp(13, 5)], vp(13, 5), vp(16, 0)),
+            ],
+            Some(1),
+        );
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
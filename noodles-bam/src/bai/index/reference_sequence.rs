@@ -12,7 +12,10 @@ use std::io;
 use bit_vec::BitVec;
 use noodles_bgzf as bgzf;
 use noodles_core::{region::Interval, Position};
-use noodles_csi::{binning_index::ReferenceSequenceExt, index::reference_sequence::Metadata};
+use noodles_csi::{
+    binning_index::ReferenceSequenceExt,
+    index::reference_sequence::{bin::Chunk, Metadata},
+};
 
 use super::{resolve_interval, MIN_SHIFT};
 
@@ -137,6 +140,10 @@ impl ReferenceSequence {
 }
 
 impl ReferenceSequenceExt for ReferenceSequence {
+    fn bin_chunks(&self) -> Box<dyn Iterator<Item = (usize, &[Chunk])> + '_> {
+        Box::new(self.bins().iter().map(|bin| (bin.id(), bin.chunks())))
+    }
+
     /// Returns the optional metadata for the reference sequence.
     ///
     /// Metadata is parsed from the optional pseudo-bin 37450.
@@ -184,6 +191,39 @@ impl ReferenceSequenceExt for ReferenceSequence {
     fn first_record_in_last_linear_bin_start_position(&self) -> Option<bgzf::VirtualPosition> {
         self.intervals().last().copied()
     }
+
+    /// Returns the linear index as a list of window indices and minimum offsets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::binning_index::ReferenceSequenceExt;
+    /// use noodles_bam::bai::index::ReferenceSequence;
+    ///
+    /// let intervals = vec![
+    ///     bgzf::VirtualPosition::from(8),
+    ///     bgzf::VirtualPosition::default(),
+    ///     bgzf::VirtualPosition::from(13),
+    /// ];
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), intervals, None);
+    ///
+    /// assert_eq!(
+    ///     reference_sequence.linear_index(14, 5),
+    ///     [
+    ///         (0, bgzf::VirtualPosition::from(8)),
+    ///         (2, bgzf::VirtualPosition::from(13)),
+    ///     ]
+    /// );
+    /// ```
+    fn linear_index(&self, _: u8, _: u8) -> Vec<(usize, bgzf::VirtualPosition)> {
+        self.intervals()
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, pos)| *pos != bgzf::VirtualPosition::default())
+            .collect()
+    }
 }
 
 fn region_to_bins(start: Position, end: Position) -> BitVec {
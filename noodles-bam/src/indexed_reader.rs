@@ -8,20 +8,24 @@ use std::io::{self, Read, Seek};
 
 use noodles_bgzf as bgzf;
 use noodles_core::Region;
+use noodles_csi::{self as csi, binning_index::verify};
 use noodles_sam::{alignment::Record, header::ReferenceSequences};
 
 use crate::reader::UnmappedRecords;
 
 use super::{
-    bai, lazy,
+    lazy,
     reader::{LazyRecords, Query, Records},
     Reader,
 };
 
 /// An indexed BAM reader.
+///
+/// The index can be either a BAM index (BAI) or a coordinate-sorted index (CSI). A BAI is
+/// converted to a CSI when the reader is created.
 pub struct IndexedReader<R> {
     inner: Reader<R>,
-    index: bai::Index,
+    index: csi::Index,
 }
 
 impl<R> IndexedReader<R>
@@ -43,6 +47,11 @@ where
         self.inner.into_inner()
     }
 
+    /// Returns the associated index.
+    pub fn index(&self) -> &csi::Index {
+        &self.index
+    }
+
     /// Reads the raw SAM header.
     pub fn read_header(&mut self) -> io::Result<String> {
         self.inner.read_header()
@@ -79,10 +88,23 @@ where
     R: Read,
 {
     /// Creates an indexed BAM reader.
-    pub fn new(inner: R, index: bai::Index) -> Self {
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, bai};
+    /// use noodles_csi as csi;
+    ///
+    /// let reader = bam::IndexedReader::new(&[][..], bai::Index::default());
+    /// let reader = bam::IndexedReader::new(&[][..], csi::Index::default());
+    /// ```
+    pub fn new<I>(inner: R, index: I) -> Self
+    where
+        I: Into<csi::Index>,
+    {
         Self {
             inner: Reader::new(inner),
-            index,
+            index: index.into(),
         }
     }
 }
@@ -100,8 +122,27 @@ where
         self.inner.query(reference_sequences, &self.index, region)
     }
 
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// See [`Reader::query_many`].
+    pub fn query_many(
+        &mut self,
+        reference_sequences: &ReferenceSequences,
+        regions: &[Region],
+    ) -> io::Result<Query<'_, R>> {
+        self.inner
+            .query_many(reference_sequences, &self.index, regions)
+    }
+
     /// Returns an iterator of unmapped records after querying for the unmapped region.
     pub fn query_unmapped(&mut self) -> io::Result<UnmappedRecords<'_, R>> {
         self.inner.query_unmapped(&self.index)
     }
+
+    /// Verifies the associated index against the records in the file.
+    ///
+    /// See [`Reader::verify_index`].
+    pub fn verify_index(&mut self) -> io::Result<Vec<verify::Error>> {
+        self.inner.verify_index(&self.index)
+    }
 }
@@ -6,6 +6,7 @@ use std::{
 };
 
 use noodles_bgzf as bgzf;
+use noodles_csi as csi;
 
 use super::IndexedReader;
 use crate::bai;
@@ -13,17 +14,24 @@ use crate::bai;
 /// An indexed BAM reader builder.
 #[derive(Default)]
 pub struct Builder {
-    index: Option<bai::Index>,
+    index: Option<csi::Index>,
 }
 
 impl Builder {
     /// Sets an index.
-    pub fn set_index(mut self, index: bai::Index) -> Self {
-        self.index = Some(index);
+    ///
+    /// This can be either a BAM index (BAI) or a coordinate-sorted index (CSI).
+    pub fn set_index<I>(mut self, index: I) -> Self
+    where
+        I: Into<csi::Index>,
+    {
+        self.index = Some(index.into());
         self
     }
 
     /// Builds an indexed BAM reader from a path.
+    ///
+    /// If no index is set, this reads `<src>.bai` or, if it does not exist, `<src>.csi`.
     pub fn build_from_path<P>(self, src: P) -> io::Result<IndexedReader<bgzf::Reader<File>>>
     where
         P: AsRef<Path>,
@@ -32,10 +40,7 @@ impl Builder {
 
         let index = match self.index {
             Some(index) => index,
-            None => {
-                let index_src = build_index_src(src);
-                bai::read(index_src)?
-            }
+            None => read_index(src)?,
         };
 
         let file = File::open(src)?;
@@ -44,6 +49,22 @@ impl Builder {
     }
 }
 
+fn read_index<P>(src: P) -> io::Result<csi::Index>
+where
+    P: AsRef<Path>,
+{
+    let src = src.as_ref();
+
+    let index_src = build_index_src(src);
+
+    if index_src.exists() {
+        return bai::read(index_src).map(csi::Index::from);
+    }
+
+    let index_src = build_csi_index_src(src);
+    csi::read(index_src)
+}
+
 fn build_index_src<P>(src: P) -> PathBuf
 where
     P: AsRef<Path>,
@@ -52,6 +73,14 @@ where
     push_ext(src.as_ref().into(), EXT)
 }
 
+fn build_csi_index_src<P>(src: P) -> PathBuf
+where
+    P: AsRef<Path>,
+{
+    const EXT: &str = "csi";
+    push_ext(src.as_ref().into(), EXT)
+}
+
 fn push_ext<S>(path: PathBuf, ext: S) -> PathBuf
 where
     S: AsRef<OsStr>,
@@ -70,4 +99,9 @@ mod tests {
     fn test_build_index_src() {
         assert_eq!(build_index_src("ref.fa"), PathBuf::from("ref.fa.bai"));
     }
+
+    #[test]
+    fn test_build_csi_index_src() {
+        assert_eq!(build_csi_index_src("ref.fa"), PathBuf::from("ref.fa.csi"));
+    }
 }
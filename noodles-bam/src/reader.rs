@@ -3,6 +3,7 @@
 mod lazy_records;
 pub(crate) mod query;
 pub mod record;
+mod record_boundary;
 mod records;
 mod unmapped_records;
 
@@ -11,14 +12,23 @@ pub use self::{
 };
 
 use std::{
+    collections::HashMap,
     ffi::CStr,
     io::{self, Read, Seek},
+    slice,
 };
 
 use byteorder::{LittleEndian, ReadBytesExt};
 use noodles_bgzf as bgzf;
-use noodles_core::Region;
-use noodles_csi::BinningIndex;
+use noodles_core::{region::IntervalSet, Region};
+use noodles_csi::{
+    binning_index::{
+        merge_chunks,
+        verify::{self, Verifier},
+    },
+    index::reference_sequence::bin::Chunk,
+    BinningIndex,
+};
 use noodles_fasta as fasta;
 use noodles_sam::{
     self as sam,
@@ -29,7 +39,7 @@ use noodles_sam::{
     },
 };
 
-use super::{bai, lazy, MAGIC_NUMBER};
+use super::{lazy, MAGIC_NUMBER};
 
 /// A BAM reader.
 ///
@@ -351,6 +361,94 @@ where
         self.inner.seek(pos)
     }
 
+    /// Seeks to the first record that starts in the block at the given compressed position.
+    ///
+    /// This allows reading to start at an arbitrary block, e.g., one found using
+    /// [`bgzf::split::find_next_block`], without an index. Each offset in the block is tried as
+    /// the start of a record, and a candidate is accepted when it and the records that follow
+    /// it have plausible fields. The reference sequence count is the number of reference
+    /// sequences in the header and is used to validate reference sequence IDs.
+    ///
+    /// The detection is heuristic. BAM records have no sync marker, so data that happens to look
+    /// like a sequence of valid records can be mistaken for a record start. The records that
+    /// follow a candidate are checked to make this unlikely, but it is not impossible.
+    ///
+    /// If a record start is found, this returns its virtual position, and the stream is
+    /// positioned at the start of the record. If no record starts in the block (e.g., the block
+    /// is in the middle of a large record or is the EOF block), this returns `None`, and the
+    /// position of the stream is undefined.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io, num::NonZeroUsize};
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+    /// let header: sam::Header = reader.read_header()?.parse().unwrap();
+    /// let reference_sequence_count = header.reference_sequences().len();
+    ///
+    /// // Use a separate handle, as seeking the reader's underlying stream would desynchronize
+    /// // the reader.
+    /// let mut file = File::open("sample.bam")?;
+    /// let count = NonZeroUsize::new(4).unwrap();
+    /// let ranges = bgzf::split::byte_ranges(&mut file, count)?;
+    ///
+    /// if let Some(range) = ranges.get(1) {
+    ///     if reader
+    ///         .seek_to_first_record_in_block(range.start, reference_sequence_count)?
+    ///         .is_some()
+    ///     {
+    ///         // Read records while `reader.virtual_position().compressed() < range.end`.
+    ///     }
+    /// }
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn seek_to_first_record_in_block(
+        &mut self,
+        compressed_position: u64,
+        reference_sequence_count: usize,
+    ) -> io::Result<Option<bgzf::VirtualPosition>> {
+        use std::io::BufRead;
+
+        // The amount of data past the block used to check the records that follow a candidate.
+        const LOOKAHEAD_SIZE: u64 = 1 << 20;
+
+        let block_start = bgzf::VirtualPosition::try_from((compressed_position, 0))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        self.seek(block_start)?;
+
+        let block_len = self.inner.fill_buf()?.len();
+
+        if block_len == 0 {
+            return Ok(None);
+        }
+
+        let limit = block_len as u64 + LOOKAHEAD_SIZE;
+        let mut buf = Vec::new();
+        (&mut self.inner).take(limit).read_to_end(&mut buf)?;
+        let is_eof = (buf.len() as u64) < limit;
+
+        let offset = match record_boundary::find_record_start(
+            &buf,
+            block_len,
+            reference_sequence_count,
+            is_eof,
+        ) {
+            Some(i) => i,
+            None => return Ok(None),
+        };
+
+        // SAFETY: `offset` < `block_len` <= `u16::MAX` + 1.
+        let pos = bgzf::VirtualPosition::try_from((compressed_position, offset as u16))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        self.seek(pos).map(Some)
+    }
+
     // Seeks to the first record by setting the cursor to the beginning of the stream and
     // (re)reading the header and binary reference sequences.
     fn seek_to_first_record(&mut self) -> io::Result<bgzf::VirtualPosition> {
@@ -393,16 +491,68 @@ where
     where
         I: BinningIndex,
     {
-        let reference_sequence_id = resolve_region(reference_sequences, region)?;
+        self.query_many(reference_sequences, index, slice::from_ref(region))
+    }
 
-        let chunks = index.query(reference_sequence_id, region.interval())?;
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// The chunks of all regions are merged, so each part of the file is read at most once.
+    /// Records are returned in file order, and a record that intersects more than one region is
+    /// only returned once.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bam::{self as bam, bai};
+    /// use noodles_core::Region;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+    /// let header: sam::Header = reader.read_header()?.parse()?;
+    ///
+    /// let reference_sequences = header.reference_sequences();
+    /// let index = bai::read("sample.bam.bai")?;
+    /// let regions = ["sq0:8-13".parse()?, "sq0:21-34".parse()?];
+    /// let query = reader.query_many(reference_sequences, &index, &regions)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_many<I>(
+        &mut self,
+        reference_sequences: &ReferenceSequences,
+        index: &I,
+        regions: &[Region],
+    ) -> io::Result<Query<'_, R>>
+    where
+        I: BinningIndex,
+    {
+        let mut interval_sets: HashMap<usize, IntervalSet> = HashMap::new();
 
-        Ok(Query::new(
-            self,
-            chunks,
-            reference_sequence_id,
-            region.interval(),
-        ))
+        for region in regions {
+            let reference_sequence_id = resolve_region(reference_sequences, region)?;
+
+            interval_sets
+                .entry(reference_sequence_id)
+                .or_default()
+                .insert(region.interval());
+        }
+
+        let mut chunks = Vec::new();
+
+        for (&reference_sequence_id, interval_set) in &interval_sets {
+            for interval in interval_set.iter() {
+                chunks.extend(index.query(reference_sequence_id, interval)?);
+            }
+        }
+
+        let chunks = merge_chunks(&chunks);
+
+        Ok(Query::new(self, chunks, interval_sets))
     }
 
     /// Returns an iterator of unmapped records after querying for the unmapped region.
@@ -423,7 +573,10 @@ where
     /// }
     /// # Ok::<(), io::Error>(())
     /// ```
-    pub fn query_unmapped(&mut self, index: &bai::Index) -> io::Result<UnmappedRecords<'_, R>> {
+    pub fn query_unmapped<I>(&mut self, index: &I) -> io::Result<UnmappedRecords<'_, R>>
+    where
+        I: BinningIndex,
+    {
         if let Some(pos) = index.first_record_in_last_linear_bin_start_position() {
             self.seek(pos)?;
         } else {
@@ -432,6 +585,61 @@ where
 
         Ok(UnmappedRecords::new(self))
     }
+
+    /// Verifies an index against the records in the file.
+    ///
+    /// This scans every record from the start of the file, so the header and reference
+    /// sequences do not need to be read first. See [`noodles_csi::binning_index::verify`] for
+    /// what is checked.
+    ///
+    /// An empty list means the index is consistent with the data.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bam::{self as bam, bai};
+    ///
+    /// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+    /// let index = bai::read("sample.bam.bai")?;
+    ///
+    /// for error in reader.verify_index(&index)? {
+    ///     eprintln!("{}", error);
+    /// }
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn verify_index<I>(&mut self, index: &I) -> io::Result<Vec<verify::Error>>
+    where
+        I: BinningIndex,
+    {
+        self.seek_to_first_record()?;
+
+        let mut verifier = Verifier::new(index);
+        let mut record = Record::default();
+        let mut start_position = self.virtual_position();
+
+        while self.read_record(&mut record)? != 0 {
+            let end_position = self.virtual_position();
+            let chunk = Chunk::new(start_position, end_position);
+
+            let alignment_context = match (
+                record.reference_sequence_id(),
+                record.alignment_start(),
+                record.alignment_end(),
+            ) {
+                (Some(id), Some(start), Some(end)) => {
+                    Some((id, start, end, !record.flags().is_unmapped()))
+                }
+                _ => None,
+            };
+
+            verifier.add_record(alignment_context, chunk)?;
+
+            start_position = end_position;
+        }
+
+        Ok(verifier.finish())
+    }
 }
 
 impl<R> From<R> for Reader<R> {
@@ -587,6 +795,7 @@ mod tests {
     use noodles_sam as sam;
 
     use super::*;
+    use crate::bai;
 
     #[test]
     fn test_read_magic() -> io::Result<()> {
@@ -611,6 +820,129 @@ mod tests {
         Ok(())
     }
 
+    fn build_data(
+        records: &[(usize, &str)],
+    ) -> Result<(sam::Header, Vec<u8>), Box<dyn std::error::Error>> {
+        use std::io::Write;
+
+        use noodles_core::Position;
+
+        use crate::Writer;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 100)?)
+            .build();
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        for &(start, cigar) in records {
+            let record = Record::builder()
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(start)?)
+                .set_cigar(cigar.parse()?)
+                .build();
+
+            writer.write_record(&header, &record)?;
+            writer.get_mut().flush()?;
+        }
+
+        let data = writer.into_inner().finish()?;
+
+        Ok((header, data))
+    }
+
+    fn build_index<R>(
+        reader: &mut Reader<bgzf::Reader<R>>,
+        reference_sequence_count: usize,
+    ) -> io::Result<bai::Index>
+    where
+        R: Read + Seek,
+    {
+        reader.seek_to_first_record()?;
+
+        let mut builder = bai::Index::builder();
+        let mut record = Record::default();
+        let mut start_position = reader.virtual_position();
+
+        while reader.read_record(&mut record)? != 0 {
+            let end_position = reader.virtual_position();
+            builder.add_record(&record, Chunk::new(start_position, end_position))?;
+            start_position = end_position;
+        }
+
+        Ok(builder.build(reference_sequence_count))
+    }
+
+    #[test]
+    fn test_query_many() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Cursor;
+
+        use noodles_csi as csi;
+
+        let (header, data) = build_data(&[(1, "10M"), (5, "20M"), (30, "10M")])?;
+
+        let mut reader = Reader::new(Cursor::new(data));
+        let index = build_index(&mut reader, header.reference_sequences().len())?;
+
+        let regions = ["sq0:1-6".parse()?, "sq0:6-35".parse()?];
+        let starts: Vec<_> = reader
+            .query_many(header.reference_sequences(), &index, &regions)?
+            .map(|result| result.map(|r| r.alignment_start().map(usize::from)))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(starts, [Some(1), Some(5), Some(30)]);
+
+        let index = csi::Index::from(index);
+
+        let starts: Vec<_> = reader
+            .query_many(header.reference_sequences(), &index, &regions)?
+            .map(|result| result.map(|r| r.alignment_start().map(usize::from)))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(starts, [Some(1), Some(5), Some(30)]);
+
+        let regions = ["sq0:36-40".parse()?, "sq0:1-2".parse()?];
+        let starts: Vec<_> = reader
+            .query_many(header.reference_sequences(), &index, &regions)?
+            .map(|result| result.map(|r| r.alignment_start().map(usize::from)))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(starts, [Some(1), Some(30)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_index() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Cursor;
+
+        let (header, data) = build_data(&[(1, "10M"), (5, "20M"), (30, "10M")])?;
+        let mut reader = Reader::new(Cursor::new(data));
+        let index = build_index(&mut reader, header.reference_sequences().len())?;
+
+        assert!(reader.verify_index(&index)?.is_empty());
+
+        // The index is stale, i.e., it was built before the file was rewritten.
+        let (_, data) = build_data(&[(1, "10M"), (30, "10M")])?;
+        let mut reader = Reader::new(Cursor::new(data));
+
+        let errors = reader.verify_index(&index)?;
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, verify::Error::InvalidChunkBoundary { .. })));
+        assert!(
+            errors.contains(&verify::Error::UnmappedRecordCountMismatch {
+                reference_sequence_id: 0,
+                expected: 3,
+                actual: 2,
+            })
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_header() -> io::Result<()> {
         let expected = "@HD\tVN:1.6\n";
@@ -1,10 +1,11 @@
 use std::{
+    collections::HashMap,
     io::{self, Read, Seek},
     vec,
 };
 
 use noodles_bgzf::{self as bgzf, VirtualPosition};
-use noodles_core::region::Interval;
+use noodles_core::region::{Interval, IntervalSet};
 use noodles_csi::index::reference_sequence::bin::Chunk;
 use noodles_sam::alignment::Record;
 
@@ -18,7 +19,7 @@ enum State {
 
 /// An iterator over records of a BAM reader that intersects a given region.
 ///
-/// This is created by calling [`Reader::query`].
+/// This is created by calling [`Reader::query`] or [`Reader::query_many`].
 pub struct Query<'a, R>
 where
     R: Read + Seek,
@@ -27,8 +28,7 @@ where
 
     chunks: vec::IntoIter<Chunk>,
 
-    reference_sequence_id: usize,
-    interval: Interval,
+    interval_sets: HashMap<usize, IntervalSet>,
 
     state: State,
     record: Record,
@@ -41,16 +41,14 @@ where
     pub(super) fn new(
         reader: &'a mut Reader<bgzf::Reader<R>>,
         chunks: Vec<Chunk>,
-        reference_sequence_id: usize,
-        interval: Interval,
+        interval_sets: HashMap<usize, IntervalSet>,
     ) -> Self {
         Self {
             reader,
 
             chunks: chunks.into_iter(),
 
-            reference_sequence_id,
-            interval,
+            interval_sets,
 
             state: State::Seek,
             record: Record::default(),
@@ -92,7 +90,7 @@ where
                             self.state = State::Seek;
                         }
 
-                        if intersects(&record, self.reference_sequence_id, self.interval) {
+                        if intersects_any(&record, &self.interval_sets) {
                             return Some(Ok(record));
                         }
                     }
@@ -105,20 +103,37 @@ where
     }
 }
 
+fn intersects_any(record: &Record, interval_sets: &HashMap<usize, IntervalSet>) -> bool {
+    match alignment_interval(record) {
+        Some((id, alignment_interval)) => interval_sets
+            .get(&id)
+            .map(|interval_set| interval_set.intersects(alignment_interval))
+            .unwrap_or(false),
+        None => false,
+    }
+}
+
+#[cfg(feature = "async")]
 pub(crate) fn intersects(
     record: &Record,
     reference_sequence_id: usize,
     region_interval: Interval,
 ) -> bool {
+    match alignment_interval(record) {
+        Some((id, alignment_interval)) => {
+            id == reference_sequence_id && region_interval.intersects(alignment_interval)
+        }
+        None => false,
+    }
+}
+
+fn alignment_interval(record: &Record) -> Option<(usize, Interval)> {
     match (
         record.reference_sequence_id(),
         record.alignment_start(),
         record.alignment_end(),
     ) {
-        (Some(id), Some(start), Some(end)) => {
-            let alignment_interval = (start..=end).into();
-            id == reference_sequence_id && region_interval.intersects(alignment_interval)
-        }
-        _ => false,
+        (Some(id), Some(start), Some(end)) => Some((id, (start..=end).into())),
+        _ => None,
     }
 }
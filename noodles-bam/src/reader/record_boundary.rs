@@ -0,0 +1,225 @@
+//! BAM record boundary detection.
+//!
+//! This finds the start of a record in uncompressed BAM data without any prior position
+//! information, e.g., at the start of an arbitrary BGZF block. It is similar to the split guesser
+//! used by Hadoop-BAM: each offset in the block is tried as a record start, and a candidate is
+//! accepted when it and the records that follow it have plausible fields.
+
+use std::mem;
+
+// refID (4) + pos (4) + l_read_name (1) + mapq (1) + bin (2) + n_cigar_op (2) + flag (2) + l_seq (4)
+// + next_refID (4) + next_pos (4) + tlen (4)
+const FIXED_FIELDS_SIZE: usize = 32;
+
+// The number of consecutive records that must be valid to accept a candidate.
+const MIN_RECORD_COUNT: usize = 4;
+
+// The largest CIGAR operation kind (`X`).
+const MAX_CIGAR_OP_KIND: u32 = 8;
+
+const UNMAPPED: i32 = -1;
+
+enum Check {
+    Valid(usize),
+    Invalid,
+    Incomplete,
+}
+
+/// Finds the offset of the first record that starts in `buf[..block_len]`.
+///
+/// `buf` is the uncompressed data starting at a block boundary and may extend past the block to
+/// allow following records to be checked. If `is_eof` is set, `buf` ends at the end of the stream.
+pub(crate) fn find_record_start(
+    buf: &[u8],
+    block_len: usize,
+    reference_sequence_count: usize,
+    is_eof: bool,
+) -> Option<usize> {
+    (0..block_len.min(buf.len()))
+        .find(|&i| is_record_start(buf, i, reference_sequence_count, is_eof))
+}
+
+fn is_record_start(
+    buf: &[u8],
+    mut offset: usize,
+    reference_sequence_count: usize,
+    is_eof: bool,
+) -> bool {
+    let mut record_count = 0;
+
+    while record_count < MIN_RECORD_COUNT {
+        if offset == buf.len() {
+            return record_count > 0 && is_eof;
+        }
+
+        match check_record(&buf[offset..], reference_sequence_count) {
+            Check::Valid(len) => {
+                offset += len;
+                record_count += 1;
+            }
+            Check::Invalid => return false,
+            Check::Incomplete => return record_count > 0 && !is_eof,
+        }
+    }
+
+    true
+}
+
+fn check_record(src: &[u8], reference_sequence_count: usize) -> Check {
+    if src.len() < mem::size_of::<u32>() + FIXED_FIELDS_SIZE {
+        return Check::Incomplete;
+    }
+
+    let block_size = read_u32(src, 0) as usize;
+
+    let reference_sequence_id = read_i32(src, 4);
+    let position = read_i32(src, 8);
+    let l_read_name = usize::from(src[12]);
+    let n_cigar_op = usize::from(read_u16(src, 16));
+    let l_seq = read_u32(src, 20) as usize;
+    let mate_reference_sequence_id = read_i32(src, 24);
+    let mate_position = read_i32(src, 28);
+
+    if !is_valid_reference_sequence_id(reference_sequence_id, reference_sequence_count)
+        || !is_valid_reference_sequence_id(mate_reference_sequence_id, reference_sequence_count)
+        || position < UNMAPPED
+        || mate_position < UNMAPPED
+        || l_read_name == 0
+    {
+        return Check::Invalid;
+    }
+
+    let min_block_size = FIXED_FIELDS_SIZE
+        .checked_add(l_read_name)
+        .and_then(|n| n.checked_add(n_cigar_op * mem::size_of::<u32>()))
+        .and_then(|n| n.checked_add((l_seq + 1) / 2))
+        .and_then(|n| n.checked_add(l_seq));
+
+    match min_block_size {
+        Some(n) if block_size >= n => {}
+        _ => return Check::Invalid,
+    }
+
+    let record_len = mem::size_of::<u32>() + block_size;
+
+    if src.len() < record_len {
+        return Check::Incomplete;
+    }
+
+    let read_name_start = mem::size_of::<u32>() + FIXED_FIELDS_SIZE;
+    let read_name_end = read_name_start + l_read_name;
+
+    if !is_valid_read_name(&src[read_name_start..read_name_end]) {
+        return Check::Invalid;
+    }
+
+    let cigar_end = read_name_end + n_cigar_op * mem::size_of::<u32>();
+
+    for raw_op in src[read_name_end..cigar_end].chunks_exact(mem::size_of::<u32>()) {
+        let n = u32::from_le_bytes([raw_op[0], raw_op[1], raw_op[2], raw_op[3]]);
+
+        if n & 0x0f > MAX_CIGAR_OP_KIND {
+            return Check::Invalid;
+        }
+    }
+
+    Check::Valid(record_len)
+}
+
+fn is_valid_reference_sequence_id(n: i32, reference_sequence_count: usize) -> bool {
+    n == UNMAPPED || usize::try_from(n).map_or(false, |m| m < reference_sequence_count)
+}
+
+// § 1.4 "The alignment section: mandatory fields" (2021-06-03): QNAME matches
+// `[!-?A-~]{1,254}`. The raw read name is NUL-terminated.
+fn is_valid_read_name(buf: &[u8]) -> bool {
+    match buf.split_last() {
+        Some((0, name)) => {
+            !name.is_empty() && name.iter().all(|&b| matches!(b, b'!'..=b'?' | b'A'..=b'~'))
+        }
+        _ => false,
+    }
+}
+
+fn read_u16(src: &[u8], i: usize) -> u16 {
+    u16::from_le_bytes([src[i], src[i + 1]])
+}
+
+fn read_u32(src: &[u8], i: usize) -> u32 {
+    u32::from_le_bytes([src[i], src[i + 1], src[i + 2], src[i + 3]])
+}
+
+fn read_i32(src: &[u8], i: usize) -> i32 {
+    i32::from_le_bytes([src[i], src[i + 1], src[i + 2], src[i + 3]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_record(read_name: &[u8]) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        let block_size = (FIXED_FIELDS_SIZE + read_name.len() + 1 + 4) as u32;
+        buf.extend_from_slice(&block_size.to_le_bytes());
+        buf.extend_from_slice(&0i32.to_le_bytes()); // refID = 0
+        buf.extend_from_slice(&7i32.to_le_bytes()); // pos = 7
+        buf.push((read_name.len() + 1) as u8); // l_read_name
+        buf.push(12); // mapq = 12
+        buf.extend_from_slice(&4680u16.to_le_bytes()); // bin = 4680
+        buf.extend_from_slice(&1u16.to_le_bytes()); // n_cigar_op = 1
+        buf.extend_from_slice(&0u16.to_le_bytes()); // flag = 0
+        buf.extend_from_slice(&0u32.to_le_bytes()); // l_seq = 0
+        buf.extend_from_slice(&(-1i32).to_le_bytes()); // next_refID = -1
+        buf.extend_from_slice(&(-1i32).to_le_bytes()); // next_pos = -1
+        buf.extend_from_slice(&0i32.to_le_bytes()); // tlen = 0
+        buf.extend_from_slice(read_name);
+        buf.push(0x00);
+        buf.extend_from_slice(&0x40u32.to_le_bytes()); // cigar = 4M
+
+        buf
+    }
+
+    #[test]
+    fn test_find_record_start() {
+        let records: Vec<u8> = (0..8)
+            .flat_map(|i| build_record(format!("r{}", i).as_bytes()))
+            .collect();
+
+        // Simulate a block that starts in the middle of a record.
+        let offset = 13;
+        let buf = &records[offset..];
+        let record_len = build_record(b"r0").len();
+
+        assert_eq!(
+            find_record_start(buf, buf.len(), 1, true),
+            Some(record_len - offset)
+        );
+
+        assert_eq!(find_record_start(&records, records.len(), 1, true), Some(0));
+
+        // The reference sequence ID is out of range.
+        assert!(find_record_start(&records, records.len(), 0, true).is_none());
+    }
+
+    #[test]
+    fn test_find_record_start_with_incomplete_data() {
+        let mut buf = build_record(b"r0");
+        buf.extend(build_record(b"r1"));
+        buf.truncate(buf.len() - 1);
+
+        assert_eq!(find_record_start(&buf, buf.len(), 1, false), Some(0));
+        assert!(find_record_start(&buf, buf.len(), 1, true).is_none());
+    }
+
+    #[test]
+    fn test_is_valid_read_name() {
+        assert!(is_valid_read_name(b"r0\x00"));
+        assert!(is_valid_read_name(b"*\x00"));
+        assert!(!is_valid_read_name(b""));
+        assert!(!is_valid_read_name(b"\x00"));
+        assert!(!is_valid_read_name(b"r0"));
+        assert!(!is_valid_read_name(b"r 0\x00"));
+        assert!(!is_valid_read_name(b"@r0\x00"));
+    }
+}
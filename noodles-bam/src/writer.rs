@@ -5,6 +5,7 @@ pub mod record;
 use std::{
     ffi::CString,
     io::{self, Write},
+    mem,
 };
 
 use byteorder::{LittleEndian, WriteBytesExt};
@@ -155,12 +156,16 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn write_record(&mut self, header: &sam::Header, record: &Record) -> io::Result<()> {
+        const BLOCK_SIZE_LEN: usize = mem::size_of::<u32>();
+
+        // See `bgzf::writer::Builder::set_aligned_writes`.
         self.buf.clear();
+        self.buf.resize(BLOCK_SIZE_LEN, 0);
         encode_record(&mut self.buf, header, record)?;
 
-        let block_size = u32::try_from(self.buf.len())
+        let block_size = u32::try_from(self.buf.len() - BLOCK_SIZE_LEN)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
-        self.inner.write_u32::<LittleEndian>(block_size)?;
+        self.buf[..BLOCK_SIZE_LEN].copy_from_slice(&block_size.to_le_bytes());
 
         self.inner.write_all(&self.buf)?;
 
@@ -522,6 +527,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_write_record_with_aligned_bgzf_writer() -> Result<(), Box<dyn std::error::Error>> {
+        let bgzf_writer = bgzf::writer::Builder::default()
+            .set_aligned_writes(true)
+            .set_gzi_index_enabled(true)
+            .build_with_writer(Vec::new());
+
+        let mut writer = Writer::from(bgzf_writer);
+
+        let header = sam::Header::default();
+        let record = Record::builder()
+            .set_sequence("ACGT".repeat(250).parse()?)
+            .build();
+
+        writer.write_record(&header, &record)?;
+        let record_len = writer.get_ref().virtual_position().uncompressed();
+
+        for _ in 0..127 {
+            writer.write_record(&header, &record)?;
+        }
+
+        writer.try_finish()?;
+
+        let index = writer.get_ref().gzi_index().ok_or("missing index")?;
+        assert!(index.len() > 2);
+
+        for &(_, uncompressed_position) in index {
+            assert_eq!(uncompressed_position % u64::from(record_len), 0);
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_write_reference_sequence() -> Result<(), Box<dyn std::error::Error>> {
         let mut buf = Vec::new();
@@ -9,14 +9,16 @@ pub(crate) mod value;
 pub use self::{query::Query, records::Records};
 
 use std::{
+    collections::HashMap,
     ffi::CStr,
     io::{self, Read, Seek},
+    slice,
 };
 
 use byteorder::{LittleEndian, ReadBytesExt};
 use noodles_bgzf as bgzf;
-use noodles_core::Region;
-use noodles_csi::BinningIndex;
+use noodles_core::{region::IntervalSet, Region};
+use noodles_csi::{binning_index::merge_chunks, BinningIndex};
 
 use super::Record;
 use crate::header::string_maps::ContigStringMap;
@@ -241,7 +243,7 @@ where
     /// ```no_run
     /// # use std::fs::File;
     /// use noodles_bcf::{self as bcf, header::StringMaps};
-    /// use noodles_core::Region;
+    /// use noodles_core::{region::IntervalSet, Region};
     /// use noodles_csi as csi;
     ///
     /// let mut reader = File::open("sample.bcf").map(bcf::Reader::new)?;
@@ -268,15 +270,69 @@ where
     where
         I: BinningIndex,
     {
-        let reference_sequence_id = resolve_region(contig_string_map, region)?;
-        let chunks = index.query(reference_sequence_id, region.interval())?;
-
-        Ok(Query::new(
-            self,
-            chunks,
-            reference_sequence_id,
-            region.interval(),
-        ))
+        self.query_many(contig_string_map, index, slice::from_ref(region))
+    }
+
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// The chunks of all regions are merged, so each part of the file is read at most once.
+    /// Records are returned in file order, and a record that intersects more than one region is
+    /// only returned once.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bcf::{self as bcf, header::StringMaps};
+    /// use noodles_core::{region::IntervalSet, Region};
+    /// use noodles_csi as csi;
+    ///
+    /// let mut reader = File::open("sample.bcf").map(bcf::Reader::new)?;
+    /// reader.read_file_format()?;
+    ///
+    /// let string_maps: StringMaps = reader.read_header()?.parse()?;
+    ///
+    /// let index = csi::read("sample.bcf.csi")?;
+    /// let regions = ["sq0:8-13".parse()?, "sq0:21-34".parse()?];
+    /// let query = reader.query_many(string_maps.contigs(), &index, &regions)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_many<I>(
+        &mut self,
+        contig_string_map: &ContigStringMap,
+        index: &I,
+        regions: &[Region],
+    ) -> io::Result<Query<'_, R>>
+    where
+        I: BinningIndex,
+    {
+        let mut interval_sets: HashMap<usize, IntervalSet> = HashMap::new();
+
+        for region in regions {
+            let reference_sequence_id = resolve_region(contig_string_map, region)?;
+
+            interval_sets
+                .entry(reference_sequence_id)
+                .or_default()
+                .insert(region.interval());
+        }
+
+        let mut chunks = Vec::new();
+
+        for (&reference_sequence_id, interval_set) in &interval_sets {
+            for interval in interval_set.iter() {
+                chunks.extend(index.query(reference_sequence_id, interval)?);
+            }
+        }
+
+        let chunks = merge_chunks(&chunks);
+
+        Ok(Query::new(self, chunks, interval_sets))
     }
 }
 
@@ -1,10 +1,14 @@
 use std::{
+    collections::HashMap,
     io::{self, Read, Seek},
     vec,
 };
 
 use noodles_bgzf as bgzf;
-use noodles_core::{region::Interval, Position};
+use noodles_core::{
+    region::{Interval, IntervalSet},
+    Position,
+};
 use noodles_csi::index::reference_sequence::bin::Chunk;
 
 use crate::Record;
@@ -19,7 +23,7 @@ enum State {
 
 /// An iterator over records of a BCF reader that intersects a given region.
 ///
-/// This is created by calling [`Reader::query`].
+/// This is created by calling [`Reader::query`] or [`Reader::query_many`].
 pub struct Query<'a, R>
 where
     R: Read + Seek,
@@ -28,8 +32,7 @@ where
 
     chunks: vec::IntoIter<Chunk>,
 
-    chromosome_id: usize,
-    interval: Interval,
+    interval_sets: HashMap<usize, IntervalSet>,
 
     state: State,
     record: Record,
@@ -42,16 +45,14 @@ where
     pub(crate) fn new(
         reader: &'a mut Reader<bgzf::Reader<R>>,
         chunks: Vec<Chunk>,
-        chromosome_id: usize,
-        interval: Interval,
+        interval_sets: HashMap<usize, IntervalSet>,
     ) -> Self {
         Self {
             reader,
 
             chunks: chunks.into_iter(),
 
-            chromosome_id,
-            interval,
+            interval_sets,
 
             state: State::Seek,
             record: Record::default(),
@@ -93,7 +94,7 @@ where
                             self.state = State::Seek;
                         }
 
-                        match intersects(&record, self.chromosome_id, self.interval) {
+                        match intersects_any(&record, &self.interval_sets) {
                             Ok(true) => return Some(Ok(record)),
                             Ok(false) => {}
                             Err(e) => return Some(Err(e)),
@@ -108,13 +109,30 @@ where
     }
 }
 
+fn intersects_any(
+    record: &Record,
+    interval_sets: &HashMap<usize, IntervalSet>,
+) -> io::Result<bool> {
+    let id = record.chromosome_id();
+
+    match interval_sets.get(&id) {
+        Some(interval_set) => record_interval(record).map(|i| interval_set.intersects(i)),
+        None => Ok(false),
+    }
+}
+
+#[cfg(feature = "async")]
 pub(crate) fn intersects(
     record: &Record,
     chromosome_id: usize,
     region_interval: Interval,
 ) -> io::Result<bool> {
     let id = record.chromosome_id();
+    let record_interval = record_interval(record)?;
+    Ok(id == chromosome_id && record_interval.intersects(region_interval))
+}
 
+fn record_interval(record: &Record) -> io::Result<Interval> {
     let start = Position::try_from(usize::from(record.position()))
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
 
@@ -122,7 +140,5 @@ pub(crate) fn intersects(
         Position::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     })?;
 
-    let record_interval = Interval::from(start..=end);
-
-    Ok(id == chromosome_id && record_interval.intersects(region_interval))
+    Ok(Interval::from(start..=end))
 }
@@ -20,6 +20,7 @@ const MINOR: u8 = 2;
 /// A BCF writer.
 pub struct Writer<W> {
     inner: W,
+    buf: Vec<u8>,
 }
 
 impl<W> Writer<W>
@@ -112,7 +113,10 @@ where
     /// # Ok::<_, io::Error>(())
     /// ```
     pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
-        record::write_record(&mut self.inner, record)
+        // See `bgzf::writer::Builder::set_aligned_writes`.
+        self.buf.clear();
+        record::write_record(&mut self.buf, record)?;
+        self.inner.write_all(&self.buf)
     }
 
     /// Writes a VCF record.
@@ -152,7 +156,9 @@ where
         string_maps: &StringMaps,
         record: &vcf::Record,
     ) -> io::Result<()> {
-        vcf_record::write_vcf_record(&mut self.inner, header, string_maps, record)
+        self.buf.clear();
+        vcf_record::write_vcf_record(&mut self.buf, header, string_maps, record)?;
+        self.inner.write_all(&self.buf)
     }
 }
 
@@ -195,7 +201,10 @@ where
 
 impl<W> From<W> for Writer<W> {
     fn from(inner: W) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            buf: Vec::new(),
+        }
     }
 }
 
@@ -16,7 +16,11 @@ use std::{
     str::FromStr,
 };
 
-use noodles_core::Position;
+use noodles_core::{
+    position::ZeroBasedPosition,
+    region::{HalfOpenInterval, Interval},
+    Position,
+};
 
 const DELIMITER: char = '\t';
 const MISSING_STRING: &str = ".";
@@ -198,6 +202,29 @@ where
         &self.standard_fields.reference_sequence_name
     }
 
+    /// Returns a mutable reference to the reference sequence name (`chrom`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// use noodles_core::Position;
+    ///
+    /// let mut record = bed::Record::<3>::builder()
+    ///     .set_reference_sequence_name("sq0")
+    ///     .set_start_position(Position::try_from(8)?)
+    ///     .set_end_position(Position::try_from(13)?)
+    ///     .build()?;
+    ///
+    /// *record.reference_sequence_name_mut() = String::from("sq1");
+    ///
+    /// assert_eq!(record.reference_sequence_name(), "sq1");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn reference_sequence_name_mut(&mut self) -> &mut String {
+        &mut self.standard_fields.reference_sequence_name
+    }
+
     /// Returns the feature start position (`chromStart`).
     ///
     /// # Examples
@@ -221,6 +248,30 @@ where
         self.standard_fields.start_position
     }
 
+    /// Returns a mutable reference to the feature start position (`chromStart`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// use noodles_core::Position;
+    ///
+    /// let mut record = bed::Record::<3>::builder()
+    ///     .set_reference_sequence_name("sq0")
+    ///     .set_start_position(Position::try_from(8)?)
+    ///     .set_end_position(Position::try_from(13)?)
+    ///     .build()?;
+    ///
+    /// let start_position = Position::try_from(5)?;
+    /// *record.start_position_mut() = start_position;
+    ///
+    /// assert_eq!(record.start_position(), start_position);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn start_position_mut(&mut self) -> &mut Position {
+        &mut self.standard_fields.start_position
+    }
+
     /// Returns the feature end position (`chromEnd`).
     ///
     /// # Examples
@@ -244,6 +295,58 @@ where
         self.standard_fields.end_position
     }
 
+    /// Returns a mutable reference to the feature end position (`chromEnd`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// use noodles_core::Position;
+    ///
+    /// let mut record = bed::Record::<3>::builder()
+    ///     .set_reference_sequence_name("sq0")
+    ///     .set_start_position(Position::try_from(8)?)
+    ///     .set_end_position(Position::try_from(13)?)
+    ///     .build()?;
+    ///
+    /// let end_position = Position::try_from(21)?;
+    /// *record.end_position_mut() = end_position;
+    ///
+    /// assert_eq!(record.end_position(), end_position);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn end_position_mut(&mut self) -> &mut Position {
+        &mut self.standard_fields.end_position
+    }
+
+    /// Returns the feature interval as a 0-based, half-open interval ([`chromStart`, `chromEnd`)).
+    ///
+    /// This is the interval as it is written in a BED file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// use noodles_core::{position::ZeroBasedPosition, region::HalfOpenInterval, Position};
+    ///
+    /// let record = bed::Record::<3>::builder()
+    ///     .set_reference_sequence_name("sq0")
+    ///     .set_start_position(Position::try_from(8)?)
+    ///     .set_end_position(Position::try_from(13)?)
+    ///     .build()?;
+    ///
+    /// assert_eq!(
+    ///     record.interval(),
+    ///     HalfOpenInterval::new(ZeroBasedPosition::try_from(7)?, ZeroBasedPosition::try_from(13)?)
+    /// );
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn interval(&self) -> HalfOpenInterval {
+        let start = self.start_position();
+        let end = self.end_position();
+        HalfOpenInterval::from(Interval::from(start..=end))
+    }
+
     /// Returns the list of raw optional fields.
     ///
     /// # Examples
@@ -347,6 +450,30 @@ where
     pub fn strand(&self) -> Option<Strand> {
         self.standard_fields.strand
     }
+
+    /// Returns a mutable reference to the feature strand (`strand`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::Strand};
+    /// use noodles_core::Position;
+    ///
+    /// let mut record = bed::Record::<6>::builder()
+    ///     .set_reference_sequence_name("sq0")
+    ///     .set_start_position(Position::try_from(8)?)
+    ///     .set_end_position(Position::try_from(13)?)
+    ///     .set_strand(Strand::Forward)
+    ///     .build()?;
+    ///
+    /// *record.strand_mut() = Some(Strand::Reverse);
+    ///
+    /// assert_eq!(record.strand(), Some(Strand::Reverse));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn strand_mut(&mut self) -> &mut Option<Strand> {
+        &mut self.standard_fields.strand
+    }
 }
 
 impl<const N: u8> Record<N>
@@ -376,6 +503,30 @@ where
     pub fn thick_start(&self) -> Position {
         self.standard_fields.thick_start
     }
+
+    /// Returns a mutable reference to the thick start position (`thickStart`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// use noodles_core::Position;
+    ///
+    /// let mut record = bed::Record::<7>::builder()
+    ///     .set_reference_sequence_name("sq0")
+    ///     .set_start_position(Position::try_from(8)?)
+    ///     .set_end_position(Position::try_from(13)?)
+    ///     .build()?;
+    ///
+    /// let thick_start = Position::try_from(10)?;
+    /// *record.thick_start_mut() = thick_start;
+    ///
+    /// assert_eq!(record.thick_start(), thick_start);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn thick_start_mut(&mut self) -> &mut Position {
+        &mut self.standard_fields.thick_start
+    }
 }
 
 impl<const N: u8> Record<N>
@@ -405,6 +556,30 @@ where
     pub fn thick_end(&self) -> Position {
         self.standard_fields.thick_end
     }
+
+    /// Returns a mutable reference to the thick end position (`thickEnd`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// use noodles_core::Position;
+    ///
+    /// let mut record = bed::Record::<8>::builder()
+    ///     .set_reference_sequence_name("sq0")
+    ///     .set_start_position(Position::try_from(8)?)
+    ///     .set_end_position(Position::try_from(13)?)
+    ///     .build()?;
+    ///
+    /// let thick_end = Position::try_from(10)?;
+    /// *record.thick_end_mut() = thick_end;
+    ///
+    /// assert_eq!(record.thick_end(), thick_end);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn thick_end_mut(&mut self) -> &mut Position {
+        &mut self.standard_fields.thick_end
+    }
 }
 
 impl<const N: u8> Record<N>
@@ -463,6 +638,30 @@ where
     pub fn blocks(&self) -> &[Block] {
         &self.standard_fields.blocks
     }
+
+    /// Returns a mutable reference to the blocks (`[(blockStarts, blockSizes)]`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// use noodles_core::Position;
+    ///
+    /// let mut record = bed::Record::<12>::builder()
+    ///     .set_reference_sequence_name("sq0")
+    ///     .set_start_position(Position::try_from(8)?)
+    ///     .set_end_position(Position::try_from(13)?)
+    ///     .set_blocks(vec![(0, 2)])
+    ///     .build()?;
+    ///
+    /// record.blocks_mut().push((3, 3));
+    ///
+    /// assert_eq!(record.blocks(), [(0, 2), (3, 3)]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn blocks_mut(&mut self) -> &mut Vec<Block> {
+        &mut self.standard_fields.blocks
+    }
 }
 
 impl fmt::Display for Record<3> {
@@ -538,7 +737,7 @@ where
         "{}{}{}{}{}",
         record.reference_sequence_name(),
         DELIMITER,
-        usize::from(record.start_position()) - 1,
+        ZeroBasedPosition::from(record.start_position()),
         DELIMITER,
         record.end_position()
     )
@@ -595,7 +794,7 @@ where
 {
     format_bed_6_fields(f, record)?;
     f.write_char(DELIMITER)?;
-    write!(f, "{}", usize::from(record.thick_start()) - 1)
+    write!(f, "{}", ZeroBasedPosition::from(record.thick_start()))
 }
 
 fn format_bed_8_fields<const N: u8>(f: &mut fmt::Formatter<'_>, record: &Record<N>) -> fmt::Result
@@ -918,14 +1117,10 @@ where
         .ok_or(ParseError::MissingStartPosition)
         .and_then(|s| {
             s.parse()
-                .map_err(|_| ParseError::InvalidStartPosition)
-                .and_then(|n: usize| {
-                    n.checked_add(1)
-                        .ok_or(ParseError::InvalidStartPosition)
-                        .and_then(|m| {
-                            Position::try_from(m).map_err(|_| ParseError::InvalidStartPosition)
-                        })
-                })
+                .ok()
+                .and_then(ZeroBasedPosition::new)
+                .map(Position::from)
+                .ok_or(ParseError::InvalidStartPosition)
         })?;
 
     let end_position = fields
@@ -988,14 +1183,10 @@ where
         .ok_or(ParseError::MissingThickStart)
         .and_then(|s| {
             s.parse()
-                .map_err(|_| ParseError::InvalidThickStart)
-                .and_then(|n: usize| {
-                    n.checked_add(1)
-                        .ok_or(ParseError::InvalidThickStart)
-                        .and_then(|m| {
-                            Position::try_from(m).map_err(|_| ParseError::InvalidThickStart)
-                        })
-                })
+                .ok()
+                .and_then(ZeroBasedPosition::new)
+                .map(Position::from)
+                .ok_or(ParseError::InvalidThickStart)
         })
 }
 
@@ -1070,6 +1261,25 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_interval() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record<3> = "sq0\t7\t13".parse()?;
+        assert_eq!(
+            record.interval(),
+            HalfOpenInterval::new(
+                ZeroBasedPosition::try_from(7)?,
+                ZeroBasedPosition::try_from(13)?
+            )
+        );
+
+        let record: Record<3> = "sq0\t7\t7".parse()?;
+        let interval = record.interval();
+        assert_eq!(interval.start(), ZeroBasedPosition::try_from(7)?);
+        assert!(interval.is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn test_fmt_for_record_3() -> Result<(), noodles_core::position::TryFromIntError> {
         let start = Position::try_from(8)?;
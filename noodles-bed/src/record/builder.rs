@@ -2,7 +2,7 @@
 
 use std::{error, fmt};
 
-use noodles_core::Position;
+use noodles_core::{region::HalfOpenInterval, Position};
 
 use super::{BedN, Block, Color, Name, OptionalFields, Record, Score, StandardFields, Strand};
 
@@ -143,6 +143,37 @@ where
         self
     }
 
+    /// Sets the feature start (`chromStart`) and end (`chromEnd`) positions from a 0-based,
+    /// half-open interval.
+    ///
+    /// A BED record end position is 1-based, so an interval that ends at 0, i.e., [0, 0), leaves
+    /// the end position unset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// use noodles_core::{position::ZeroBasedPosition, region::HalfOpenInterval, Position};
+    ///
+    /// let interval =
+    ///     HalfOpenInterval::new(ZeroBasedPosition::try_from(7)?, ZeroBasedPosition::try_from(13)?);
+    ///
+    /// let record = bed::Record::<3>::builder()
+    ///     .set_reference_sequence_name("sq0")
+    ///     .set_interval(interval)
+    ///     .build()?;
+    ///
+    /// assert_eq!(record.start_position(), Position::try_from(8)?);
+    /// assert_eq!(record.end_position(), Position::try_from(13)?);
+    /// assert_eq!(record.interval(), interval);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn set_interval(mut self, interval: HalfOpenInterval) -> Self {
+        self.start_position = Some(Position::from(interval.start()));
+        self.end_position = Position::new(usize::from(interval.end()));
+        self
+    }
+
     /// Sets the list of raw optional fields.
     ///
     /// # Examples
@@ -7,7 +7,7 @@ use super::{virtual_position, VirtualPosition};
 ///
 /// A BGZF block is a gzip stream less than 64 KiB and contains an extra field describing the size
 /// of the block itself.
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Block {
     /// The position of the compressed block.
     pos: u64,
@@ -18,6 +18,10 @@ pub struct Block {
 }
 
 impl Block {
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
     pub fn set_position(&mut self, position: u64) {
         self.pos = position;
     }
@@ -1,9 +1,12 @@
-use std::cmp;
+use std::{cmp, sync::Arc};
 
 /// An uncompressed block data buffer with a cursor.
-#[derive(Debug, Default)]
+///
+/// The buffer is shared between clones, which only copy the cursor. It is copied on write when it
+/// is shared.
+#[derive(Clone, Debug, Default)]
 pub struct Data {
-    buf: Vec<u8>,
+    buf: Arc<Vec<u8>>,
     pos: usize,
 }
 
@@ -28,7 +31,7 @@ impl Data {
     }
 
     pub fn resize(&mut self, len: usize) {
-        self.buf.resize(len, 0);
+        Arc::make_mut(&mut self.buf).resize(len, 0);
     }
 
     /// Moves the cursor from the current position by `amt` bytes.
@@ -47,7 +50,7 @@ impl AsRef<[u8]> for Data {
 
 impl AsMut<[u8]> for Data {
     fn as_mut(&mut self) -> &mut [u8] {
-        &mut self.buf[self.pos..]
+        &mut Arc::make_mut(&mut self.buf)[self.pos..]
     }
 }
 
@@ -87,4 +90,20 @@ mod tests {
         data.consume(3);
         assert_eq!(data.position(), 3);
     }
+
+    #[test]
+    fn test_clone() {
+        let mut data = Data::default();
+        data.resize(4);
+
+        let mut clone = data.clone();
+        assert!(Arc::ptr_eq(&data.buf, &clone.buf));
+
+        clone.consume(2);
+        assert_eq!(data.position(), 0);
+
+        clone.as_mut()[0] = 8;
+        assert!(!Arc::ptr_eq(&data.buf, &clone.buf));
+        assert_eq!(data.as_ref(), [0, 0, 0, 0]);
+    }
 }
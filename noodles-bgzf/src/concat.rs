@@ -0,0 +1,298 @@
+//! Lossless BGZF stream concatenation.
+//!
+//! BGZF streams can be concatenated by copying their blocks as-is, without decompressing and
+//! recompressing the data. Each input's trailing EOF marker is dropped, and a single EOF marker is
+//! written at the end of the output.
+//!
+//! Because blocks are moved but not modified, a virtual position in an input only changes by the
+//! compressed position at which the input starts in the output. See
+//! [`crate::VirtualPosition::checked_shift`].
+
+use std::{
+    io::{self, Read, Seek, SeekFrom, Write},
+    mem,
+    ops::Range,
+};
+
+use crate::{
+    gz,
+    reader::block::{parse_header, parse_trailer, read_frame_into},
+    writer::BGZF_EOF,
+};
+
+/// Concatenates BGZF streams.
+///
+/// The blocks of each input are copied to the writer without recompression, and a final EOF
+/// marker is written. This returns the compressed position at which each input starts in the
+/// output, i.e., the offset to add to compressed positions of the input.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Read, Write};
+/// use noodles_bgzf::{self as bgzf, concat};
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// let a = writer.finish()?;
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"bgzf")?;
+/// let b = writer.finish()?;
+///
+/// let mut dst = Vec::new();
+/// let offsets = concat::concat([&a[..], &b[..]], &mut dst)?;
+/// assert_eq!(offsets, [0, 35]);
+///
+/// let mut reader = bgzf::Reader::new(&dst[..]);
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf)?;
+/// assert_eq!(buf, b"noodlesbgzf");
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn concat<I, R, W>(readers: I, writer: &mut W) -> io::Result<Vec<u64>>
+where
+    I: IntoIterator<Item = R>,
+    R: Read,
+    W: Write,
+{
+    let mut offsets = Vec::new();
+    let mut position = 0;
+
+    for mut reader in readers {
+        offsets.push(position);
+        position += copy_blocks(&mut reader, writer)?;
+    }
+
+    writer.write_all(BGZF_EOF)?;
+
+    Ok(offsets)
+}
+
+/// Copies the blocks of a BGZF stream without recompression.
+///
+/// A trailing empty block, i.e., an EOF marker, is not copied. Empty blocks elsewhere in the stream
+/// are kept so that compressed positions in the stream are only offset by where the copy starts.
+///
+/// This returns the number of bytes written.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Write};
+/// use noodles_bgzf::{self as bgzf, concat};
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// let src = writer.finish()?;
+///
+/// let mut dst = Vec::new();
+/// assert_eq!(concat::copy_blocks(&mut &src[..], &mut dst)?, 35);
+/// assert_eq!(dst, &src[..35]);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn copy_blocks<R, W>(reader: &mut R, writer: &mut W) -> io::Result<u64>
+where
+    R: Read,
+    W: Write,
+{
+    let (n, _) = copy_frames(reader, writer, None)?;
+    Ok(n)
+}
+
+/// Copies the blocks in a compressed byte range of a BGZF stream without recompression.
+///
+/// Both the start and end of the range must be at block boundaries, e.g., as returned by
+/// [`crate::split::byte_ranges`] or [`crate::gzi::Indexer`]. If the range ends at the end of the
+/// stream, a trailing EOF marker is not copied.
+///
+/// This returns the number of bytes written.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Cursor, Write};
+/// use noodles_bgzf::{self as bgzf, concat};
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// writer.flush()?;
+/// writer.write_all(b"bgzf")?;
+/// let src = writer.finish()?;
+///
+/// let mut reader = Cursor::new(&src);
+/// let mut dst = Vec::new();
+/// assert_eq!(concat::copy_block_range(&mut reader, &mut dst, 35..95)?, 32);
+/// assert_eq!(dst, &src[35..67]);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn copy_block_range<R, W>(reader: &mut R, writer: &mut W, range: Range<u64>) -> io::Result<u64>
+where
+    R: Read + Seek,
+    W: Write,
+{
+    let len = reader.seek(SeekFrom::End(0))?;
+    let end = range.end.min(len);
+    let limit = end.saturating_sub(range.start);
+
+    reader.seek(SeekFrom::Start(range.start))?;
+
+    let (mut n, trailing_empty_frame) = copy_frames(reader, writer, Some(limit))?;
+
+    if end < len {
+        if let Some(frame) = trailing_empty_frame {
+            writer.write_all(&frame)?;
+            n += frame.len() as u64;
+        }
+    }
+
+    Ok(n)
+}
+
+// Copies frames, holding back a trailing empty frame. At most `limit` bytes are read.
+fn copy_frames<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    limit: Option<u64>,
+) -> io::Result<(u64, Option<Vec<u8>>)>
+where
+    R: Read,
+    W: Write,
+{
+    let mut buf = Vec::new();
+    let mut empty_frame: Option<Vec<u8>> = None;
+
+    let mut read_len = 0;
+    let mut write_len = 0;
+
+    loop {
+        if limit.map(|n| read_len >= n).unwrap_or(false) {
+            break;
+        }
+
+        if read_frame_into(reader, &mut buf)?.is_none() {
+            break;
+        }
+
+        read_len += buf.len() as u64;
+
+        if limit.map(|n| read_len > n).unwrap_or(false) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "range end is not at a block boundary",
+            ));
+        }
+
+        parse_header(&buf)?;
+
+        let n = buf.len() - gz::TRAILER_SIZE;
+        let (_, r#isize) = parse_trailer(&buf[n..])?;
+
+        if let Some(frame) = empty_frame.take() {
+            write_frame(writer, &frame, &mut write_len)?;
+        }
+
+        if r#isize == 0 {
+            empty_frame = Some(mem::take(&mut buf));
+        } else {
+            write_frame(writer, &buf, &mut write_len)?;
+        }
+    }
+
+    Ok((write_len, empty_frame))
+}
+
+fn write_frame<W>(writer: &mut W, frame: &[u8], write_len: &mut u64) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_all(frame)?;
+    *write_len += frame.len() as u64;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{VirtualPosition, Writer};
+
+    fn build_data(chunks: &[&[u8]]) -> io::Result<Vec<u8>> {
+        let mut writer = Writer::new(Vec::new());
+
+        for chunk in chunks {
+            writer.write_all(chunk)?;
+            writer.flush()?;
+        }
+
+        writer.finish()
+    }
+
+    #[test]
+    fn test_concat() -> Result<(), Box<dyn std::error::Error>> {
+        let a = build_data(&[b"noodles", b"-"])?;
+        let b = build_data(&[b"bgzf"])?;
+        let c = Vec::new();
+
+        let mut dst = Vec::new();
+        let offsets = concat([&a[..], &b[..], &c[..]], &mut dst)?;
+
+        let a_len = (a.len() - BGZF_EOF.len()) as u64;
+        let b_len = (b.len() - BGZF_EOF.len()) as u64;
+        assert_eq!(offsets, [0, a_len, a_len + b_len]);
+        assert!(dst.ends_with(BGZF_EOF));
+        assert_eq!(dst.len() as u64, a_len + b_len + BGZF_EOF.len() as u64);
+
+        // The virtual position of "bgzf" in `b`, shifted by its offset.
+        let pos = VirtualPosition::try_from((0, 1))?
+            .checked_shift(offsets[1])
+            .unwrap();
+
+        let mut reader = crate::Reader::new(Cursor::new(&dst));
+        reader.seek(pos)?;
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"gzf");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_blocks_with_empty_blocks() -> io::Result<()> {
+        let mut src = build_data(&[b"noodles"])?;
+        let data_len = src.len() - BGZF_EOF.len();
+        src.extend_from_within(..data_len);
+        src.extend_from_slice(BGZF_EOF);
+
+        // Empty blocks that are not at the end of the stream are kept.
+        let mut dst = Vec::new();
+        copy_blocks(&mut &src[..], &mut dst)?;
+        assert_eq!(dst, &src[..src.len() - BGZF_EOF.len()]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_block_range() -> io::Result<()> {
+        let src = build_data(&[b"noodles", b"bgzf"])?;
+        let mut reader = Cursor::new(&src);
+
+        let mut dst = Vec::new();
+        copy_block_range(&mut reader, &mut dst, 0..35)?;
+        assert_eq!(dst, &src[..35]);
+
+        dst.clear();
+        copy_block_range(&mut reader, &mut dst, 0..u64::MAX)?;
+        assert_eq!(dst, &src[..src.len() - BGZF_EOF.len()]);
+
+        dst.clear();
+        assert!(matches!(
+            copy_block_range(&mut reader, &mut dst, 0..36),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
@@ -7,14 +7,20 @@
 #[cfg(feature = "async")]
 mod r#async;
 
+mod indexer;
 mod reader;
+mod writer;
 
-pub use self::reader::Reader;
+pub use self::{indexer::Indexer, reader::Reader, writer::Writer};
 
 #[cfg(feature = "async")]
 pub use self::r#async::Reader as AsyncReader;
 
-use std::{fs::File, io, path::Path};
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    path::Path,
+};
 
 /// A gzip index.
 pub type Index = Vec<(u64, u64)>;
@@ -39,3 +45,46 @@ where
     let mut reader = File::open(src).map(Reader::new)?;
     reader.read_index()
 }
+
+/// Builds a gzip index from a BGZF file.
+///
+/// This is a convenience function and is equivalent to opening the given path and indexing every
+/// block in the file. It is similar to the output of `bgzip --reindex <src>`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bgzf::gzi;
+/// let index = gzi::index("in.gz")?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn index<P>(src: P) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let indexer = File::open(src).map(BufReader::new).map(Indexer::new)?;
+    indexer.build_index()
+}
+
+/// Writes a gzip index to a file.
+///
+/// This is a convenience function and is equivalent to creating a file at the given path and
+/// writing the index.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bgzf::gzi;
+/// let index = vec![(0, 0)];
+/// gzi::write("in.gz.gzi", &index)?;
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn write<P>(dst: P, index: &Index) -> io::Result<()>
+where
+    P: AsRef<Path>,
+{
+    let mut writer = File::create(dst).map(Writer::new)?;
+    writer.write_index(index)
+}
@@ -0,0 +1,145 @@
+use std::io::{self, Read};
+
+use super::Index;
+use crate::{gz, reader::block::read_frame_into};
+
+/// A gzip index (GZI) indexer.
+///
+/// The indexer reads the frames of a BGZF stream and records the compressed and uncompressed
+/// positions at the start of each block. Block data is not decompressed.
+pub struct Indexer<R> {
+    inner: R,
+    buf: Vec<u8>,
+    compressed_position: u64,
+    uncompressed_position: u64,
+}
+
+impl<R> Indexer<R>
+where
+    R: Read,
+{
+    /// Creates a gzip index (GZI) indexer.
+    ///
+    /// The position of the stream is expected to be at the start of a BGZF stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let data = [];
+    /// let indexer = gzi::Indexer::new(&data[..]);
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            compressed_position: 0,
+            uncompressed_position: 0,
+        }
+    }
+
+    /// Indexes a single block.
+    ///
+    /// This returns the compressed and uncompressed positions at the end of the block, i.e., the
+    /// start of the next block. If there are no more blocks, this returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut indexer = gzi::Indexer::new(&data[..]);
+    /// assert_eq!(indexer.index_block()?, Some((35, 7)));
+    /// assert_eq!(indexer.index_block()?, Some((63, 7)));
+    /// assert!(indexer.index_block()?.is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn index_block(&mut self) -> io::Result<Option<(u64, u64)>> {
+        use crate::reader::block::{parse_header, parse_trailer};
+
+        if read_frame_into(&mut self.inner, &mut self.buf)?.is_none() {
+            return Ok(None);
+        }
+
+        parse_header(&self.buf)?;
+
+        let n = self.buf.len() - gz::TRAILER_SIZE;
+        let (_, r#isize) = parse_trailer(&self.buf[n..])?;
+
+        self.compressed_position += self.buf.len() as u64;
+        self.uncompressed_position += r#isize as u64;
+
+        Ok(Some((self.compressed_position, self.uncompressed_position)))
+    }
+
+    /// Builds a gzip index from the blocks in the stream.
+    ///
+    /// The index includes the initial block, i.e., (0, 0), and the start position of every
+    /// subsequent block, including the final EOF block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let indexer = gzi::Indexer::new(&data[..]);
+    /// let index = indexer.build_index()?;
+    ///
+    /// assert_eq!(index, [(0, 0), (35, 7)]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build_index(mut self) -> io::Result<Index> {
+        let mut index = vec![(0, 0)];
+
+        while let Some(position) = self.index_block()? {
+            index.push(position);
+        }
+
+        // The position after the last block is the end of the stream, not the start of a block.
+        if index.len() > 1 {
+            index.pop();
+        }
+
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::Writer;
+
+    #[test]
+    fn test_build_index() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let index = Indexer::new(&data[..]).build_index()?;
+        assert_eq!(index, [(0, 0), (35, 7), (67, 11)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_index_with_empty_stream() -> io::Result<()> {
+        let data = [];
+        let index = Indexer::new(&data[..]).build_index()?;
+        assert_eq!(index, [(0, 0)]);
+        Ok(())
+    }
+}
@@ -12,6 +12,7 @@ use super::{gzi, Reader, VirtualPosition};
 pub struct IndexedReader<R> {
     inner: Reader<R>,
     index: gzi::Index,
+    uncompressed_position: u64,
 }
 
 impl<R> IndexedReader<R>
@@ -31,6 +32,7 @@ where
         Self {
             inner: Reader::new(inner),
             index,
+            uncompressed_position: 0,
         }
     }
 
@@ -105,12 +107,51 @@ where
     }
 }
 
+impl<R> IndexedReader<R>
+where
+    R: Read + Seek,
+{
+    fn seek_to_uncompressed_position(&mut self, pos: u64) -> io::Result<u64> {
+        self.inner.seek_by_uncompressed_position(&self.index, pos)?;
+        self.uncompressed_position = pos;
+        Ok(pos)
+    }
+
+    // Returns the size of the uncompressed stream.
+    //
+    // This seeks to the last indexed block and reads the remaining blocks to the end of the
+    // stream. The stream position is left at the end of the stream.
+    fn uncompressed_len(&mut self) -> io::Result<u64> {
+        let (_, last_uncompressed_position) = self
+            .index
+            .last()
+            .copied()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "empty index"))?;
+
+        self.seek_to_uncompressed_position(last_uncompressed_position)?;
+
+        loop {
+            let n = self.fill_buf()?.len();
+
+            if n == 0 {
+                break;
+            }
+
+            self.consume(n);
+        }
+
+        Ok(self.uncompressed_position)
+    }
+}
+
 impl<R> Read for IndexedReader<R>
 where
     R: Read,
 {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.read(buf)
+        let n = self.inner.read(buf)?;
+        self.uncompressed_position += n as u64;
+        Ok(n)
     }
 }
 
@@ -123,7 +164,8 @@ where
     }
 
     fn consume(&mut self, amt: usize) {
-        self.inner.consume(amt)
+        self.inner.consume(amt);
+        self.uncompressed_position += amt as u64;
     }
 }
 
@@ -132,9 +174,96 @@ where
     R: Read + Seek,
 {
     fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
-        match pos {
-            SeekFrom::Start(pos) => self.inner.seek_by_uncompressed_position(&self.index, pos),
-            _ => unimplemented!(),
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => return self.seek_to_uncompressed_position(n),
+            SeekFrom::Current(n) => (self.uncompressed_position, n),
+            SeekFrom::End(n) => (self.uncompressed_len()?, n),
+        };
+
+        let pos = if offset >= 0 {
+            base.checked_add(offset as u64)
+        } else {
+            base.checked_sub(offset.unsigned_abs())
+        }
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        self.seek_to_uncompressed_position(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_reader() -> io::Result<IndexedReader<io::Cursor<Vec<u8>>>> {
+        use std::io::Write;
+
+        use crate::writer;
+
+        let mut writer = writer::Builder::default()
+            .set_gzi_index_enabled(true)
+            .build_with_writer(Vec::new());
+
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"-bgzf")?;
+        writer.try_finish()?;
+
+        let index = writer.gzi_index().cloned().unwrap();
+        let data = writer.into_inner();
+
+        Ok(IndexedReader::new(io::Cursor::new(data), index))
+    }
+
+    #[test]
+    fn test_seek() -> io::Result<()> {
+        let mut reader = build_reader()?;
+        let mut buf = [0; 3];
+
+        assert_eq!(reader.stream_position()?, 0);
+
+        assert_eq!(reader.seek(SeekFrom::Start(2))?, 2);
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"odl");
+        assert_eq!(reader.stream_position()?, 5);
+
+        assert_eq!(reader.seek(SeekFrom::Current(3))?, 8);
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"bgz");
+
+        assert_eq!(reader.seek(SeekFrom::Current(-5))?, 6);
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"s-b");
+
+        assert_eq!(reader.seek(SeekFrom::End(-3))?, 9);
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"gzf");
+
+        assert_eq!(reader.seek(SeekFrom::End(0))?, 12);
+        assert_eq!(reader.read(&mut buf)?, 0);
+
+        assert!(matches!(
+            reader.seek(SeekFrom::Current(-13)),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seek_with_empty_index() {
+        let mut reader = IndexedReader::new(io::empty(), gzi::Index::default());
+
+        for pos in [SeekFrom::Start(0), SeekFrom::Current(0), SeekFrom::End(0)] {
+            assert!(matches!(
+                reader.seek(pos),
+                Err(e) if e.kind() == io::ErrorKind::InvalidInput
+            ));
         }
     }
 }
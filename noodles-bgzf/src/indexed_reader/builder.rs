@@ -25,6 +25,14 @@ impl Builder {
         self
     }
 
+    /// Enables a cache of decompressed blocks with the given capacity.
+    ///
+    /// By default, the block cache is disabled. See [`reader::Builder::set_block_cache_capacity`].
+    pub fn set_block_cache_capacity(mut self, capacity: NonZeroUsize) -> Self {
+        self.reader_builder = self.reader_builder.set_block_cache_capacity(capacity);
+        self
+    }
+
     /// Set a GZ index.
     pub fn set_index(mut self, index: gzi::Index) -> Self {
         self.index = Some(index);
@@ -46,7 +54,11 @@ impl Builder {
 
         let inner = self.reader_builder.build_from_path(src)?;
 
-        Ok(IndexedReader { inner, index })
+        Ok(IndexedReader {
+            inner,
+            index,
+            uncompressed_position: 0,
+        })
     }
 
     /// Builds a indexed BGZF reader from a reader.
@@ -60,7 +72,11 @@ impl Builder {
 
         let inner = self.reader_builder.build_from_reader(reader);
 
-        Ok(IndexedReader { inner, index })
+        Ok(IndexedReader {
+            inner,
+            index,
+            uncompressed_position: 0,
+        })
     }
 }
 
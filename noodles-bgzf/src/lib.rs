@@ -38,15 +38,22 @@
 mod r#async;
 
 mod block;
+pub mod concat;
 mod gz;
 pub mod gzi;
 pub mod indexed_reader;
 pub mod reader;
+pub mod split;
+pub mod validate;
 pub mod virtual_position;
 pub mod writer;
 
 pub use self::{
-    indexed_reader::IndexedReader, reader::Reader, virtual_position::VirtualPosition,
+    concat::concat,
+    indexed_reader::IndexedReader,
+    reader::Reader,
+    validate::{salvage, validate},
+    virtual_position::VirtualPosition,
     writer::Writer,
 };
 
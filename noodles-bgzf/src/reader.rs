@@ -1,12 +1,14 @@
 //! BGZF reader.
 
 pub(crate) mod block;
+mod block_cache;
 mod builder;
 
 pub use self::builder::Builder;
 
 use std::io::{self, BufRead, Read, Seek, SeekFrom};
 
+use self::block_cache::BlockCache;
 use super::{gzi, Block, VirtualPosition};
 
 /// A BGZF reader.
@@ -30,6 +32,7 @@ pub struct Reader<R> {
     inner: block::Inner<R>,
     position: u64,
     block: Block,
+    block_cache: Option<BlockCache>,
 }
 
 impl<R> Reader<R>
@@ -139,6 +142,10 @@ where
     /// The underlying stream's cursor is first moved the the compressed position. A block is read,
     /// decompressed, and has its own cursor moved to the uncompressed position.
     ///
+    /// If the block cache is enabled and holds the block at the compressed position, the block is
+    /// taken from the cache instead of being read and decompressed again. Otherwise, the block that
+    /// is read is added to the cache. Blocks read sequentially are not cached.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -152,10 +159,7 @@ where
     pub fn seek(&mut self, pos: VirtualPosition) -> io::Result<VirtualPosition> {
         let (cpos, upos) = pos.into();
 
-        self.inner.get_mut().seek(SeekFrom::Start(cpos))?;
-        self.position = cpos;
-
-        self.read_block()?;
+        self.seek_to_block(cpos)?;
 
         self.block.data_mut().set_position(usize::from(upos));
 
@@ -164,6 +168,8 @@ where
 
     /// Seeks the stream to the given uncompressed position.
     ///
+    /// This returns an error if the index is empty.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -179,17 +185,16 @@ where
         index: &gzi::Index,
         pos: u64,
     ) -> io::Result<u64> {
-        assert!(!index.is_empty());
-
         let i = index.iter().position(|r| r.1 > pos).unwrap_or(index.len());
-        // SAFETY: `i` is > 0.
-        let record = index[i - 1];
 
-        let cpos = record.0;
-        self.inner.get_mut().seek(SeekFrom::Start(cpos))?;
-        self.position = cpos;
+        let record = i
+            .checked_sub(1)
+            .and_then(|j| index.get(j))
+            .copied()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "empty index"))?;
 
-        self.read_block()?;
+        let cpos = record.0;
+        self.seek_to_block(cpos)?;
 
         let upos = usize::try_from(pos - record.1)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
@@ -197,6 +202,25 @@ where
 
         Ok(pos)
     }
+
+    fn seek_to_block(&mut self, cpos: u64) -> io::Result<()> {
+        if let Some(block) = self.block_cache.as_mut().and_then(|cache| cache.get(cpos)) {
+            let next_cpos = cpos + block.size();
+            self.inner.get_mut().seek(SeekFrom::Start(next_cpos))?;
+            self.position = next_cpos;
+            self.block = block;
+        } else {
+            self.inner.get_mut().seek(SeekFrom::Start(cpos))?;
+            self.position = cpos;
+            self.read_block()?;
+
+            if let Some(block_cache) = self.block_cache.as_mut() {
+                block_cache.insert(self.block.clone());
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl<R> Read for Reader<R>
@@ -287,6 +311,44 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_seek_with_block_cache() -> Result<(), Box<dyn std::error::Error>> {
+        use std::{io::Write, num::NonZeroUsize};
+
+        let mut writer = crate::Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let mut reader = Builder::default()
+            .set_block_cache_capacity(NonZeroUsize::try_from(2)?)
+            .build_from_reader(Cursor::new(data));
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"noodlesbgzf");
+
+        // Sequentially read blocks are not cached.
+        assert!(reader.block_cache.as_mut().and_then(|c| c.get(0)).is_none());
+
+        reader.seek(VirtualPosition::from(0))?;
+
+        // Invalidate the underlying data to ensure the cached block is used.
+        for b in &mut reader.get_mut().get_mut()[..35] {
+            *b = 0;
+        }
+
+        reader.seek(VirtualPosition::try_from((0, 3))?)?;
+        assert_eq!(reader.position(), 35);
+
+        buf.clear();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"dlesbgzf");
+
+        Ok(())
+    }
+
     #[test]
     fn test_seek_by_uncompressed_position() -> io::Result<()> {
         #[rustfmt::skip]
@@ -59,35 +59,81 @@ where
     }
 }
 
-fn read_frame_into<R>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<Option<()>>
+pub(crate) fn read_frame_into<R>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<Option<()>>
+where
+    R: Read,
+{
+    match read_raw_frame_into(reader, buf)? {
+        Frame::Complete => Ok(Some(())),
+        Frame::Eof => Ok(None),
+        Frame::Truncated if buf.len() < BGZF_HEADER_SIZE => Ok(None),
+        Frame::Truncated => Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+        Frame::InvalidHeader => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid BGZF header",
+        )),
+        Frame::InvalidBlockSize(_) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid frame size",
+        )),
+    }
+}
+
+/// The result of reading a raw frame.
+pub(crate) enum Frame {
+    /// The stream ended before the frame.
+    Eof,
+    /// The frame was completely read.
+    Complete,
+    /// The stream ended within the frame.
+    Truncated,
+    /// The header is not a valid BGZF header.
+    InvalidHeader,
+    /// The block size (`BSIZE` + 1) is too small to hold a header and trailer.
+    InvalidBlockSize(usize),
+}
+
+// Reads a raw frame into `buf`.
+//
+// Unlike `read_frame_into`, this distinguishes a clean end of stream from a partially read frame
+// and reports why a frame is invalid. On a partial read, `buf` holds the bytes that were read.
+pub(crate) fn read_raw_frame_into<R>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<Frame>
 where
     R: Read,
 {
     const MIN_FRAME_SIZE: usize = BGZF_HEADER_SIZE + gz::TRAILER_SIZE;
     const BSIZE_POSITION: usize = 16;
 
-    buf.resize(BGZF_HEADER_SIZE, 0);
+    buf.clear();
+
+    let n = reader
+        .by_ref()
+        .take(BGZF_HEADER_SIZE as u64)
+        .read_to_end(buf)?;
 
-    match reader.read_exact(buf) {
-        Ok(()) => {}
-        Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
-        Err(e) => return Err(e),
+    if n == 0 {
+        return Ok(Frame::Eof);
+    } else if n < BGZF_HEADER_SIZE {
+        return Ok(Frame::Truncated);
+    } else if !is_valid_header(&buf[..]) {
+        return Ok(Frame::InvalidHeader);
     }
 
     let bsize = (&buf[BSIZE_POSITION..]).get_u16_le();
     let block_size = usize::from(bsize) + 1;
 
     if block_size < MIN_FRAME_SIZE {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "invalid frame size",
-        ));
+        return Ok(Frame::InvalidBlockSize(block_size));
     }
 
-    buf.resize(block_size, 0);
-    reader.read_exact(&mut buf[BGZF_HEADER_SIZE..])?;
+    let len = block_size - BGZF_HEADER_SIZE;
+    let n = reader.by_ref().take(len as u64).read_to_end(buf)?;
 
-    Ok(Some(()))
+    if n < len {
+        Ok(Frame::Truncated)
+    } else {
+        Ok(Frame::Complete)
+    }
 }
 
 fn split_frame(buf: &[u8]) -> (&[u8], &[u8], &[u8]) {
@@ -101,7 +147,7 @@ fn split_frame(buf: &[u8]) -> (&[u8], &[u8], &[u8]) {
     (header, cdata, trailer)
 }
 
-fn parse_header(src: &[u8]) -> io::Result<()> {
+pub(crate) fn parse_header(src: &[u8]) -> io::Result<()> {
     if is_valid_header(src) {
         Ok(())
     } else {
@@ -112,7 +158,7 @@ fn parse_header(src: &[u8]) -> io::Result<()> {
     }
 }
 
-fn is_valid_header<B>(mut src: B) -> bool
+pub(crate) fn is_valid_header<B>(mut src: B) -> bool
 where
     B: Buf,
 {
@@ -148,7 +194,7 @@ where
         && subfield_len == BGZF_SLEN
 }
 
-fn parse_trailer<B>(mut src: B) -> io::Result<(u32, usize)>
+pub(crate) fn parse_trailer<B>(mut src: B) -> io::Result<(u32, usize)>
 where
     B: Buf,
 {
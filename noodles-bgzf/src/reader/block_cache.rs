@@ -0,0 +1,196 @@
+use std::{collections::HashMap, num::NonZeroUsize};
+
+use crate::Block;
+
+/// A bounded least recently used (LRU) cache of inflated blocks.
+///
+/// Blocks are keyed by their position in the compressed stream. Block data is shared with the
+/// reader, so inserting and getting a block does not copy its data.
+///
+/// The recency order is kept as a doubly linked list over the entries, making each operation
+/// O(1).
+#[derive(Debug)]
+pub struct BlockCache {
+    capacity: usize,
+    indices: HashMap<u64, usize>,
+    entries: Vec<Entry>,
+    // The most recently used entry.
+    head: Option<usize>,
+    // The least recently used entry.
+    tail: Option<usize>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    block: Block,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+impl BlockCache {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        let capacity = capacity.get();
+
+        Self {
+            capacity,
+            indices: HashMap::with_capacity(capacity),
+            entries: Vec::with_capacity(capacity),
+            head: None,
+            tail: None,
+        }
+    }
+
+    /// Returns the block at the given compressed position and marks it as most recently used.
+    ///
+    /// The cursor of the returned block's data is at the start.
+    pub fn get(&mut self, position: u64) -> Option<Block> {
+        let i = self.indices.get(&position).copied()?;
+        self.move_to_front(i);
+        Some(self.entries[i].block.clone())
+    }
+
+    /// Adds the given block, evicting the least recently used block if the cache is full.
+    ///
+    /// Empty blocks, e.g., EOF markers, are not cached.
+    pub fn insert(&mut self, mut block: Block) {
+        if block.data().len() == 0 {
+            return;
+        }
+
+        let position = block.position();
+
+        if let Some(&i) = self.indices.get(&position) {
+            self.move_to_front(i);
+            return;
+        }
+
+        block.data_mut().set_position(0);
+
+        let i = if self.entries.len() < self.capacity {
+            self.entries.push(Entry {
+                block,
+                prev: None,
+                next: None,
+            });
+
+            self.entries.len() - 1
+        } else {
+            // The cache is full and has a nonzero capacity, so it has a tail.
+            let i = self.tail.expect("missing tail");
+            self.unlink(i);
+
+            let entry = &mut self.entries[i];
+            self.indices.remove(&entry.block.position());
+            entry.block = block;
+
+            i
+        };
+
+        self.indices.insert(position, i);
+        self.push_front(i);
+    }
+
+    fn move_to_front(&mut self, i: usize) {
+        if self.head != Some(i) {
+            self.unlink(i);
+            self.push_front(i);
+        }
+    }
+
+    fn unlink(&mut self, i: usize) {
+        let (prev, next) = {
+            let entry = &mut self.entries[i];
+            (entry.prev.take(), entry.next.take())
+        };
+
+        match prev {
+            Some(j) => self.entries[j].next = next,
+            None => self.head = next,
+        }
+
+        match next {
+            Some(j) => self.entries[j].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    fn push_front(&mut self, i: usize) {
+        self.entries[i].next = self.head;
+
+        match self.head {
+            Some(j) => self.entries[j].prev = Some(i),
+            None => self.tail = Some(i),
+        }
+
+        self.head = Some(i);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_block(position: u64) -> Block {
+        let mut block = Block::default();
+        block.set_position(position);
+        block.data_mut().resize(4);
+        block.set_size(8);
+        block
+    }
+
+    #[test]
+    fn test_get_and_insert() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cache = BlockCache::new(NonZeroUsize::try_from(2)?);
+
+        cache.insert(build_block(0));
+        cache.insert(build_block(8));
+        assert!(cache.get(0).is_some());
+
+        // Evicts the block at 8, the least recently used.
+        cache.insert(build_block(16));
+
+        assert!(cache.get(0).is_some());
+        assert!(cache.get(8).is_none());
+        assert!(cache.get(16).is_some());
+
+        // Evicts the block at 0.
+        cache.insert(build_block(24));
+
+        assert!(cache.get(0).is_none());
+        assert!(cache.get(16).is_some());
+        assert!(cache.get(24).is_some());
+
+        cache.insert(Block::default());
+        assert!(cache.get(0).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_with_capacity_of_one() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cache = BlockCache::new(NonZeroUsize::try_from(1)?);
+
+        cache.insert(build_block(0));
+        assert!(cache.get(0).is_some());
+
+        cache.insert(build_block(8));
+        assert!(cache.get(0).is_none());
+        assert!(cache.get(8).is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_resets_data_position() -> Result<(), Box<dyn std::error::Error>> {
+        let mut cache = BlockCache::new(NonZeroUsize::try_from(1)?);
+
+        let mut block = build_block(0);
+        block.data_mut().set_position(2);
+        cache.insert(block);
+
+        let block = cache.get(0).ok_or("missing block")?;
+        assert_eq!(block.data().position(), 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,267 @@
+//! BGZF block boundary resynchronization and splitting.
+//!
+//! These allow a BGZF stream to be processed in parallel without an index. A stream is divided
+//! into byte ranges that start at block boundaries ([`byte_ranges`]), and each range can be read
+//! independently by seeking a [`crate::Reader`] to the start of the range.
+
+use std::{
+    io::{self, Read, Seek, SeekFrom},
+    num::NonZeroUsize,
+    ops::Range,
+};
+
+use crate::{
+    gz,
+    reader::block::{is_valid_header, read_raw_frame_into, Frame},
+    validate::check_frame,
+    BGZF_HEADER_SIZE, BGZF_MAX_ISIZE,
+};
+
+// CM = DEFLATE, FLG = FEXTRA
+const BGZF_MAGIC_PREFIX: [u8; 4] = [gz::MAGIC_NUMBER[0], gz::MAGIC_NUMBER[1], 0x08, 0x04];
+
+const WINDOW_SIZE: usize = BGZF_MAX_ISIZE;
+
+/// Finds the start of the next block at or after the given compressed position.
+///
+/// This scans the stream for a gzip header with a BGZF extra subfield. A candidate is only
+/// accepted if its compressed data successfully inflates and matches its checksum and
+/// uncompressed size.
+///
+/// If no block is found before the end of the stream, this returns `None`. The position of the
+/// stream is undefined after this call.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Cursor, Write};
+/// use noodles_bgzf::{self as bgzf, split};
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// writer.flush()?;
+/// writer.write_all(b"bgzf")?;
+/// let data = writer.finish()?;
+///
+/// let mut reader = Cursor::new(data);
+/// assert_eq!(split::find_next_block(&mut reader, 0)?, Some(0));
+/// assert_eq!(split::find_next_block(&mut reader, 1)?, Some(35));
+/// assert_eq!(split::find_next_block(&mut reader, 36)?, Some(67));
+/// assert_eq!(split::find_next_block(&mut reader, 68)?, None);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn find_next_block<R>(reader: &mut R, pos: u64) -> io::Result<Option<u64>>
+where
+    R: Read + Seek,
+{
+    let mut window = Vec::with_capacity(WINDOW_SIZE + BGZF_HEADER_SIZE);
+    let mut frame = Vec::new();
+    let mut data = Vec::new();
+
+    let mut window_start = pos;
+
+    loop {
+        reader.seek(SeekFrom::Start(window_start))?;
+
+        window.clear();
+        reader
+            .by_ref()
+            .take((WINDOW_SIZE + BGZF_HEADER_SIZE) as u64)
+            .read_to_end(&mut window)?;
+
+        if window.len() < BGZF_HEADER_SIZE {
+            return Ok(None);
+        }
+
+        let candidate_count = window.len() - BGZF_HEADER_SIZE + 1;
+
+        for i in 0..candidate_count {
+            let header = &window[i..i + BGZF_HEADER_SIZE];
+
+            if !header.starts_with(&BGZF_MAGIC_PREFIX) || !is_valid_header(header) {
+                continue;
+            }
+
+            let candidate = window_start + i as u64;
+
+            if is_valid_block(reader, candidate, &mut frame, &mut data)? {
+                return Ok(Some(candidate));
+            }
+        }
+
+        window_start += candidate_count as u64;
+    }
+}
+
+fn is_valid_block<R>(
+    reader: &mut R,
+    pos: u64,
+    frame: &mut Vec<u8>,
+    data: &mut Vec<u8>,
+) -> io::Result<bool>
+where
+    R: Read + Seek,
+{
+    reader.seek(SeekFrom::Start(pos))?;
+
+    match read_raw_frame_into(reader, frame)? {
+        Frame::Complete => Ok(check_frame(frame, data)?.is_ok()),
+        _ => Ok(false),
+    }
+}
+
+/// Divides a BGZF stream into byte ranges aligned to block boundaries.
+///
+/// The stream is divided into `count` ranges of roughly equal compressed size. Each range starts
+/// at a block boundary and ends at the start of the next range or at the end of the stream. Fewer
+/// ranges may be returned if the stream has fewer blocks than `count`.
+///
+/// A block belongs to the range that contains its start position.
+///
+/// This seeks the given stream. When splitting a stream that is also read by a
+/// [`crate::Reader`], pass a separate handle to the same data rather than the reader's underlying
+/// stream, which would leave the reader out of sync.
+///
+/// # Examples
+///
+/// ```
+/// # use std::{io::{self, Cursor, Write}, num::NonZeroUsize};
+/// use noodles_bgzf::{self as bgzf, split};
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// writer.flush()?;
+/// writer.write_all(b"bgzf")?;
+/// let data = writer.finish()?;
+///
+/// let mut reader = Cursor::new(data);
+/// let count = NonZeroUsize::try_from(2).unwrap();
+/// let ranges = split::byte_ranges(&mut reader, count)?;
+///
+/// assert_eq!(ranges, [0..67, 67..95]);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn byte_ranges<R>(reader: &mut R, count: NonZeroUsize) -> io::Result<Vec<Range<u64>>>
+where
+    R: Read + Seek,
+{
+    let len = reader.seek(SeekFrom::End(0))?;
+
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+
+    let count = count.get() as u64;
+    let mut starts = vec![0];
+
+    for i in 1..count {
+        let target = len * i / count;
+
+        // SAFETY: `starts` is never empty.
+        let last_start = starts[starts.len() - 1];
+
+        if target <= last_start {
+            continue;
+        }
+
+        match find_next_block(reader, target)? {
+            Some(start) if start > last_start => starts.push(start),
+            Some(_) => {}
+            None => break,
+        }
+    }
+
+    let mut ranges = Vec::with_capacity(starts.len());
+
+    for (i, &start) in starts.iter().enumerate() {
+        let end = starts.get(i + 1).copied().unwrap_or(len);
+        ranges.push(start..end);
+    }
+
+    Ok(ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use super::*;
+
+    fn build_data() -> io::Result<Vec<u8>> {
+        let mut writer = crate::Writer::new(Vec::new());
+
+        for i in 0..8 {
+            // Make the data incompressible-ish to get larger blocks.
+            let buf: Vec<u8> = (0..4096u32)
+                .map(|j| (j.wrapping_mul(2654435761) >> (i + 3)) as u8)
+                .collect();
+
+            writer.write_all(&buf)?;
+            writer.flush()?;
+        }
+
+        writer.finish()
+    }
+
+    #[test]
+    fn test_find_next_block() -> io::Result<()> {
+        let data = build_data()?;
+        let index = crate::gzi::Indexer::new(&data[..]).build_index()?;
+
+        let mut reader = Cursor::new(&data);
+
+        for window in index.windows(2) {
+            let (start, _) = window[0];
+            let (next_start, _) = window[1];
+
+            assert_eq!(find_next_block(&mut reader, start)?, Some(start));
+            assert_eq!(find_next_block(&mut reader, start + 1)?, Some(next_start));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_next_block_with_corrupt_block() -> io::Result<()> {
+        let mut data = build_data()?;
+        let index = crate::gzi::Indexer::new(&data[..]).build_index()?;
+
+        // Corrupt the CDATA of block 1.
+        let (start, _) = index[1];
+        data[start as usize + BGZF_HEADER_SIZE] ^= 0xff;
+
+        let mut reader = Cursor::new(&data);
+        assert_eq!(find_next_block(&mut reader, 1)?, Some(index[2].0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_byte_ranges() -> io::Result<()> {
+        let data = build_data()?;
+        let index = crate::gzi::Indexer::new(&data[..]).build_index()?;
+        let block_starts: Vec<_> = index.iter().map(|&(start, _)| start).collect();
+
+        let mut reader = Cursor::new(&data);
+        let count = NonZeroUsize::new(3).unwrap();
+        let ranges = byte_ranges(&mut reader, count)?;
+
+        assert_eq!(ranges.len(), 3);
+        assert_eq!(ranges[0].start, 0);
+        assert_eq!(ranges[2].end, data.len() as u64);
+
+        for window in ranges.windows(2) {
+            assert_eq!(window[0].end, window[1].start);
+            assert!(block_starts.contains(&window[1].start));
+        }
+
+        let count = NonZeroUsize::new(64).unwrap();
+        let ranges = byte_ranges(&mut reader, count)?;
+        assert!(ranges.len() <= block_starts.len() + 1);
+
+        let mut reader = Cursor::new(Vec::new());
+        assert!(byte_ranges(&mut reader, count)?.is_empty());
+
+        Ok(())
+    }
+}
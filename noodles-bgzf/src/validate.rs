@@ -0,0 +1,478 @@
+//! BGZF stream validation and salvage.
+//!
+//! [`validate`] walks every block in a BGZF stream and reports problems with the position of the
+//! block in which they occur. [`salvage`] copies all intact blocks to a new stream and appends a
+//! BGZF EOF marker, which recovers, e.g., truncated files, files missing an EOF marker, or files
+//! with corrupt blocks.
+
+use std::{
+    error, fmt,
+    io::{self, Read, Seek, SeekFrom, Write},
+};
+
+use flate2::Crc;
+
+use crate::{
+    gz,
+    reader::block::{parse_trailer, read_raw_frame_into, Frame},
+    split::find_next_block,
+    writer::BGZF_EOF,
+    BGZF_HEADER_SIZE, BGZF_MAX_ISIZE,
+};
+
+/// A BGZF stream validation error.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Error {
+    position: u64,
+    kind: ErrorKind,
+}
+
+impl Error {
+    /// Returns the position of the compressed block where the error occurred.
+    ///
+    /// For [`ErrorKind::MissingEofMarker`], this is the end of the stream.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the kind of error.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.kind, self.position)
+    }
+}
+
+/// A BGZF stream validation error kind.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// The gzip header or BGZF extra subfield is invalid.
+    InvalidHeader,
+    /// The block size (`BSIZE` + 1) is too small to hold a header and trailer.
+    InvalidBlockSize(usize),
+    /// The stream ends before the end of the block.
+    Truncated,
+    /// The compressed data (`CDATA`) fails to inflate.
+    InvalidData,
+    /// The uncompressed data size does not match `ISIZE`.
+    SizeMismatch {
+        /// The size recorded in the block trailer (`ISIZE`).
+        expected: usize,
+        /// The actual size of the inflated data.
+        actual: usize,
+    },
+    /// The CRC32 of the uncompressed data does not match `CRC32`.
+    ChecksumMismatch {
+        /// The checksum recorded in the block trailer (`CRC32`).
+        expected: u32,
+        /// The actual checksum of the inflated data.
+        actual: u32,
+    },
+    /// The stream does not end with a BGZF EOF marker.
+    MissingEofMarker,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeader => f.write_str("invalid BGZF header"),
+            Self::InvalidBlockSize(n) => write!(f, "invalid block size: {}", n),
+            Self::Truncated => f.write_str("truncated block"),
+            Self::InvalidData => f.write_str("invalid block data"),
+            Self::SizeMismatch { expected, actual } => write!(
+                f,
+                "uncompressed size mismatch: expected {}, got {}",
+                expected, actual
+            ),
+            Self::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "block data checksum mismatch: expected {:08x}, got {:08x}",
+                expected, actual
+            ),
+            Self::MissingEofMarker => f.write_str("missing BGZF EOF marker"),
+        }
+    }
+}
+
+/// Validates every block in a BGZF stream.
+///
+/// Each block has its header, block size (`BSIZE`), checksum (`CRC32`), and uncompressed size
+/// (`ISIZE`) checked. The stream is also checked to end with a BGZF EOF marker.
+///
+/// An invalid block is reported, and validation continues with the next valid block after it,
+/// which is found using [`crate::split::find_next_block`]. This includes blocks with an invalid
+/// header or block size, where the start of the next block cannot be determined from the block
+/// itself. A truncated block at the end of the stream is followed by a
+/// [`ErrorKind::MissingEofMarker`] error.
+///
+/// Error positions are stream positions, i.e., they are relative to the start of the stream,
+/// not the position of the reader when this is called.
+///
+/// An empty list means the stream is valid. I/O errors from the underlying reader are returned
+/// as an `Err`.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Cursor, Write};
+/// use noodles_bgzf as bgzf;
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// let data = writer.finish()?;
+///
+/// let errors = bgzf::validate(Cursor::new(&data[..]))?;
+/// assert!(errors.is_empty());
+///
+/// let errors = bgzf::validate(Cursor::new(&data[..data.len() - 1]))?;
+/// assert_eq!(errors.len(), 2);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn validate<R>(reader: R) -> io::Result<Vec<Error>>
+where
+    R: Read + Seek,
+{
+    walk(reader, |_, _| Ok(()))
+}
+
+/// Copies all intact blocks of a BGZF stream to a new BGZF stream.
+///
+/// Blocks that fail validation are skipped, as are empty blocks, including EOF markers. Intact
+/// blocks after an invalid block are still copied. A single BGZF EOF marker is appended to the
+/// output. Blocks are copied as is and not recompressed.
+///
+/// This returns the list of validation errors encountered in the input, i.e., the same list
+/// returned by [`validate`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Cursor, Write};
+/// use noodles_bgzf as bgzf;
+///
+/// let mut writer = bgzf::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// let data = writer.finish()?;
+///
+/// // Truncate the EOF marker.
+/// let src = Cursor::new(&data[..data.len() - 4]);
+///
+/// let mut dst = Vec::new();
+/// let errors = bgzf::salvage(src, &mut dst)?;
+/// assert_eq!(errors.len(), 2);
+///
+/// assert_eq!(dst, data);
+/// assert!(bgzf::validate(Cursor::new(&dst))?.is_empty());
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn salvage<R, W>(reader: R, mut writer: W) -> io::Result<Vec<Error>>
+where
+    R: Read + Seek,
+    W: Write,
+{
+    let errors = walk(reader, |frame, r#isize| {
+        if r#isize > 0 {
+            writer.write_all(frame)?;
+        }
+
+        Ok(())
+    })?;
+
+    writer.write_all(BGZF_EOF)?;
+
+    Ok(errors)
+}
+
+// Walks each frame in the stream, calling `f` with each intact frame and its uncompressed size.
+fn walk<R, F>(mut reader: R, mut f: F) -> io::Result<Vec<Error>>
+where
+    R: Read + Seek,
+    F: FnMut(&[u8], usize) -> io::Result<()>,
+{
+    let mut errors = Vec::new();
+    let mut buf = Vec::new();
+    let mut data = Vec::new();
+    let mut position = reader.stream_position()?;
+    let mut is_eof_marker = false;
+
+    loop {
+        let kind = match read_raw_frame_into(&mut reader, &mut buf)? {
+            Frame::Eof => break,
+            Frame::Complete => match check_frame(&buf, &mut data)? {
+                Ok(r#isize) => {
+                    is_eof_marker = buf == BGZF_EOF;
+                    f(&buf, r#isize)?;
+                    position += buf.len() as u64;
+                    continue;
+                }
+                Err(kind) => kind,
+            },
+            Frame::Truncated => ErrorKind::Truncated,
+            Frame::InvalidHeader => ErrorKind::InvalidHeader,
+            Frame::InvalidBlockSize(n) => ErrorKind::InvalidBlockSize(n),
+        };
+
+        errors.push(Error { position, kind });
+        is_eof_marker = false;
+
+        // The block size of an invalid block cannot be trusted, so the start of the next block is
+        // found by scanning the stream.
+        match find_next_block(&mut reader, position + 1)? {
+            Some(pos) => {
+                reader.seek(SeekFrom::Start(pos))?;
+                position = pos;
+            }
+            None => {
+                position = reader.seek(SeekFrom::End(0))?;
+                break;
+            }
+        }
+    }
+
+    if !is_eof_marker {
+        errors.push(Error {
+            position,
+            kind: ErrorKind::MissingEofMarker,
+        });
+    }
+
+    Ok(errors)
+}
+
+// Inflates a frame and checks its trailer.
+//
+// On success, this returns the uncompressed size of the block.
+pub(crate) fn check_frame(
+    frame: &[u8],
+    data: &mut Vec<u8>,
+) -> io::Result<Result<usize, ErrorKind>> {
+    use flate2::bufread::DeflateDecoder;
+
+    let n = frame.len() - gz::TRAILER_SIZE;
+    let cdata = &frame[BGZF_HEADER_SIZE..n];
+    let (crc32, r#isize) = parse_trailer(&frame[n..])?;
+
+    data.clear();
+
+    // Read at most one byte past the max ISIZE to detect oversized data.
+    let mut decoder = DeflateDecoder::new(cdata).take(BGZF_MAX_ISIZE as u64 + 1);
+
+    if decoder.read_to_end(data).is_err() {
+        return Ok(Err(ErrorKind::InvalidData));
+    }
+
+    if data.len() != r#isize {
+        return Ok(Err(ErrorKind::SizeMismatch {
+            expected: r#isize,
+            actual: data.len(),
+        }));
+    }
+
+    let mut crc = Crc::new();
+    crc.update(data);
+
+    if crc.sum() != crc32 {
+        return Ok(Err(ErrorKind::ChecksumMismatch {
+            expected: crc32,
+            actual: crc.sum(),
+        }));
+    }
+
+    Ok(Ok(r#isize))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn build_data() -> io::Result<Vec<u8>> {
+        let mut writer = crate::Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        writer.finish()
+    }
+
+    #[test]
+    fn test_validate() -> io::Result<()> {
+        let data = build_data()?;
+        assert!(validate(Cursor::new(&data))?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_with_empty_stream() -> io::Result<()> {
+        let data = [];
+
+        assert_eq!(
+            validate(Cursor::new(&data))?,
+            [Error {
+                position: 0,
+                kind: ErrorKind::MissingEofMarker,
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_with_checksum_mismatch() -> io::Result<()> {
+        let mut data = build_data()?;
+
+        // block 1 CRC32
+        data[59] ^= 0xff;
+
+        let errors = validate(Cursor::new(&data))?;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].position(), 35);
+        assert!(matches!(
+            errors[0].kind(),
+            ErrorKind::ChecksumMismatch { .. }
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_with_size_mismatch() -> io::Result<()> {
+        let mut data = build_data()?;
+
+        // block 0 ISIZE
+        data[31] = 0x08;
+
+        assert_eq!(
+            validate(Cursor::new(&data))?,
+            [Error {
+                position: 0,
+                kind: ErrorKind::SizeMismatch {
+                    expected: 8,
+                    actual: 7
+                },
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_with_invalid_header() -> io::Result<()> {
+        let mut data = build_data()?;
+
+        // block 1 SI1
+        data[35 + 12] = b'X';
+
+        assert_eq!(
+            validate(Cursor::new(&data))?,
+            [Error {
+                position: 35,
+                kind: ErrorKind::InvalidHeader,
+            },]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_with_invalid_block_size() -> io::Result<()> {
+        let mut data = build_data()?;
+
+        // block 0 BSIZE = 0
+        data[16] = 0x00;
+        data[17] = 0x00;
+
+        assert_eq!(
+            validate(Cursor::new(&data))?,
+            [Error {
+                position: 0,
+                kind: ErrorKind::InvalidBlockSize(1),
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_with_truncated_stream() -> io::Result<()> {
+        let data = build_data()?;
+        let src = &data[..40];
+
+        assert_eq!(
+            validate(Cursor::new(src))?,
+            [
+                Error {
+                    position: 35,
+                    kind: ErrorKind::Truncated,
+                },
+                Error {
+                    position: 40,
+                    kind: ErrorKind::MissingEofMarker,
+                }
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_salvage() -> io::Result<()> {
+        let mut data = build_data()?;
+
+        // block 0 CRC32
+        data[27] ^= 0xff;
+
+        let mut dst = Vec::new();
+        let errors = salvage(Cursor::new(&data[..data.len() - BGZF_EOF.len()]), &mut dst)?;
+        assert_eq!(errors.len(), 2);
+
+        let mut expected = data[35..67].to_vec();
+        expected.extend_from_slice(BGZF_EOF);
+        assert_eq!(dst, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_salvage_with_invalid_block_in_middle() -> io::Result<()> {
+        let mut writer = crate::Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        writer.flush()?;
+        writer.write_all(b"vcf")?;
+        let mut data = writer.finish()?;
+
+        // block 1 SI1
+        data[35 + 12] = b'X';
+
+        let mut dst = Vec::new();
+        let errors = salvage(Cursor::new(&data), &mut dst)?;
+
+        assert_eq!(
+            errors,
+            [Error {
+                position: 35,
+                kind: ErrorKind::InvalidHeader,
+            }]
+        );
+
+        // blocks 0 and 2 and the EOF marker
+        let mut expected = data[..35].to_vec();
+        expected.extend_from_slice(&data[67..]);
+        assert_eq!(dst, expected);
+
+        let mut reader = crate::Reader::new(&dst[..]);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"noodlesvcf");
+
+        Ok(())
+    }
+}
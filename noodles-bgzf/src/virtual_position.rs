@@ -81,6 +81,31 @@ impl VirtualPosition {
     pub fn uncompressed(self) -> u16 {
         (self.0 & UNCOMPRESSED_POSITION_MASK) as u16
     }
+
+    /// Adds an offset to the compressed position.
+    ///
+    /// This is typically used to rewrite virtual positions after the blocks they point to are
+    /// moved, e.g., when concatenating streams (see [`crate::concat()`]). The uncompressed position
+    /// is unchanged.
+    ///
+    /// This returns `None` if the compressed position overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let virtual_position = bgzf::VirtualPosition::try_from((57, 6086))?;
+    /// assert_eq!(
+    ///     virtual_position.checked_shift(8),
+    ///     Some(bgzf::VirtualPosition::try_from((65, 6086))?)
+    /// );
+    /// # Ok::<_, bgzf::virtual_position::TryFromU64U16TupleError>(())
+    /// ```
+    pub fn checked_shift(self, offset: u64) -> Option<Self> {
+        self.compressed()
+            .checked_add(offset)
+            .and_then(|compressed_pos| Self::try_from((compressed_pos, self.uncompressed())).ok())
+    }
 }
 
 impl From<u64> for VirtualPosition {
@@ -187,6 +212,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_checked_shift() {
+        let virtual_position = VirtualPosition::from(88384945211);
+        assert_eq!(
+            virtual_position.checked_shift(0),
+            Some(VirtualPosition::from(88384945211))
+        );
+        assert_eq!(
+            virtual_position.checked_shift(1),
+            Some(VirtualPosition::from(88384945211 + (1 << 16)))
+        );
+
+        assert!(VirtualPosition::max().checked_shift(1).is_none());
+        assert!(VirtualPosition::from(1 << 16)
+            .checked_shift(u64::MAX)
+            .is_none());
+    }
+
     #[test]
     fn test_from_virtual_position_for_u64() {
         assert_eq!(u64::from(VirtualPosition::from(88384945211)), 88384945211);
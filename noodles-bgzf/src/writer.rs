@@ -2,18 +2,21 @@
 
 mod builder;
 mod compression_level;
+mod deflater;
 
 pub use self::{builder::Builder, compression_level::CompressionLevel};
 
 use std::{
     cmp,
     io::{self, Write},
+    mem,
 };
 
 use byteorder::{LittleEndian, WriteBytesExt};
 use flate2::Crc;
 
-use super::{gz, VirtualPosition, BGZF_HEADER_SIZE, BGZF_MAX_ISIZE};
+use self::deflater::Deflater;
+use super::{gz, gzi, VirtualPosition, BGZF_HEADER_SIZE, BGZF_MAX_ISIZE};
 
 // The max DEFLATE overhead for 65536 bytes of data at compression level 0.
 //
@@ -62,6 +65,14 @@ type CompressionLevelImpl = flate2::Compression;
 ///
 /// This implements [`std::io::Write`], consuming uncompressed data and emitting compressed data.
 ///
+/// By default, blocks are compressed on the calling thread. When built with a worker count > 1
+/// (see [`Builder::set_worker_count`]), blocks are compressed in parallel by a pool of worker
+/// threads and written to the underlying writer in order.
+///
+/// By default, data is split into blocks purely by size. To keep logical units (e.g., records)
+/// from spanning blocks, use [`Self::ensure_block_capacity`] before writing each unit, or build
+/// the writer with [`Builder::set_aligned_writes`] to do so for every write call.
+///
 /// # Examples
 ///
 /// ```
@@ -81,8 +92,12 @@ where
 {
     inner: Option<W>,
     position: u64,
+    uncompressed_position: u64,
     buf: Vec<u8>,
     compression_level: CompressionLevelImpl,
+    deflater: Option<Deflater>,
+    gzi_index: Option<gzi::Index>,
+    is_aligned_writes: bool,
 }
 
 impl<W> Writer<W>
@@ -140,8 +155,48 @@ where
         self.position
     }
 
+    /// Returns the current uncompressed position of the stream.
+    ///
+    /// This is the total size of the uncompressed data written, including buffered and in-flight
+    /// blocks. Unlike [`Self::virtual_position`], this never waits for blocks to be compressed.
+    ///
+    /// When the writer uses multiple workers, this can be recorded instead of the virtual position
+    /// and later resolved using the gzip index (see [`Builder::set_gzi_index_enabled`]), after
+    /// the block containing it is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// writer.flush_block()?;
+    /// writer.write_all(b"bgzf")?;
+    ///
+    /// assert_eq!(writer.uncompressed_position(), 11);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn uncompressed_position(&self) -> u64 {
+        let pending_uncompressed_len = self
+            .deflater
+            .as_ref()
+            .map(|deflater| deflater.pending_uncompressed_len())
+            .unwrap_or_default();
+
+        self.uncompressed_position + pending_uncompressed_len + self.buf.len() as u64
+    }
+
     /// Returns the current virtual position of the stream.
     ///
+    /// When the writer uses multiple workers, the compressed position depends on the sizes of
+    /// the in-flight blocks, so this waits for them to be compressed. To avoid waiting, e.g., when
+    /// recording the position of every record, use [`Self::uncompressed_position`] instead.
+    ///
+    /// An in-flight block that failed to compress is not counted. Its error is returned by the
+    /// next call to [`Write::write`], [`Write::flush`], or [`Self::finish`].
+    ///
     /// # Panics
     ///
     /// This panics if the stream flushed >= 256 TiB of compressed data.
@@ -156,10 +211,82 @@ where
     pub fn virtual_position(&self) -> VirtualPosition {
         // SAFETY: The uncompressed buffer is guaranteed to be <= `MAX_UNCOMPRESSED_POSITION`.
         let uncompressed_position = self.buf.len() as u16;
-        VirtualPosition::try_from((self.position, uncompressed_position)).unwrap()
+
+        let compressed_position = match self.deflater.as_ref() {
+            Some(deflater) => self.position + deflater.pending_len(),
+            None => self.position,
+        };
+
+        VirtualPosition::try_from((compressed_position, uncompressed_position)).unwrap()
+    }
+
+    /// Ends the current block.
+    ///
+    /// Any buffered uncompressed data is compressed as a block, and the next write starts a new
+    /// block. This does nothing if no data is buffered.
+    ///
+    /// Unlike [`Write::flush`], this does not wait for in-flight blocks to be written to the
+    /// underlying writer when using multiple workers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// writer.flush_block()?;
+    ///
+    /// assert_eq!(writer.virtual_position(), bgzf::VirtualPosition::try_from((35, 0))?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn flush_block(&mut self) -> io::Result<()> {
+        if self.buf.is_empty() {
+            Ok(())
+        } else {
+            self.deflate_block()
+        }
+    }
+
+    /// Ends the current block if it cannot hold the given number of bytes.
+    ///
+    /// This guarantees that the next `len` bytes written are not split across blocks, provided
+    /// `len` is <= the max block data size (slightly less than 64 KiB). Larger amounts always
+    /// span multiple blocks.
+    ///
+    /// This is typically called before writing a logical unit, e.g., a record, so that the unit
+    /// starts and ends in the same block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    ///
+    /// writer.ensure_block_capacity(8)?;
+    /// assert_eq!(writer.virtual_position(), bgzf::VirtualPosition::try_from((0, 7))?);
+    ///
+    /// writer.ensure_block_capacity(65490)?;
+    /// assert_eq!(writer.virtual_position(), bgzf::VirtualPosition::try_from((35, 0))?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn ensure_block_capacity(&mut self, len: usize) -> io::Result<()> {
+        if len <= MAX_BUF_SIZE && self.buf.len() + len > MAX_BUF_SIZE {
+            self.flush_block()
+        } else {
+            Ok(())
+        }
     }
 
-    fn flush_block(&mut self) -> io::Result<()> {
+    fn deflate_block(&mut self) -> io::Result<()> {
+        if self.deflater.is_some() {
+            return self.send_block();
+        }
+
         let (cdata, crc32, r#isize) = deflate_data(&self.buf, self.compression_level)?;
 
         let inner = self.inner.as_mut().unwrap();
@@ -169,13 +296,98 @@ where
         inner.write_all(&cdata[..])?;
         write_trailer(inner, crc32, r#isize)?;
 
-        self.position += block_size as u64;
+        self.add_block(block_size as u64, r#isize);
 
         self.buf.clear();
 
         Ok(())
     }
 
+    fn send_block(&mut self) -> io::Result<()> {
+        let deflater = self.deflater.as_mut().unwrap();
+
+        if deflater.is_full() {
+            self.write_next_frame()?;
+        }
+
+        let buf = mem::replace(&mut self.buf, Vec::with_capacity(MAX_BUF_SIZE));
+        self.deflater.as_mut().unwrap().send(buf);
+
+        Ok(())
+    }
+
+    fn write_next_frame(&mut self) -> io::Result<bool> {
+        match self
+            .deflater
+            .as_mut()
+            .and_then(|deflater| deflater.next_frame())
+        {
+            Some(result) => self.write_frame(result).map(|_| true),
+            None => Ok(false),
+        }
+    }
+
+    // Writes frames that are already compressed. This also returns the error of a frame that
+    // failed to compress without waiting for the in-flight queue to fill.
+    fn write_ready_frames(&mut self) -> io::Result<()> {
+        while let Some(result) = self
+            .deflater
+            .as_mut()
+            .and_then(|deflater| deflater.try_next_frame())
+        {
+            self.write_frame(result)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_frame(&mut self, result: io::Result<Vec<u8>>) -> io::Result<()> {
+        let frame = result?;
+        self.inner.as_mut().unwrap().write_all(&frame)?;
+
+        let r#isize = read_frame_isize(&frame);
+        self.add_block(frame.len() as u64, r#isize);
+
+        Ok(())
+    }
+
+    fn add_block(&mut self, block_size: u64, r#isize: u32) {
+        self.position += block_size;
+        self.uncompressed_position += u64::from(r#isize);
+
+        if let Some(index) = self.gzi_index.as_mut() {
+            // SAFETY: The index is initialized with the initial block.
+            let (_, uncompressed_position) = index[index.len() - 1];
+            index.push((self.position, uncompressed_position + u64::from(r#isize)));
+        }
+    }
+
+    /// Returns the gzip index built from the blocks written so far.
+    ///
+    /// This is only available when the writer is built with
+    /// [`Builder::set_gzi_index_enabled`]. The index is complete after the stream is finished
+    /// (e.g., using [`Self::try_finish`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::writer::Builder::default()
+    ///     .set_gzi_index_enabled(true)
+    ///     .build_with_writer(Vec::new());
+    ///
+    /// writer.write_all(b"noodles")?;
+    /// writer.try_finish()?;
+    ///
+    /// assert_eq!(writer.gzi_index(), Some(&vec![(0, 0), (35, 7)]));
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn gzi_index(&self) -> Option<&gzi::Index> {
+        self.gzi_index.as_ref()
+    }
+
     /// Attempts to finish the output stream by flushing any remaining buffers.
     ///
     /// This then appends the final BGZF EOF block.
@@ -242,23 +454,29 @@ where
     W: Write,
 {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_ready_frames()?;
+
+        if self.is_aligned_writes {
+            self.ensure_block_capacity(buf.len())?;
+        }
+
         let max_write_len = cmp::min(MAX_BUF_SIZE - self.buf.len(), buf.len());
 
         self.buf.extend_from_slice(&buf[..max_write_len]);
 
         if self.buf.len() >= MAX_BUF_SIZE {
-            self.flush()?;
+            self.deflate_block()?;
         }
 
         Ok(max_write_len)
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        if self.buf.is_empty() {
-            Ok(())
-        } else {
-            self.flush_block()
-        }
+        self.flush_block()?;
+
+        while self.write_next_frame()? {}
+
+        Ok(())
     }
 }
 
@@ -285,6 +503,25 @@ where
     Ok(())
 }
 
+// Compresses the given data as a complete BGZF block (header, CDATA, and trailer).
+fn encode_frame(data: &[u8], compression_level: CompressionLevelImpl) -> io::Result<Vec<u8>> {
+    let (cdata, crc32, r#isize) = deflate_data(data, compression_level)?;
+
+    let block_size = BGZF_HEADER_SIZE + cdata.len() + gz::TRAILER_SIZE;
+    let mut frame = Vec::with_capacity(block_size);
+
+    write_header(&mut frame, block_size)?;
+    frame.extend_from_slice(&cdata);
+    write_trailer(&mut frame, crc32, r#isize)?;
+
+    Ok(frame)
+}
+
+fn read_frame_isize(frame: &[u8]) -> u32 {
+    let n = frame.len() - 4;
+    u32::from_le_bytes([frame[n], frame[n + 1], frame[n + 2], frame[n + 3]])
+}
+
 fn write_trailer<W>(writer: &mut W, checksum: u32, uncompressed_size: u32) -> io::Result<()>
 where
     W: Write,
@@ -364,6 +601,100 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_virtual_position_with_multiple_workers() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        let mut writer = Builder::default()
+            .set_worker_count(NonZeroUsize::try_from(2)?)
+            .build_with_writer(Vec::new());
+
+        writer.write_all(b"noodles")?;
+        writer.flush_block()?;
+        writer.write_all(b"bgzf")?;
+
+        let expected_cpos = encode_frame(b"noodles", writer.compression_level)?.len() as u64;
+
+        assert_eq!(
+            writer.virtual_position(),
+            VirtualPosition::try_from((expected_cpos, 4))?
+        );
+        assert_eq!(writer.uncompressed_position(), 11);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_finish_with_multiple_workers() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        let data: Vec<u8> = (0..4 * MAX_BUF_SIZE).map(|i| (i % 251) as u8).collect();
+
+        let mut writer = Builder::default().build_with_writer(Vec::new());
+        writer.write_all(&data)?;
+        let expected = writer.finish()?;
+
+        let mut writer = Builder::default()
+            .set_worker_count(NonZeroUsize::try_from(3)?)
+            .build_with_writer(Vec::new());
+        writer.write_all(&data)?;
+        let actual = writer.finish()?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gzi_index() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use crate::gzi;
+
+        let data: Vec<u8> = (0..3 * MAX_BUF_SIZE).map(|i| (i % 251) as u8).collect();
+
+        for worker_count in [1, 2] {
+            let mut writer = Builder::default()
+                .set_worker_count(NonZeroUsize::try_from(worker_count)?)
+                .set_gzi_index_enabled(true)
+                .build_with_writer(Vec::new());
+
+            writer.write_all(&data)?;
+            writer.try_finish()?;
+
+            let actual = writer.gzi_index().cloned().ok_or("missing index")?;
+            let expected = gzi::Indexer::new(&writer.get_ref()[..]).build_index()?;
+
+            assert_eq!(actual.len(), 4);
+            assert_eq!(actual, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_aligned_writes() -> io::Result<()> {
+        let record = vec![b'n'; MAX_BUF_SIZE / 3 + 1];
+
+        let mut writer = Builder::default()
+            .set_aligned_writes(true)
+            .set_gzi_index_enabled(true)
+            .build_with_writer(Vec::new());
+
+        for _ in 0..4 {
+            writer.write_all(&record)?;
+        }
+
+        writer.try_finish()?;
+
+        let index = writer.gzi_index().cloned().unwrap();
+        let uncompressed_positions: Vec<_> = index.iter().map(|(_, upos)| *upos).collect();
+        let record_len = record.len() as u64;
+        assert_eq!(uncompressed_positions, [0, 2 * record_len, 4 * record_len]);
+
+        Ok(())
+    }
+
     #[test]
     fn test_finish() -> io::Result<()> {
         let mut writer = Writer::new(Vec::new());
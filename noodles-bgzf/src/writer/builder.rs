@@ -1,11 +1,19 @@
-use std::io::Write;
+use std::{io::Write, num::NonZeroUsize};
 
-use super::{CompressionLevel, Writer, MAX_BUF_SIZE};
+use super::{CompressionLevel, Deflater, Writer, MAX_BUF_SIZE};
+
+const DEFAULT_WORKER_COUNT: NonZeroUsize = match NonZeroUsize::new(1) {
+    Some(worker_count) => worker_count,
+    None => unreachable!(),
+};
 
 /// A BGZF writer builder.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Builder {
     compression_level: CompressionLevel,
+    worker_count: NonZeroUsize,
+    is_gzi_index_enabled: bool,
+    is_aligned_writes: bool,
 }
 
 impl Builder {
@@ -26,6 +34,70 @@ impl Builder {
         self
     }
 
+    /// Sets the worker count.
+    ///
+    /// When the worker count is > 1, blocks are compressed in parallel using a pool of threads.
+    ///
+    /// By default, the worker count is set to 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let worker_count = NonZeroUsize::try_from(4)?;
+    /// let builder = bgzf::writer::Builder::default().set_worker_count(worker_count);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = worker_count;
+        self
+    }
+
+    /// Sets whether to build a gzip index (GZI) while writing.
+    ///
+    /// When enabled, the writer records the compressed and uncompressed positions of each block it
+    /// writes. The index is available using [`Writer::gzi_index`].
+    ///
+    /// By default, building a gzip index is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::writer::Builder::default().set_gzi_index_enabled(true);
+    /// ```
+    pub fn set_gzi_index_enabled(mut self, is_gzi_index_enabled: bool) -> Self {
+        self.is_gzi_index_enabled = is_gzi_index_enabled;
+        self
+    }
+
+    /// Sets whether each write is kept within a single block.
+    ///
+    /// When enabled, a write that does not fit in the current block starts a new block, unless it
+    /// is larger than a block. This is equivalent to calling [`Writer::ensure_block_capacity`]
+    /// before every write.
+    ///
+    /// Alignment is per write, so a unit written in more than one write can still be split across
+    /// blocks. The BAM, BCF, and VCF writers therefore encode each record in full (for BAM,
+    /// including its block size) into a buffer and write it using a single call to
+    /// [`Write::write_all`], which keeps every record that fits in a block within one block.
+    ///
+    /// By default, aligned writes are disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::writer::Builder::default().set_aligned_writes(true);
+    /// ```
+    pub fn set_aligned_writes(mut self, is_aligned_writes: bool) -> Self {
+        self.is_aligned_writes = is_aligned_writes;
+        self
+    }
+
     /// Builds a BGZF writer from a writer..
     ///
     /// # Examples
@@ -39,11 +111,34 @@ impl Builder {
     where
         W: Write,
     {
+        let compression_level = self.compression_level.into();
+
+        let deflater = if self.worker_count.get() == 1 {
+            None
+        } else {
+            Some(Deflater::new(self.worker_count, compression_level))
+        };
+
         Writer {
             inner: Some(writer),
             position: 0,
+            uncompressed_position: 0,
             buf: Vec::with_capacity(MAX_BUF_SIZE),
-            compression_level: self.compression_level.into(),
+            compression_level,
+            deflater,
+            gzi_index: self.is_gzi_index_enabled.then(|| vec![(0, 0)]),
+            is_aligned_writes: self.is_aligned_writes,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            compression_level: CompressionLevel::default(),
+            worker_count: DEFAULT_WORKER_COUNT,
+            is_gzi_index_enabled: false,
+            is_aligned_writes: false,
         }
     }
 }
@@ -0,0 +1,238 @@
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    io,
+    num::NonZeroUsize,
+    thread::{self, JoinHandle},
+};
+
+use crossbeam_channel::{Receiver, Sender, TryRecvError};
+
+use super::CompressionLevelImpl;
+
+type FrameTx = Sender<io::Result<Vec<u8>>>;
+type FrameRx = Receiver<io::Result<Vec<u8>>>;
+type DeflaterTx = Sender<(Vec<u8>, FrameTx)>;
+type DeflaterRx = Receiver<(Vec<u8>, FrameTx)>;
+
+#[derive(Debug)]
+enum Frame {
+    Pending(FrameRx),
+    Ready(io::Result<Vec<u8>>),
+}
+
+impl Frame {
+    fn resolve(&mut self) {
+        if let Self::Pending(frame_rx) = self {
+            let result = frame_rx.recv().unwrap_or_else(|_| {
+                Err(io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    "deflater worker disconnected",
+                ))
+            });
+
+            *self = Self::Ready(result);
+        }
+    }
+
+    fn into_result(mut self) -> io::Result<Vec<u8>> {
+        self.resolve();
+
+        match self {
+            Self::Ready(result) => result,
+            Self::Pending(_) => unreachable!(),
+        }
+    }
+}
+
+/// A pool of workers that compresses block data into BGZF frames.
+///
+/// Frames are returned in the order their data was sent, regardless of which worker compressed
+/// them.
+#[derive(Debug)]
+pub(crate) struct Deflater {
+    deflater_tx: Option<DeflaterTx>,
+    deflater_handles: Vec<JoinHandle<()>>,
+    // Each frame is queued with the length of its uncompressed data.
+    queue: RefCell<VecDeque<(usize, Frame)>>,
+    pending_uncompressed_len: u64,
+    worker_count: usize,
+}
+
+impl Deflater {
+    pub fn new(worker_count: NonZeroUsize, compression_level: CompressionLevelImpl) -> Self {
+        let worker_count = worker_count.get();
+
+        let (deflater_tx, deflater_rx) = crossbeam_channel::bounded(worker_count);
+        let deflater_handles = spawn_deflaters(worker_count, compression_level, deflater_rx);
+
+        Self {
+            deflater_tx: Some(deflater_tx),
+            deflater_handles,
+            queue: RefCell::new(VecDeque::with_capacity(worker_count)),
+            pending_uncompressed_len: 0,
+            worker_count,
+        }
+    }
+
+    /// Returns whether the number of in-flight frames has reached the worker count.
+    pub fn is_full(&self) -> bool {
+        self.queue.borrow().len() >= self.worker_count
+    }
+
+    /// Queues uncompressed block data to be compressed.
+    pub fn send(&mut self, buf: Vec<u8>) {
+        let (frame_tx, frame_rx) = crossbeam_channel::bounded(1);
+        let len = buf.len();
+
+        self.deflater_tx
+            .as_ref()
+            .unwrap()
+            .send((buf, frame_tx))
+            .unwrap();
+
+        self.queue
+            .get_mut()
+            .push_back((len, Frame::Pending(frame_rx)));
+
+        self.pending_uncompressed_len += len as u64;
+    }
+
+    /// Returns the next frame in send order, waiting for it to be compressed if necessary.
+    pub fn next_frame(&mut self) -> Option<io::Result<Vec<u8>>> {
+        let (len, frame) = self.queue.get_mut().pop_front()?;
+        self.pending_uncompressed_len -= len as u64;
+        Some(frame.into_result())
+    }
+
+    /// Returns the next frame in send order if it is already compressed.
+    ///
+    /// Unlike [`Self::next_frame`], this does not wait for the frame to be compressed.
+    pub fn try_next_frame(&mut self) -> Option<io::Result<Vec<u8>>> {
+        let (_, frame) = self.queue.get_mut().front_mut()?;
+
+        if let Frame::Pending(frame_rx) = frame {
+            match frame_rx.try_recv() {
+                Ok(result) => *frame = Frame::Ready(result),
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => frame.resolve(),
+            }
+        }
+
+        self.next_frame()
+    }
+
+    /// Returns the total size of the uncompressed data of all in-flight frames.
+    ///
+    /// Unlike [`Self::pending_len`], this does not wait for any data to be compressed.
+    pub fn pending_uncompressed_len(&self) -> u64 {
+        self.pending_uncompressed_len
+    }
+
+    /// Returns the total size of all in-flight frames.
+    ///
+    /// This waits for all queued data to be compressed. A frame that failed to compress is counted
+    /// as empty. Its error is kept in the queue and returned by [`Self::next_frame`].
+    pub fn pending_len(&self) -> u64 {
+        let mut queue = self.queue.borrow_mut();
+
+        queue
+            .iter_mut()
+            .map(|(_, frame)| {
+                frame.resolve();
+
+                match frame {
+                    Frame::Ready(Ok(buf)) => buf.len() as u64,
+                    Frame::Ready(Err(_)) => 0,
+                    Frame::Pending(_) => unreachable!(),
+                }
+            })
+            .sum()
+    }
+
+    fn shutdown(&mut self) {
+        self.deflater_tx.take();
+
+        for handle in self.deflater_handles.drain(..) {
+            handle.join().unwrap();
+        }
+    }
+}
+
+impl Drop for Deflater {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn spawn_deflaters(
+    worker_count: usize,
+    compression_level: CompressionLevelImpl,
+    deflater_rx: DeflaterRx,
+) -> Vec<JoinHandle<()>> {
+    use super::encode_frame;
+
+    let mut handles = Vec::with_capacity(worker_count);
+
+    for _ in 0..worker_count {
+        let deflater_rx = deflater_rx.clone();
+
+        handles.push(thread::spawn(move || {
+            while let Ok((src, frame_tx)) = deflater_rx.recv() {
+                let result = encode_frame(&src, compression_level);
+
+                if frame_tx.send(result).is_err() {
+                    continue;
+                }
+            }
+        }));
+    }
+
+    handles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_frame() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::writer::encode_frame;
+
+        let worker_count = NonZeroUsize::try_from(2)?;
+        let compression_level = CompressionLevelImpl::default();
+        let mut deflater = Deflater::new(worker_count, compression_level);
+
+        assert!(deflater.next_frame().is_none());
+
+        deflater.send(b"noodles".to_vec());
+        assert!(!deflater.is_full());
+        deflater.send(b"bgzf".to_vec());
+        assert!(deflater.is_full());
+
+        let expected = [
+            encode_frame(b"noodles", compression_level)?,
+            encode_frame(b"bgzf", compression_level)?,
+        ];
+
+        assert_eq!(deflater.pending_uncompressed_len(), 11);
+        assert_eq!(
+            deflater.pending_len(),
+            (expected[0].len() + expected[1].len()) as u64
+        );
+
+        // All frames are compressed after getting the pending length.
+        assert_eq!(
+            deflater.try_next_frame().transpose()?,
+            Some(expected[0].clone())
+        );
+        assert_eq!(
+            deflater.next_frame().transpose()?,
+            Some(expected[1].clone())
+        );
+        assert!(deflater.next_frame().is_none());
+        assert_eq!(deflater.pending_uncompressed_len(), 0);
+
+        Ok(())
+    }
+}
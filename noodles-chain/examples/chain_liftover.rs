@@ -0,0 +1,40 @@
+//! Lifts over a region using a chain file.
+//!
+//! A region is printed for each overlapping chain with its strand and the number of mapped bases.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader},
+};
+
+use noodles_chain::{self as chain, Liftover};
+use noodles_core::Region;
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let region: Region = args
+        .next()
+        .expect("missing region")
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let mut reader = File::open(src)
+        .map(BufReader::new)
+        .map(chain::Reader::new)?;
+    let liftover: Liftover = reader.records().collect::<io::Result<_>>()?;
+
+    for mapped_region in liftover.map_region(&region) {
+        println!(
+            "{}\t{}\t{}/{}",
+            mapped_region.region(),
+            mapped_region.strand(),
+            mapped_region.mapped_len(),
+            mapped_region.len()
+        );
+    }
+
+    Ok(())
+}
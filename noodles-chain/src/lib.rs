@@ -0,0 +1,11 @@
+#![warn(missing_docs)]
+
+//! **noodles-chain** handles the reading and writing of the UCSC chain format and the liftover
+//! of coordinates between assemblies.
+
+pub mod liftover;
+mod reader;
+pub mod record;
+mod writer;
+
+pub use self::{liftover::Liftover, reader::Reader, record::Record, writer::Writer};
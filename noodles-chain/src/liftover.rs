@@ -0,0 +1,456 @@
+//! Coordinate liftover.
+
+use std::{cmp, collections::BTreeMap};
+
+use noodles_core::{IntervalIndex, Position, Region};
+
+use super::{record::Strand, Record};
+
+/// A liftover.
+///
+/// This maps positions and regions from the target (source) assembly of a set of chains to their
+/// query (destination) assembly, e.g., using a UCSC `hg19ToHg38.over.chain` file.
+///
+/// A liftover is built by collecting chain records.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_chain::{self as chain, Liftover};
+///
+/// let data = b"chain 21 sq0 100 + 8 21 sq1 50 + 5 16 1\n5\t2\t0\n6\n\n";
+/// let mut reader = chain::Reader::new(&data[..]);
+/// let liftover: Liftover = reader.records().collect::<io::Result<_>>()?;
+/// # Ok::<_, io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Liftover {
+    chains: Vec<Chain>,
+    index: IntervalIndex<Segment>,
+}
+
+#[derive(Debug)]
+struct Chain {
+    score: i64,
+    target_size: usize,
+    query_name: String,
+    query_size: usize,
+    query_strand: Strand,
+}
+
+#[derive(Debug)]
+struct Segment {
+    chain_index: usize,
+    // 0-based, relative to the query strand
+    query_start: usize,
+}
+
+impl Liftover {
+    /// Maps a position in the target assembly to positions in the query assembly.
+    ///
+    /// A position can map to more than one chain. The mapped positions are sorted by chain
+    /// score, from highest to lowest. The list is empty if the position is unmapped, e.g., in a
+    /// gap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain::{self as chain, record::Strand, Liftover};
+    /// use noodles_core::Position;
+    ///
+    /// let record: chain::Record = "chain 21 sq0 100 + 8 21 sq1 50 - 5 16 1\n5\t2\t0\n6".parse()?;
+    /// let liftover: Liftover = [record].into_iter().collect();
+    ///
+    /// let mapped_positions = liftover.map_position("sq0", Position::try_from(9)?);
+    /// assert_eq!(mapped_positions.len(), 1);
+    ///
+    /// let mapped_position = &mapped_positions[0];
+    /// assert_eq!(mapped_position.name(), "sq1");
+    /// assert_eq!(usize::from(mapped_position.position()), 45);
+    /// assert_eq!(mapped_position.strand(), Strand::Reverse);
+    ///
+    /// assert!(liftover.map_position("sq0", Position::try_from(14)?).is_empty());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn map_position(&self, name: &str, position: Position) -> Vec<MappedPosition> {
+        let mut mapped_positions: Vec<_> = self
+            .index
+            .overlapping(name, position..=position)
+            .filter_map(|(interval, segment)| {
+                let segment_start = interval.start()?;
+                let chain = &self.chains[segment.chain_index];
+
+                let query_start =
+                    segment.query_start + (usize::from(position) - usize::from(segment_start));
+
+                let (start, _) = resolve_query_interval(chain, query_start, query_start + 1);
+
+                Position::new(start + 1).map(|position| {
+                    (
+                        chain.score,
+                        MappedPosition {
+                            name: chain.query_name.clone(),
+                            position,
+                            strand: chain.query_strand,
+                        },
+                    )
+                })
+            })
+            .collect();
+
+        mapped_positions.sort_by_key(|(score, _)| cmp::Reverse(*score));
+
+        mapped_positions
+            .into_iter()
+            .map(|(_, mapped_position)| mapped_position)
+            .collect()
+    }
+
+    /// Maps a region in the target assembly to regions in the query assembly.
+    ///
+    /// The region is mapped to one region per overlapping chain, spanning the mapped bases of the
+    /// chain. A region that is not fully covered by the aligned blocks of a chain, e.g., one that
+    /// spans a gap or the end of a chain, is partially mapped (see [`MappedRegion::is_partial`]).
+    ///
+    /// An unbounded start or end of the region is resolved to the start or end of the reference
+    /// sequence, respectively.
+    ///
+    /// The mapped regions are sorted by chain score, from highest to lowest. The list is empty if
+    /// the region is unmapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain::{self as chain, record::Strand, Liftover};
+    /// use noodles_core::{Position, Region};
+    ///
+    /// let record: chain::Record = "chain 21 sq0 100 + 8 21 sq1 50 + 5 16 1\n5\t2\t0\n6".parse()?;
+    /// let liftover: Liftover = [record].into_iter().collect();
+    ///
+    /// let region = Region::new("sq0", Position::try_from(10)?..=Position::try_from(13)?);
+    /// let mapped_regions = liftover.map_region(&region);
+    /// assert_eq!(mapped_regions.len(), 1);
+    ///
+    /// let mapped_region = &mapped_regions[0];
+    /// assert_eq!(mapped_region.region().to_string(), "sq1:7-10");
+    /// assert_eq!(mapped_region.strand(), Strand::Forward);
+    /// assert!(!mapped_region.is_partial());
+    ///
+    /// let region = Region::new("sq0", Position::try_from(12)?..=Position::try_from(17)?);
+    /// let mapped_region = &liftover.map_region(&region)[0];
+    /// assert_eq!(mapped_region.region().to_string(), "sq1:9-12");
+    /// assert_eq!(mapped_region.mapped_len(), 4);
+    /// assert_eq!(mapped_region.len(), 6);
+    /// assert!(!mapped_region.is_empty());
+    /// assert!(mapped_region.is_partial());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn map_region(&self, region: &Region) -> Vec<MappedRegion> {
+        let interval = region.interval();
+        let start = interval.start().unwrap_or(Position::MIN);
+        let end = interval.end().unwrap_or(Position::MAX);
+
+        if start > end {
+            return Vec::new();
+        }
+
+        // chain index => (query start (0-based), query end (exclusive), mapped length)
+        let mut spans: BTreeMap<usize, (usize, usize, usize)> = BTreeMap::new();
+
+        for (interval, segment) in self.index.overlapping(region.name(), start..=end) {
+            let (segment_start, segment_end) = match (interval.start(), interval.end()) {
+                (Some(s), Some(e)) => (s, e),
+                _ => continue,
+            };
+
+            let chain = &self.chains[segment.chain_index];
+
+            let a = cmp::max(start, segment_start);
+            let b = cmp::min(end, segment_end);
+
+            let query_start = segment.query_start + (usize::from(a) - usize::from(segment_start));
+            let len = usize::from(b) - usize::from(a) + 1;
+
+            let (query_start, query_end) =
+                resolve_query_interval(chain, query_start, query_start + len);
+
+            spans
+                .entry(segment.chain_index)
+                .and_modify(|(s, e, n)| {
+                    *s = cmp::min(*s, query_start);
+                    *e = cmp::max(*e, query_end);
+                    *n += len;
+                })
+                .or_insert((query_start, query_end, len));
+        }
+
+        let mut mapped_regions: Vec<_> = spans
+            .into_iter()
+            .filter_map(|(chain_index, (query_start, query_end, mapped_len))| {
+                let chain = &self.chains[chain_index];
+
+                let query_start = Position::new(query_start + 1)?;
+                let query_end = Position::new(query_end)?;
+
+                let end = cmp::min(usize::from(end), chain.target_size);
+                let len = (end + 1).saturating_sub(usize::from(start));
+
+                Some(MappedRegion {
+                    region: Region::new(chain.query_name.clone(), query_start..=query_end),
+                    strand: chain.query_strand,
+                    mapped_len,
+                    len,
+                    score: chain.score,
+                })
+            })
+            .collect();
+
+        mapped_regions.sort_by_key(|mapped_region| cmp::Reverse(mapped_region.score));
+
+        mapped_regions
+    }
+}
+
+impl FromIterator<Record> for Liftover {
+    fn from_iter<T: IntoIterator<Item = Record>>(iter: T) -> Self {
+        let mut chains = Vec::new();
+        let mut builder = IntervalIndex::builder();
+
+        for record in iter {
+            let chain_index = chains.len();
+
+            let target = record.target();
+            let query = record.query();
+
+            let mut target_start = target.start();
+            let mut query_start = query.start();
+
+            for block in record.blocks() {
+                let size = block.size();
+
+                if let (Some(start), Some(end)) = (
+                    Position::new(target_start + 1),
+                    Position::new(target_start + size),
+                ) {
+                    builder.add(
+                        target.name(),
+                        start..=end,
+                        Segment {
+                            chain_index,
+                            query_start,
+                        },
+                    );
+                }
+
+                target_start += size + block.target_gap();
+                query_start += size + block.query_gap();
+            }
+
+            chains.push(Chain {
+                score: record.score(),
+                target_size: target.size(),
+                query_name: query.name().into(),
+                query_size: query.size(),
+                query_strand: query.strand(),
+            });
+        }
+
+        Self {
+            chains,
+            index: builder.build(),
+        }
+    }
+}
+
+/// A position mapped to the query assembly.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MappedPosition {
+    name: String,
+    position: Position,
+    strand: Strand,
+}
+
+impl MappedPosition {
+    /// Returns the query reference sequence name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the position on the forward strand of the query reference sequence.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the strand of the query relative to the target.
+    ///
+    /// If this is the reverse strand, the base at the mapped position is the reverse complement
+    /// of the base in the target.
+    pub fn strand(&self) -> Strand {
+        self.strand
+    }
+}
+
+/// A region mapped to the query assembly.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MappedRegion {
+    region: Region,
+    strand: Strand,
+    mapped_len: usize,
+    len: usize,
+    score: i64,
+}
+
+impl MappedRegion {
+    /// Returns the region on the forward strand of the query reference sequence.
+    ///
+    /// This spans the first to last mapped bases, which can include unmapped gaps.
+    pub fn region(&self) -> &Region {
+        &self.region
+    }
+
+    /// Returns the strand of the query relative to the target.
+    pub fn strand(&self) -> Strand {
+        self.strand
+    }
+
+    /// Returns the number of bases in the source region that are in aligned blocks of the chain.
+    pub fn mapped_len(&self) -> usize {
+        self.mapped_len
+    }
+
+    /// Returns the number of bases in the source region.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the source region has no bases.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns whether only part of the source region is mapped.
+    pub fn is_partial(&self) -> bool {
+        self.mapped_len < self.len
+    }
+
+    /// Returns the score of the chain used to map the region.
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+}
+
+// Converts a 0-based, half-open interval relative to the query strand to the forward strand.
+fn resolve_query_interval(chain: &Chain, start: usize, end: usize) -> (usize, usize) {
+    match chain.query_strand {
+        Strand::Forward => (start, end),
+        Strand::Reverse => (chain.query_size - end, chain.query_size - start),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::{Block, Sequence};
+
+    fn build_liftover() -> Liftover {
+        // sq0:9-13 => sq1:6-10 (+), sq0:16-21 => sq1:11-16 (+)
+        let chain0 = Record::new(
+            21,
+            Sequence::new("sq0", 100, Strand::Forward, 8, 21),
+            Sequence::new("sq1", 50, Strand::Forward, 5, 16),
+            Some(1),
+            vec![Block::new(5, 2, 0), Block::new(6, 0, 0)],
+        );
+
+        // sq0:31-35 => sq2:16-20 (-)
+        let chain1 = Record::new(
+            13,
+            Sequence::new("sq0", 100, Strand::Forward, 30, 35),
+            Sequence::new("sq2", 20, Strand::Reverse, 0, 5),
+            Some(2),
+            vec![Block::new(5, 0, 0)],
+        );
+
+        // sq0:11-12 => sq3:1-2 (+)
+        let chain2 = Record::new(
+            34,
+            Sequence::new("sq0", 100, Strand::Forward, 10, 12),
+            Sequence::new("sq3", 10, Strand::Forward, 0, 2),
+            Some(3),
+            vec![Block::new(2, 0, 0)],
+        );
+
+        [chain0, chain1, chain2].into_iter().collect()
+    }
+
+    #[test]
+    fn test_map_position() -> Result<(), noodles_core::position::TryFromIntError> {
+        let liftover = build_liftover();
+
+        let map = |n| -> Result<Vec<(String, usize, Strand)>, _> {
+            Position::try_from(n).map(|position| {
+                liftover
+                    .map_position("sq0", position)
+                    .into_iter()
+                    .map(|p| (p.name().into(), usize::from(p.position()), p.strand()))
+                    .collect()
+            })
+        };
+
+        assert_eq!(map(9)?, [(String::from("sq1"), 6, Strand::Forward)]);
+        assert_eq!(
+            map(11)?,
+            [
+                (String::from("sq3"), 1, Strand::Forward),
+                (String::from("sq1"), 8, Strand::Forward)
+            ]
+        );
+        assert!(map(14)?.is_empty());
+        assert_eq!(map(16)?, [(String::from("sq1"), 11, Strand::Forward)]);
+        assert_eq!(map(31)?, [(String::from("sq2"), 20, Strand::Reverse)]);
+        assert_eq!(map(35)?, [(String::from("sq2"), 16, Strand::Reverse)]);
+        assert!(map(36)?.is_empty());
+
+        assert!(liftover
+            .map_position("sq1", Position::try_from(9)?)
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_region() -> Result<(), noodles_core::position::TryFromIntError> {
+        let liftover = build_liftover();
+
+        let region = Region::new("sq0", Position::try_from(12)?..=Position::try_from(17)?);
+        let mapped_regions = liftover.map_region(&region);
+        assert_eq!(mapped_regions.len(), 2);
+
+        assert_eq!(mapped_regions[0].region().to_string(), "sq3:2-2");
+        assert_eq!(mapped_regions[0].mapped_len(), 1);
+        assert_eq!(mapped_regions[0].len(), 6);
+
+        assert_eq!(mapped_regions[1].region().to_string(), "sq1:9-12");
+        assert_eq!(mapped_regions[1].mapped_len(), 4);
+        assert!(mapped_regions[1].is_partial());
+
+        let region = Region::new("sq0", Position::try_from(32)?..=Position::try_from(34)?);
+        let mapped_regions = liftover.map_region(&region);
+        assert_eq!(mapped_regions.len(), 1);
+        assert_eq!(mapped_regions[0].region().to_string(), "sq2:17-19");
+        assert_eq!(mapped_regions[0].strand(), Strand::Reverse);
+        assert!(!mapped_regions[0].is_partial());
+
+        let region = Region::new("sq0", Position::try_from(30)?..);
+        let mapped_regions = liftover.map_region(&region);
+        assert_eq!(mapped_regions.len(), 1);
+        assert_eq!(mapped_regions[0].mapped_len(), 5);
+        assert_eq!(mapped_regions[0].len(), 71);
+
+        let region = Region::new("sq0", Position::try_from(40)?..=Position::try_from(50)?);
+        assert!(liftover.map_region(&region).is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,240 @@
+use std::{
+    io::{self, BufRead},
+    iter,
+};
+
+use super::Record;
+
+const COMMENT_PREFIX: char = '#';
+
+/// A chain reader.
+pub struct Reader<R> {
+    inner: R,
+}
+
+impl<R> Reader<R>
+where
+    R: BufRead,
+{
+    /// Creates a chain reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain as chain;
+    /// let data = [];
+    /// let reader = chain::Reader::new(&data[..]);
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain as chain;
+    /// let data = [];
+    /// let reader = chain::Reader::new(&data[..]);
+    /// assert!(reader.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain as chain;
+    /// let data = [];
+    /// let mut reader = chain::Reader::new(&data[..]);
+    /// assert!(reader.get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain as chain;
+    /// let data = [];
+    /// let reader = chain::Reader::new(&data[..]);
+    /// assert!(reader.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads a raw chain record.
+    ///
+    /// This reads the header line and the block lines of a chain. Blank lines and comments
+    /// before the header are skipped. Lines are separated by a line feed (`\n`) in the buffer.
+    ///
+    /// The number of bytes read is returned. At EOF, 0 is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_chain as chain;
+    ///
+    /// let data = b"chain 21 sq0 100 + 8 21 sq1 50 + 5 16 1\n5\t2\t0\n6\n\n";
+    /// let mut reader = chain::Reader::new(&data[..]);
+    ///
+    /// let mut buf = String::new();
+    /// reader.read_record(&mut buf)?;
+    ///
+    /// assert_eq!(buf, "chain 21 sq0 100 + 8 21 sq1 50 + 5 16 1\n5\t2\t0\n6");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_record(&mut self, buf: &mut String) -> io::Result<usize> {
+        read_record(&mut self.inner, buf)
+    }
+
+    /// Returns an iterator over records starting from the current stream position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_chain as chain;
+    ///
+    /// let data = b"chain 21 sq0 100 + 8 21 sq1 50 + 5 16 1\n5\t2\t0\n6\n\n";
+    /// let mut reader = chain::Reader::new(&data[..]);
+    ///
+    /// let mut records = reader.records();
+    ///
+    /// let record = records.next().transpose()?;
+    /// assert_eq!(record.map(|r| r.score()), Some(21));
+    ///
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn records(&mut self) -> impl Iterator<Item = io::Result<Record>> + '_ {
+        let mut buf = String::new();
+
+        iter::from_fn(move || {
+            buf.clear();
+
+            match self.read_record(&mut buf) {
+                Ok(0) => None,
+                Ok(_) => Some(
+                    buf.parse()
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+                ),
+                Err(e) => Some(Err(e)),
+            }
+        })
+    }
+}
+
+fn read_record<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
+where
+    R: BufRead,
+{
+    let mut line = String::new();
+    let mut len = 0;
+
+    // header
+    loop {
+        line.clear();
+
+        match read_line(reader, &mut line)? {
+            0 => return Ok(0),
+            n => len += n,
+        }
+
+        if !line.trim().is_empty() && !line.starts_with(COMMENT_PREFIX) {
+            break;
+        }
+    }
+
+    buf.push_str(&line);
+
+    // blocks
+    loop {
+        line.clear();
+
+        match read_line(reader, &mut line)? {
+            0 => break,
+            n => len += n,
+        }
+
+        if line.trim().is_empty() {
+            break;
+        }
+
+        buf.push('\n');
+        buf.push_str(&line);
+
+        // The last block only has a size.
+        if line.split_whitespace().nth(1).is_none() {
+            break;
+        }
+    }
+
+    Ok(len)
+}
+
+fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
+where
+    R: BufRead,
+{
+    const LINE_FEED: char = '\n';
+    const CARRIAGE_RETURN: char = '\r';
+
+    match reader.read_line(buf) {
+        Ok(0) => Ok(0),
+        Ok(n) => {
+            if buf.ends_with(LINE_FEED) {
+                buf.pop();
+
+                if buf.ends_with(CARRIAGE_RETURN) {
+                    buf.pop();
+                }
+            }
+
+            Ok(n)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_record() -> io::Result<()> {
+        let data = b"\
+# comment
+chain 21 sq0 100 + 8 21 sq1 50 + 5 16 1
+5\t2\t0
+6
+
+chain 13 sq0 100 + 34 39 sq1 50 - 0 5 2
+5
+";
+
+        let mut reader = &data[..];
+        let mut buf = String::new();
+
+        read_record(&mut reader, &mut buf)?;
+        assert_eq!(buf, "chain 21 sq0 100 + 8 21 sq1 50 + 5 16 1\n5\t2\t0\n6");
+
+        buf.clear();
+        read_record(&mut reader, &mut buf)?;
+        assert_eq!(buf, "chain 13 sq0 100 + 34 39 sq1 50 - 0 5 2\n5");
+
+        buf.clear();
+        assert_eq!(read_record(&mut reader, &mut buf)?, 0);
+
+        Ok(())
+    }
+}
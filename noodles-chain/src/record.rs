@@ -0,0 +1,331 @@
+//! Chain record and fields.
+
+pub mod block;
+pub mod sequence;
+pub mod strand;
+
+pub use self::{block::Block, sequence::Sequence, strand::Strand};
+
+use std::{error, fmt, num, str::FromStr};
+
+const PREFIX: &str = "chain";
+const DELIMITER: char = ' ';
+
+/// A chain record.
+///
+/// A chain is a list of ungapped alignment blocks between a target and a query sequence. In a
+/// liftover chain file, the target is the source assembly, and the query is the destination
+/// assembly.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record {
+    score: i64,
+    target: Sequence,
+    query: Sequence,
+    id: Option<u64>,
+    blocks: Vec<Block>,
+}
+
+impl Record {
+    /// Creates a chain record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain::{
+    ///     self as chain,
+    ///     record::{Block, Sequence, Strand},
+    /// };
+    ///
+    /// let record = chain::Record::new(
+    ///     21,
+    ///     Sequence::new("sq0", 100, Strand::Forward, 8, 13),
+    ///     Sequence::new("sq0", 100, Strand::Forward, 34, 39),
+    ///     Some(1),
+    ///     vec![Block::new(5, 0, 0)],
+    /// );
+    /// ```
+    pub fn new(
+        score: i64,
+        target: Sequence,
+        query: Sequence,
+        id: Option<u64>,
+        blocks: Vec<Block>,
+    ) -> Self {
+        Self {
+            score,
+            target,
+            query,
+            id,
+            blocks,
+        }
+    }
+
+    /// Returns the chain score.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain as chain;
+    /// let record: chain::Record = "chain 21 sq0 100 + 8 13 sq0 100 + 34 39 1\n5".parse()?;
+    /// assert_eq!(record.score(), 21);
+    /// # Ok::<_, chain::record::ParseError>(())
+    /// ```
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    /// Returns the target (reference) sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain as chain;
+    /// let record: chain::Record = "chain 21 sq0 100 + 8 13 sq0 100 + 34 39 1\n5".parse()?;
+    /// assert_eq!(record.target().name(), "sq0");
+    /// assert_eq!(record.target().start(), 8);
+    /// # Ok::<_, chain::record::ParseError>(())
+    /// ```
+    pub fn target(&self) -> &Sequence {
+        &self.target
+    }
+
+    /// Returns the query sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain as chain;
+    /// let record: chain::Record = "chain 21 sq0 100 + 8 13 sq0 100 + 34 39 1\n5".parse()?;
+    /// assert_eq!(record.query().start(), 34);
+    /// # Ok::<_, chain::record::ParseError>(())
+    /// ```
+    pub fn query(&self) -> &Sequence {
+        &self.query
+    }
+
+    /// Returns the chain ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain as chain;
+    /// let record: chain::Record = "chain 21 sq0 100 + 8 13 sq0 100 + 34 39 1\n5".parse()?;
+    /// assert_eq!(record.id(), Some(1));
+    /// # Ok::<_, chain::record::ParseError>(())
+    /// ```
+    pub fn id(&self) -> Option<u64> {
+        self.id
+    }
+
+    /// Returns the alignment blocks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain::{self as chain, record::Block};
+    /// let record: chain::Record = "chain 21 sq0 100 + 8 13 sq0 100 + 34 39 1\n5".parse()?;
+    /// assert_eq!(record.blocks(), [Block::new(5, 0, 0)]);
+    /// # Ok::<_, chain::record::ParseError>(())
+    /// ```
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}{}{}{}{}",
+            PREFIX, DELIMITER, self.score, DELIMITER, self.target, DELIMITER, self.query
+        )?;
+
+        if let Some(id) = self.id {
+            write!(f, "{}{}", DELIMITER, id)?;
+        }
+
+        if let Some((last_block, blocks)) = self.blocks.split_last() {
+            for block in blocks {
+                write!(f, "\n{}", block)?;
+            }
+
+            write!(f, "\n{}", last_block.size())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An error returned when a raw chain record fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The header prefix (`chain`) is missing.
+    MissingPrefix,
+    /// The score is missing.
+    MissingScore,
+    /// The score is invalid.
+    InvalidScore(num::ParseIntError),
+    /// The target sequence is invalid.
+    InvalidTarget(sequence::ParseError),
+    /// The query sequence is invalid.
+    InvalidQuery(sequence::ParseError),
+    /// The ID is invalid.
+    InvalidId(num::ParseIntError),
+    /// The header has more than 13 fields.
+    UnexpectedField,
+    /// The blocks are missing.
+    MissingBlocks,
+    /// A block is invalid.
+    InvalidBlock(block::ParseError),
+    /// The blocks do not span the target and query intervals.
+    InvalidBlocks,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::MissingPrefix => write!(f, "missing prefix: expected {}", PREFIX),
+            Self::MissingScore => f.write_str("missing score"),
+            Self::InvalidScore(e) => write!(f, "invalid score: {}", e),
+            Self::InvalidTarget(e) => write!(f, "invalid target: {}", e),
+            Self::InvalidQuery(e) => write!(f, "invalid query: {}", e),
+            Self::InvalidId(e) => write!(f, "invalid ID: {}", e),
+            Self::UnexpectedField => f.write_str("unexpected field"),
+            Self::MissingBlocks => f.write_str("missing blocks"),
+            Self::InvalidBlock(e) => write!(f, "invalid block: {}", e),
+            Self::InvalidBlocks => f.write_str("invalid blocks"),
+        }
+    }
+}
+
+impl FromStr for Record {
+    type Err = ParseError;
+
+    /// Parses a raw chain record.
+    ///
+    /// The input is the header line followed by a line for each block.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+
+        let header = lines.next().ok_or(ParseError::Empty)?;
+        let mut fields = header.split_whitespace();
+
+        match fields.next() {
+            Some(PREFIX) => {}
+            Some(_) => return Err(ParseError::MissingPrefix),
+            None => return Err(ParseError::Empty),
+        }
+
+        let score = fields
+            .next()
+            .ok_or(ParseError::MissingScore)
+            .and_then(|t| t.parse().map_err(ParseError::InvalidScore))?;
+
+        let target = sequence::parse_fields(&mut fields).map_err(ParseError::InvalidTarget)?;
+        let query = sequence::parse_fields(&mut fields).map_err(ParseError::InvalidQuery)?;
+
+        let id = fields
+            .next()
+            .map(|t| t.parse().map_err(ParseError::InvalidId))
+            .transpose()?;
+
+        if fields.next().is_some() {
+            return Err(ParseError::UnexpectedField);
+        }
+
+        let blocks: Vec<Block> = lines
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.parse().map_err(ParseError::InvalidBlock))
+            .collect::<Result<_, _>>()?;
+
+        if blocks.is_empty() {
+            return Err(ParseError::MissingBlocks);
+        }
+
+        let (target_len, query_len) = blocks.iter().fold((0, 0), |(t, q), block| {
+            (
+                t + block.size() + block.target_gap(),
+                q + block.size() + block.query_gap(),
+            )
+        });
+
+        if target_len != target.end() - target.start() || query_len != query.end() - query.start() {
+            return Err(ParseError::InvalidBlocks);
+        }
+
+        Ok(Self::new(score, target, query, id, blocks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_record() -> Record {
+        Record::new(
+            21,
+            Sequence::new("sq0", 100, Strand::Forward, 8, 21),
+            Sequence::new("sq1", 50, Strand::Reverse, 5, 20),
+            Some(1),
+            vec![Block::new(5, 0, 2), Block::new(8, 0, 0)],
+        )
+    }
+
+    #[test]
+    fn test_fmt() {
+        let record = build_record();
+        let expected = "chain 21 sq0 100 + 8 21 sq1 50 - 5 20 1\n5\t0\t2\n8";
+        assert_eq!(record.to_string(), expected);
+
+        let record = Record::new(
+            21,
+            Sequence::new("sq0", 100, Strand::Forward, 8, 13),
+            Sequence::new("sq1", 50, Strand::Forward, 5, 10),
+            None,
+            vec![Block::new(5, 0, 0)],
+        );
+        assert_eq!(
+            record.to_string(),
+            "chain 21 sq0 100 + 8 13 sq1 50 + 5 10\n5"
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "chain 21 sq0 100 + 8 21 sq1 50 - 5 20 1\n5\t0\t2\n8\n".parse(),
+            Ok(build_record())
+        );
+
+        assert_eq!("".parse::<Record>(), Err(ParseError::Empty));
+        assert_eq!(
+            "ndls 21 sq0 100 + 8 21 sq1 50 - 5 20 1\n13".parse::<Record>(),
+            Err(ParseError::MissingPrefix)
+        );
+        assert!(matches!(
+            "chain n sq0 100 + 8 21 sq1 50 - 5 20 1\n13".parse::<Record>(),
+            Err(ParseError::InvalidScore(_))
+        ));
+        assert!(matches!(
+            "chain 21 sq0 100 + 8 21 sq1 50 - 5\n13".parse::<Record>(),
+            Err(ParseError::InvalidQuery(_))
+        ));
+        assert_eq!(
+            "chain 21 sq0 100 + 8 21 sq1 50 - 5 20 1 2\n13".parse::<Record>(),
+            Err(ParseError::UnexpectedField)
+        );
+        assert_eq!(
+            "chain 21 sq0 100 + 8 21 sq1 50 - 5 20 1".parse::<Record>(),
+            Err(ParseError::MissingBlocks)
+        );
+        assert_eq!(
+            "chain 21 sq0 100 + 8 21 sq1 50 - 5 20 1\n5\t0\t2\n5".parse::<Record>(),
+            Err(ParseError::InvalidBlocks)
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! Chain record alignment block.
+
+use std::{error, fmt, num, str::FromStr};
+
+const DELIMITER: char = '\t';
+
+/// A chain record alignment block.
+///
+/// A block is an ungapped alignment of `size` bases, followed by the gaps to the next block in
+/// the target (`dt`) and query (`dq`) sequences. The gaps of the last block in a chain are 0.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Block {
+    size: usize,
+    target_gap: usize,
+    query_gap: usize,
+}
+
+impl Block {
+    /// Creates a chain record alignment block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain::record::Block;
+    /// let block = Block::new(8, 2, 0);
+    /// ```
+    pub fn new(size: usize, target_gap: usize, query_gap: usize) -> Self {
+        Self {
+            size,
+            target_gap,
+            query_gap,
+        }
+    }
+
+    /// Returns the size of the ungapped alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain::record::Block;
+    /// let block = Block::new(8, 2, 0);
+    /// assert_eq!(block.size(), 8);
+    /// ```
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the gap to the next block in the target sequence (`dt`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain::record::Block;
+    /// let block = Block::new(8, 2, 0);
+    /// assert_eq!(block.target_gap(), 2);
+    /// ```
+    pub fn target_gap(&self) -> usize {
+        self.target_gap
+    }
+
+    /// Returns the gap to the next block in the query sequence (`dq`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain::record::Block;
+    /// let block = Block::new(8, 2, 0);
+    /// assert_eq!(block.query_gap(), 0);
+    /// ```
+    pub fn query_gap(&self) -> usize {
+        self.query_gap
+    }
+}
+
+impl fmt::Display for Block {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}{}{}",
+            self.size, DELIMITER, self.target_gap, DELIMITER, self.query_gap
+        )
+    }
+}
+
+/// An error returned when a raw chain record alignment block fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The size is invalid.
+    InvalidSize(num::ParseIntError),
+    /// The target gap is invalid.
+    InvalidTargetGap(num::ParseIntError),
+    /// The query gap is missing.
+    MissingQueryGap,
+    /// The query gap is invalid.
+    InvalidQueryGap(num::ParseIntError),
+    /// The input has more than 3 fields.
+    UnexpectedField,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::InvalidSize(e) => write!(f, "invalid size: {}", e),
+            Self::InvalidTargetGap(e) => write!(f, "invalid target gap: {}", e),
+            Self::MissingQueryGap => f.write_str("missing query gap"),
+            Self::InvalidQueryGap(e) => write!(f, "invalid query gap: {}", e),
+            Self::UnexpectedField => f.write_str("unexpected field"),
+        }
+    }
+}
+
+impl FromStr for Block {
+    type Err = ParseError;
+
+    /// Parses a raw chain record alignment block.
+    ///
+    /// The gaps are optional and default to 0, i.e., the last block of a chain only has a size.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split_whitespace();
+
+        let size = fields
+            .next()
+            .ok_or(ParseError::Empty)
+            .and_then(|t| t.parse().map_err(ParseError::InvalidSize))?;
+
+        let (target_gap, query_gap) = match fields.next() {
+            Some(t) => {
+                let target_gap = t.parse().map_err(ParseError::InvalidTargetGap)?;
+
+                let query_gap = fields
+                    .next()
+                    .ok_or(ParseError::MissingQueryGap)
+                    .and_then(|t| t.parse().map_err(ParseError::InvalidQueryGap))?;
+
+                (target_gap, query_gap)
+            }
+            None => (0, 0),
+        };
+
+        if fields.next().is_some() {
+            return Err(ParseError::UnexpectedField);
+        }
+
+        Ok(Self::new(size, target_gap, query_gap))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(Block::new(8, 2, 0).to_string(), "8\t2\t0");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("8\t2\t0".parse(), Ok(Block::new(8, 2, 0)));
+        assert_eq!("8 2 0".parse(), Ok(Block::new(8, 2, 0)));
+        assert_eq!("8".parse(), Ok(Block::new(8, 0, 0)));
+
+        assert_eq!("".parse::<Block>(), Err(ParseError::Empty));
+        assert!(matches!(
+            "n".parse::<Block>(),
+            Err(ParseError::InvalidSize(_))
+        ));
+        assert!(matches!(
+            "8\tn\t0".parse::<Block>(),
+            Err(ParseError::InvalidTargetGap(_))
+        ));
+        assert_eq!("8\t2".parse::<Block>(), Err(ParseError::MissingQueryGap));
+        assert!(matches!(
+            "8\t2\tn".parse::<Block>(),
+            Err(ParseError::InvalidQueryGap(_))
+        ));
+        assert_eq!(
+            "8\t2\t0\t1".parse::<Block>(),
+            Err(ParseError::UnexpectedField)
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! Chain record sequence.
+
+use std::{error, fmt, num};
+
+use super::{strand, Strand};
+
+const DELIMITER: char = ' ';
+
+/// A chain record sequence.
+///
+/// This is the target or query sequence of a chain, i.e., the `{t,q}Name`, `{t,q}Size`,
+/// `{t,q}Strand`, `{t,q}Start`, and `{t,q}End` header fields.
+///
+/// The start and end positions are 0-based, half-open and relative to the strand. For the
+/// reverse strand, they are positions in the reverse complement of the sequence.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sequence {
+    name: String,
+    size: usize,
+    strand: Strand,
+    start: usize,
+    end: usize,
+}
+
+impl Sequence {
+    /// Creates a chain record sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain::record::{Sequence, Strand};
+    /// let sequence = Sequence::new("sq0", 100, Strand::Forward, 8, 13);
+    /// ```
+    pub fn new<N>(name: N, size: usize, strand: Strand, start: usize, end: usize) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            size,
+            strand,
+            start,
+            end,
+        }
+    }
+
+    /// Returns the sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain::record::{Sequence, Strand};
+    /// let sequence = Sequence::new("sq0", 100, Strand::Forward, 8, 13);
+    /// assert_eq!(sequence.name(), "sq0");
+    /// ```
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the sequence size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain::record::{Sequence, Strand};
+    /// let sequence = Sequence::new("sq0", 100, Strand::Forward, 8, 13);
+    /// assert_eq!(sequence.size(), 100);
+    /// ```
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the strand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain::record::{Sequence, Strand};
+    /// let sequence = Sequence::new("sq0", 100, Strand::Forward, 8, 13);
+    /// assert_eq!(sequence.strand(), Strand::Forward);
+    /// ```
+    pub fn strand(&self) -> Strand {
+        self.strand
+    }
+
+    /// Returns the start position of the alignment (0-based, inclusive).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain::record::{Sequence, Strand};
+    /// let sequence = Sequence::new("sq0", 100, Strand::Forward, 8, 13);
+    /// assert_eq!(sequence.start(), 8);
+    /// ```
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the end position of the alignment (0-based, exclusive).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain::record::{Sequence, Strand};
+    /// let sequence = Sequence::new("sq0", 100, Strand::Forward, 8, 13);
+    /// assert_eq!(sequence.end(), 13);
+    /// ```
+    pub fn end(&self) -> usize {
+        self.end
+    }
+}
+
+impl fmt::Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}{}{}{}{}{}{}",
+            self.name,
+            DELIMITER,
+            self.size,
+            DELIMITER,
+            self.strand,
+            DELIMITER,
+            self.start,
+            DELIMITER,
+            self.end
+        )
+    }
+}
+
+/// An error returned when a raw chain record sequence fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The name is missing.
+    MissingName,
+    /// The size is missing.
+    MissingSize,
+    /// The size is invalid.
+    InvalidSize(num::ParseIntError),
+    /// The strand is missing.
+    MissingStrand,
+    /// The strand is invalid.
+    InvalidStrand(strand::ParseError),
+    /// The start position is missing.
+    MissingStart,
+    /// The start position is invalid.
+    InvalidStart(num::ParseIntError),
+    /// The end position is missing.
+    MissingEnd,
+    /// The end position is invalid.
+    InvalidEnd(num::ParseIntError),
+    /// The start and end positions are not in the sequence.
+    InvalidInterval,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingName => f.write_str("missing name"),
+            Self::MissingSize => f.write_str("missing size"),
+            Self::InvalidSize(e) => write!(f, "invalid size: {}", e),
+            Self::MissingStrand => f.write_str("missing strand"),
+            Self::InvalidStrand(e) => write!(f, "invalid strand: {}", e),
+            Self::MissingStart => f.write_str("missing start position"),
+            Self::InvalidStart(e) => write!(f, "invalid start position: {}", e),
+            Self::MissingEnd => f.write_str("missing end position"),
+            Self::InvalidEnd(e) => write!(f, "invalid end position: {}", e),
+            Self::InvalidInterval => f.write_str("invalid interval"),
+        }
+    }
+}
+
+pub(super) fn parse_fields<'a, I>(fields: &mut I) -> Result<Sequence, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let name = fields.next().ok_or(ParseError::MissingName)?;
+
+    let size = fields
+        .next()
+        .ok_or(ParseError::MissingSize)
+        .and_then(|s| s.parse().map_err(ParseError::InvalidSize))?;
+
+    let strand = fields
+        .next()
+        .ok_or(ParseError::MissingStrand)
+        .and_then(|s| s.parse().map_err(ParseError::InvalidStrand))?;
+
+    let start = fields
+        .next()
+        .ok_or(ParseError::MissingStart)
+        .and_then(|s| s.parse().map_err(ParseError::InvalidStart))?;
+
+    let end = fields
+        .next()
+        .ok_or(ParseError::MissingEnd)
+        .and_then(|s| s.parse().map_err(ParseError::InvalidEnd))?;
+
+    if start > end || end > size {
+        return Err(ParseError::InvalidInterval);
+    }
+
+    Ok(Sequence::new(name, size, strand, start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        let sequence = Sequence::new("sq0", 100, Strand::Reverse, 8, 13);
+        assert_eq!(sequence.to_string(), "sq0 100 - 8 13");
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        fn t(s: &str) -> Result<Sequence, ParseError> {
+            parse_fields(&mut s.split(' '))
+        }
+
+        assert_eq!(
+            t("sq0 100 - 8 13"),
+            Ok(Sequence::new("sq0", 100, Strand::Reverse, 8, 13))
+        );
+
+        assert_eq!(t("sq0"), Err(ParseError::MissingSize));
+        assert!(matches!(t("sq0 n"), Err(ParseError::InvalidSize(_))));
+        assert!(matches!(
+            t("sq0 100 . 8 13"),
+            Err(ParseError::InvalidStrand(_))
+        ));
+        assert_eq!(t("sq0 100 + 8"), Err(ParseError::MissingEnd));
+        assert_eq!(t("sq0 100 + 13 8"), Err(ParseError::InvalidInterval));
+        assert_eq!(t("sq0 100 + 8 101"), Err(ParseError::InvalidInterval));
+    }
+}
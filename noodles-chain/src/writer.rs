@@ -0,0 +1,96 @@
+use std::io::{self, Write};
+
+use super::Record;
+
+/// A chain writer.
+pub struct Writer<W> {
+    inner: W,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a chain writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain as chain;
+    /// let writer = chain::Writer::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain as chain;
+    /// let writer = chain::Writer::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain as chain;
+    /// let mut writer = chain::Writer::new(Vec::new());
+    /// assert!(writer.get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_chain as chain;
+    /// let writer = chain::Writer::new(Vec::new());
+    /// assert!(writer.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes a chain record.
+    ///
+    /// A blank line is written after the record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_chain::{
+    ///     self as chain,
+    ///     record::{Block, Sequence, Strand},
+    /// };
+    ///
+    /// let mut writer = chain::Writer::new(Vec::new());
+    ///
+    /// let record = chain::Record::new(
+    ///     21,
+    ///     Sequence::new("sq0", 100, Strand::Forward, 8, 13),
+    ///     Sequence::new("sq1", 50, Strand::Forward, 5, 10),
+    ///     Some(1),
+    ///     vec![Block::new(5, 0, 0)],
+    /// );
+    ///
+    /// writer.write_record(&record)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"chain 21 sq0 100 + 8 13 sq1 50 + 5 10 1\n5\n\n");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        writeln!(self.inner, "{}\n", record)
+    }
+}
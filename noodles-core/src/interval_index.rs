@@ -0,0 +1,569 @@
+//! In-memory interval index.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{cmp, collections::HashMap};
+
+use super::{region::Interval, Position, Region};
+
+// Subtrees at or below this level are scanned linearly.
+const LINEAR_SCAN_MAX_LEVEL: usize = 3;
+
+/// An in-memory interval index.
+///
+/// This maps reference sequence names and intervals to values, e.g., features loaded from a
+/// GFF/GTF or BED file, and supports overlap, containment, and nearest neighbor queries.
+///
+/// Each reference sequence is indexed as an implicit augmented interval tree over an array of
+/// intervals sorted by start position, similar to [cgranges]. Building is O(n log n), and an
+/// overlap query is O(log n + k), where k is the number of results. Query results are sorted by
+/// start position.
+///
+/// [cgranges]: https://github.com/lh3/cgranges
+#[derive(Debug)]
+pub struct IntervalIndex<T> {
+    trees: HashMap<String, Tree<T>>,
+}
+
+impl<T> IntervalIndex<T> {
+    /// Returns a builder to create an interval index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{IntervalIndex, Position};
+    ///
+    /// let mut builder = IntervalIndex::builder();
+    /// builder.add("sq0", Position::try_from(8)?..=Position::try_from(13)?, "f0");
+    /// let index = builder.build();
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn builder() -> Builder<T> {
+        Builder::default()
+    }
+
+    /// Returns the number of values in the index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{IntervalIndex, Position};
+    ///
+    /// let mut builder = IntervalIndex::builder();
+    /// builder.add("sq0", Position::try_from(8)?..=Position::try_from(13)?, "f0");
+    /// builder.add("sq1", Position::try_from(21)?..=Position::try_from(34)?, "f1");
+    /// let index = builder.build();
+    ///
+    /// assert_eq!(index.len(), 2);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn len(&self) -> usize {
+        self.trees.values().map(|tree| tree.entries.len()).sum()
+    }
+
+    /// Returns whether the index has no values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalIndex;
+    /// let index: IntervalIndex<()> = IntervalIndex::builder().build();
+    /// assert!(index.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the values with intervals that intersect the given interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{IntervalIndex, Position};
+    ///
+    /// let mut builder = IntervalIndex::builder();
+    /// builder.add("sq0", Position::try_from(8)?..=Position::try_from(13)?, "f0");
+    /// builder.add("sq0", Position::try_from(21)?..=Position::try_from(34)?, "f1");
+    /// let index = builder.build();
+    ///
+    /// let values: Vec<_> = index
+    ///     .overlapping("sq0", Position::try_from(13)?..=Position::try_from(21)?)
+    ///     .map(|(_, value)| *value)
+    ///     .collect();
+    ///
+    /// assert_eq!(values, ["f0", "f1"]);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn overlapping<I>(&self, name: &str, interval: I) -> impl Iterator<Item = (Interval, &T)>
+    where
+        I: Into<Interval>,
+    {
+        let (start, end) = resolve_interval(interval.into());
+        self.query(name, move |tree| tree.overlapping(start, end))
+    }
+
+    /// Returns the values with intervals that are contained in the given interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{IntervalIndex, Position};
+    ///
+    /// let mut builder = IntervalIndex::builder();
+    /// builder.add("sq0", Position::try_from(8)?..=Position::try_from(13)?, "f0");
+    /// builder.add("sq0", Position::try_from(21)?..=Position::try_from(34)?, "f1");
+    /// let index = builder.build();
+    ///
+    /// let values: Vec<_> = index
+    ///     .within("sq0", Position::try_from(5)?..=Position::try_from(21)?)
+    ///     .map(|(_, value)| *value)
+    ///     .collect();
+    ///
+    /// assert_eq!(values, ["f0"]);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn within<I>(&self, name: &str, interval: I) -> impl Iterator<Item = (Interval, &T)>
+    where
+        I: Into<Interval>,
+    {
+        let (start, end) = resolve_interval(interval.into());
+
+        self.query(name, move |tree| {
+            let mut indices = tree.overlapping(start, end);
+
+            indices.retain(|&i| {
+                let (s, e, _) = &tree.entries[i];
+                start <= *s && *e <= end
+            });
+
+            indices
+        })
+    }
+
+    /// Returns the values with intervals that contain the given interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{IntervalIndex, Position};
+    ///
+    /// let mut builder = IntervalIndex::builder();
+    /// builder.add("sq0", Position::try_from(8)?..=Position::try_from(13)?, "f0");
+    /// builder.add("sq0", Position::try_from(1)?..=Position::try_from(34)?, "f1");
+    /// let index = builder.build();
+    ///
+    /// let values: Vec<_> = index
+    ///     .containing("sq0", Position::try_from(13)?..=Position::try_from(21)?)
+    ///     .map(|(_, value)| *value)
+    ///     .collect();
+    ///
+    /// assert_eq!(values, ["f1"]);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn containing<I>(&self, name: &str, interval: I) -> impl Iterator<Item = (Interval, &T)>
+    where
+        I: Into<Interval>,
+    {
+        let (start, end) = resolve_interval(interval.into());
+
+        self.query(name, move |tree| {
+            let mut indices = tree.overlapping(start, end);
+
+            indices.retain(|&i| {
+                let (s, e, _) = &tree.entries[i];
+                *s <= start && end <= *e
+            });
+
+            indices
+        })
+    }
+
+    /// Returns the values with intervals nearest to the given interval.
+    ///
+    /// If any intervals intersect the given interval, these are returned. Otherwise, the values
+    /// with the closest preceding end and/or following start are returned, including all ties.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{IntervalIndex, Position};
+    ///
+    /// let mut builder = IntervalIndex::builder();
+    /// builder.add("sq0", Position::try_from(8)?..=Position::try_from(13)?, "f0");
+    /// builder.add("sq0", Position::try_from(55)?..=Position::try_from(89)?, "f1");
+    /// let index = builder.build();
+    ///
+    /// let values: Vec<_> = index
+    ///     .nearest("sq0", Position::try_from(21)?..=Position::try_from(34)?)
+    ///     .map(|(_, value)| *value)
+    ///     .collect();
+    ///
+    /// assert_eq!(values, ["f0"]);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn nearest<I>(&self, name: &str, interval: I) -> impl Iterator<Item = (Interval, &T)>
+    where
+        I: Into<Interval>,
+    {
+        let (start, end) = resolve_interval(interval.into());
+        self.query(name, move |tree| tree.nearest(start, end))
+    }
+
+    fn query<F>(&self, name: &str, f: F) -> impl Iterator<Item = (Interval, &T)>
+    where
+        F: FnOnce(&Tree<T>) -> Vec<usize>,
+    {
+        let tree = self.trees.get(name);
+
+        let mut indices = tree.map(f).unwrap_or_default();
+        indices.sort_unstable();
+
+        indices
+            .into_iter()
+            .filter_map(move |i| tree.map(|t| t.get(i)))
+    }
+}
+
+impl<T> FromIterator<(Region, T)> for IntervalIndex<T> {
+    fn from_iter<I: IntoIterator<Item = (Region, T)>>(iter: I) -> Self {
+        let mut builder = Self::builder();
+
+        for (region, value) in iter {
+            builder.add(region.name(), region.interval(), value);
+        }
+
+        builder.build()
+    }
+}
+
+// An implicit augmented interval tree.
+//
+// The entries are sorted by start position and form the in-order traversal of a complete binary
+// tree, where a node at index i is at level k if the lowest k bits of i are set and bit k is
+// not. `max_ends[i]` is the max end position of the subtree rooted at node i.
+#[derive(Debug)]
+struct Tree<T> {
+    entries: Vec<(Position, Position, T)>,
+    max_ends: Vec<Position>,
+    prefix_max_ends: Vec<Position>,
+    max_level: usize,
+}
+
+impl<T> Tree<T> {
+    fn new(mut entries: Vec<(Position, Position, T)>) -> Self {
+        entries.sort_by_key(|(start, end, _)| (*start, *end));
+
+        let (max_ends, max_level) = build_max_ends(&entries);
+
+        let prefix_max_ends = entries
+            .iter()
+            .scan(Position::MIN, |max_end, (_, end, _)| {
+                *max_end = cmp::max(*max_end, *end);
+                Some(*max_end)
+            })
+            .collect();
+
+        Self {
+            entries,
+            max_ends,
+            prefix_max_ends,
+            max_level,
+        }
+    }
+
+    fn get(&self, i: usize) -> (Interval, &T) {
+        let (start, end, value) = &self.entries[i];
+        (Interval::from(*start..=*end), value)
+    }
+
+    fn overlapping(&self, start: Position, end: Position) -> Vec<usize> {
+        let n = self.entries.len();
+        let mut indices = Vec::new();
+
+        if n == 0 {
+            return indices;
+        }
+
+        // (level, node index, whether the left subtree was visited)
+        let mut stack = vec![(self.max_level, (1 << self.max_level) - 1, false)];
+
+        while let Some((k, x, is_left_visited)) = stack.pop() {
+            if k <= LINEAR_SCAN_MAX_LEVEL {
+                let i0 = x >> k << k;
+                let i1 = cmp::min(i0 + (1 << (k + 1)) - 1, n);
+
+                for i in i0..i1 {
+                    let (s, e, _) = &self.entries[i];
+
+                    if *s > end {
+                        break;
+                    }
+
+                    if start <= *e {
+                        indices.push(i);
+                    }
+                }
+            } else if !is_left_visited {
+                let y = x - (1 << (k - 1));
+
+                stack.push((k, x, true));
+
+                if y >= n || self.max_ends[y] >= start {
+                    stack.push((k - 1, y, false));
+                }
+            } else if x < n && self.entries[x].0 <= end {
+                if start <= self.entries[x].1 {
+                    indices.push(x);
+                }
+
+                stack.push((k - 1, x + (1 << (k - 1)), false));
+            }
+        }
+
+        indices
+    }
+
+    fn nearest(&self, start: Position, end: Position) -> Vec<usize> {
+        let indices = self.overlapping(start, end);
+
+        if !indices.is_empty() {
+            return indices;
+        }
+
+        // Since no intervals intersect [start, end], all intervals that start before `start` also
+        // end before it.
+        let i = self.entries.partition_point(|(s, _, _)| *s < start);
+        let preceding_end = i.checked_sub(1).map(|j| self.prefix_max_ends[j]);
+
+        let j = self.entries.partition_point(|(s, _, _)| *s <= end);
+        let following_start = self.entries.get(j).map(|(s, _, _)| *s);
+
+        let preceding_distance = preceding_end.map(|e| usize::from(start) - usize::from(e));
+        let following_distance = following_start.map(|s| usize::from(s) - usize::from(end));
+
+        let mut indices = Vec::new();
+
+        let min_distance = match (preceding_distance, following_distance) {
+            (Some(a), Some(b)) => cmp::min(a, b),
+            (Some(a), None) => a,
+            (None, Some(b)) => b,
+            (None, None) => return indices,
+        };
+
+        if let Some(e) = preceding_end {
+            if preceding_distance == Some(min_distance) {
+                indices.extend(self.overlapping(e, e));
+            }
+        }
+
+        if let Some(s) = following_start {
+            if following_distance == Some(min_distance) {
+                indices.extend((j..self.entries.len()).take_while(|&k| self.entries[k].0 == s));
+            }
+        }
+
+        indices
+    }
+}
+
+fn build_max_ends<T>(entries: &[(Position, Position, T)]) -> (Vec<Position>, usize) {
+    let n = entries.len();
+    let mut max_ends: Vec<_> = entries.iter().map(|(_, end, _)| *end).collect();
+
+    if n == 0 {
+        return (max_ends, 0);
+    }
+
+    // The last leaf and the max end of the subtree that contains it but is not completely in the
+    // array.
+    let mut last_i = 0;
+    let mut last = max_ends[0];
+
+    for i in (0..n).step_by(2) {
+        last_i = i;
+        last = max_ends[i];
+    }
+
+    let mut k = 1;
+
+    while 1 << k <= n {
+        let x = 1 << (k - 1);
+        let step = x << 2;
+
+        for i in ((x << 1) - 1..n).step_by(step) {
+            let left_max_end = max_ends[i - x];
+            let right_max_end = if i + x < n { max_ends[i + x] } else { last };
+
+            max_ends[i] = cmp::max(entries[i].1, cmp::max(left_max_end, right_max_end));
+        }
+
+        last_i = if (last_i >> k) & 1 == 1 {
+            last_i - x
+        } else {
+            last_i + x
+        };
+
+        if last_i < n {
+            last = cmp::max(last, max_ends[last_i]);
+        }
+
+        k += 1;
+    }
+
+    (max_ends, k - 1)
+}
+
+fn resolve_interval(interval: Interval) -> (Position, Position) {
+    (
+        interval.start().unwrap_or(Position::MIN),
+        interval.end().unwrap_or(Position::MAX),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(n: usize) -> Position {
+        Position::new(n).unwrap()
+    }
+
+    // A linear congruential generator for reproducible test data.
+    fn build_entries(seed: u64, n: usize) -> Vec<(usize, usize)> {
+        let mut state = seed;
+
+        let mut next = |m: u64| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) % m
+        };
+
+        (0..n)
+            .map(|_| {
+                let start = next(100_000) as usize + 1;
+                let len = if next(10) == 0 {
+                    next(10_000)
+                } else {
+                    next(100)
+                };
+                (start, start + len as usize)
+            })
+            .collect()
+    }
+
+    fn build_index(entries: &[(usize, usize)]) -> IntervalIndex<usize> {
+        let mut builder = IntervalIndex::builder();
+
+        for (i, &(start, end)) in entries.iter().enumerate() {
+            builder.add("sq0", position(start)..=position(end), i);
+        }
+
+        builder.build()
+    }
+
+    fn values<'a, I>(iter: I) -> Vec<usize>
+    where
+        I: Iterator<Item = (Interval, &'a usize)>,
+    {
+        let mut values: Vec<_> = iter.map(|(_, value)| *value).collect();
+        values.sort_unstable();
+        values
+    }
+
+    #[test]
+    fn test_overlapping() {
+        for n in [0, 1, 2, 3, 7, 8, 9, 15, 16, 17, 100, 1000] {
+            let entries = build_entries(8, n);
+            let index = build_index(&entries);
+
+            for &(start, end) in &build_entries(13, 200) {
+                let actual = values(index.overlapping("sq0", position(start)..=position(end)));
+
+                let expected: Vec<_> = entries
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &(s, e))| s <= end && start <= e)
+                    .map(|(i, _)| i)
+                    .collect();
+
+                assert_eq!(actual, expected, "n = {}, query = [{}, {}]", n, start, end);
+            }
+        }
+    }
+
+    #[test]
+    fn test_overlapping_sorts_results() {
+        let index = build_index(&[(21, 34), (8, 13), (1, 55)]);
+
+        let intervals: Vec<_> = index
+            .overlapping("sq0", position(13)..=position(21))
+            .map(|(interval, _)| interval)
+            .collect();
+
+        assert_eq!(
+            intervals,
+            [
+                Interval::from(position(1)..=position(55)),
+                Interval::from(position(8)..=position(13)),
+                Interval::from(position(21)..=position(34)),
+            ]
+        );
+
+        assert_eq!(index.overlapping("sq1", ..).count(), 0);
+    }
+
+    #[test]
+    fn test_within_and_containing() {
+        let index = build_index(&[(1, 55), (8, 13), (13, 21), (21, 34)]);
+
+        assert_eq!(
+            values(index.within("sq0", position(8)..=position(21))),
+            [1, 2]
+        );
+        assert_eq!(
+            values(index.containing("sq0", position(13)..=position(13))),
+            [0, 1, 2]
+        );
+        assert_eq!(
+            values(index.containing("sq0", position(13)..=position(34))),
+            [0]
+        );
+    }
+
+    #[test]
+    fn test_nearest() {
+        let entries = build_entries(8, 1000);
+        let index = build_index(&entries);
+
+        for &(start, end) in &build_entries(13, 200) {
+            let actual = values(index.nearest("sq0", position(start)..=position(end)));
+
+            let distance =
+                |&(s, e): &(usize, usize)| cmp::max(start.saturating_sub(e), s.saturating_sub(end));
+
+            let min_distance = entries.iter().map(distance).min().unwrap();
+
+            let expected: Vec<_> = entries
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| distance(entry) == min_distance)
+                .map(|(i, _)| i)
+                .collect();
+
+            assert_eq!(actual, expected, "query = [{}, {}]", start, end);
+        }
+
+        let index = build_index(&[(1, 5), (8, 13), (21, 34)]);
+        assert_eq!(
+            values(index.nearest("sq0", position(17)..=position(17))),
+            [1, 2]
+        );
+        assert_eq!(
+            values(index.nearest("sq0", position(55)..=position(89))),
+            [2]
+        );
+    }
+}
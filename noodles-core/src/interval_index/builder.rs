@@ -0,0 +1,73 @@
+//! Interval index builder.
+
+use std::collections::HashMap;
+
+use super::{IntervalIndex, Tree};
+use crate::{region::Interval, Position};
+
+/// An interval index builder.
+#[derive(Debug)]
+pub struct Builder<T> {
+    entries: HashMap<String, Vec<(Position, Position, T)>>,
+}
+
+impl<T> Builder<T> {
+    /// Adds a value with the given reference sequence name and interval.
+    ///
+    /// An unbounded start or end is resolved to [`Position::MIN`] or [`Position::MAX`],
+    /// respectively. A value with an interval where the start is greater than the end is
+    /// ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{IntervalIndex, Position};
+    ///
+    /// let mut builder = IntervalIndex::builder();
+    /// builder.add("sq0", Position::try_from(8)?..=Position::try_from(13)?, "f0");
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn add<N, I>(&mut self, name: N, interval: I, value: T)
+    where
+        N: Into<String>,
+        I: Into<Interval>,
+    {
+        let interval = interval.into();
+
+        let start = interval.start().unwrap_or(Position::MIN);
+        let end = interval.end().unwrap_or(Position::MAX);
+
+        if start <= end {
+            self.entries
+                .entry(name.into())
+                .or_default()
+                .push((start, end, value));
+        }
+    }
+
+    /// Builds an interval index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::IntervalIndex;
+    /// let index: IntervalIndex<()> = IntervalIndex::builder().build();
+    /// ```
+    pub fn build(self) -> IntervalIndex<T> {
+        let trees = self
+            .entries
+            .into_iter()
+            .map(|(name, entries)| (name, Tree::new(entries)))
+            .collect();
+
+        IntervalIndex { trees }
+    }
+}
+
+impl<T> Default for Builder<T> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+}
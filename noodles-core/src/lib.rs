@@ -2,7 +2,13 @@
 
 //! **noodles-core** contains shared structures and behavior among noodles libraries.
 
+pub mod interval_index;
 pub mod position;
 pub mod region;
+pub mod region_set;
+pub mod sequence_dictionary;
 
-pub use self::{position::Position, region::Region};
+pub use self::{
+    interval_index::IntervalIndex, position::Position, region::Region, region_set::RegionSet,
+    sequence_dictionary::SequenceDictionary,
+};
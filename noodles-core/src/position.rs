@@ -1,8 +1,9 @@
 //! 1-based position.
 
 mod sequence_index;
+pub mod zero_based;
 
-pub use self::sequence_index::SequenceIndex;
+pub use self::{sequence_index::SequenceIndex, zero_based::ZeroBasedPosition};
 
 use std::{
     fmt,
@@ -0,0 +1,151 @@
+//! 0-based position.
+
+use std::{error, fmt};
+
+use super::Position;
+
+/// A 0-based position.
+///
+/// This is the offset of a base from the start of a sequence, as used in formats such as BED.
+///
+/// A 0-based position converts to and from a 1-based [`Position`] without loss. The maximum
+/// value is one less than [`Position::MAX`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ZeroBasedPosition(usize);
+
+impl ZeroBasedPosition {
+    /// The minimum value of a 0-based position.
+    pub const MIN: Self = Self(0);
+
+    /// The maximum value of a 0-based position.
+    pub const MAX: Self = Self(usize::MAX - 1);
+
+    /// Creates a 0-based position if the given value is not greater than the maximum value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::position::ZeroBasedPosition;
+    /// assert!(ZeroBasedPosition::new(0).is_some());
+    /// assert!(ZeroBasedPosition::new(usize::MAX).is_none());
+    /// ```
+    pub const fn new(n: usize) -> Option<Self> {
+        if n < usize::MAX {
+            Some(Self(n))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the inner value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::position::ZeroBasedPosition;
+    /// assert_eq!(ZeroBasedPosition::MIN.get(), 0);
+    /// ```
+    pub const fn get(&self) -> usize {
+        self.0
+    }
+
+    /// Adds an unsigned integer to a 0-based position.
+    ///
+    /// This returns `None` if the operation overflowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::position::ZeroBasedPosition;
+    /// let position = ZeroBasedPosition::MIN;
+    /// assert_eq!(position.checked_add(8), ZeroBasedPosition::new(8));
+    /// assert!(ZeroBasedPosition::MAX.checked_add(1).is_none());
+    /// ```
+    pub fn checked_add(self, other: usize) -> Option<Self> {
+        self.0.checked_add(other).and_then(Self::new)
+    }
+}
+
+impl fmt::Display for ZeroBasedPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// An error returned when a raw 0-based position fails to convert.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TryFromIntError(usize);
+
+impl error::Error for TryFromIntError {}
+
+impl fmt::Display for TryFromIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid 0-based position: expected <= {}, got {}",
+            ZeroBasedPosition::MAX,
+            self.0
+        )
+    }
+}
+
+impl TryFrom<usize> for ZeroBasedPosition {
+    type Error = TryFromIntError;
+
+    fn try_from(n: usize) -> Result<Self, Self::Error> {
+        Self::new(n).ok_or(TryFromIntError(n))
+    }
+}
+
+impl From<ZeroBasedPosition> for usize {
+    fn from(position: ZeroBasedPosition) -> Self {
+        position.0
+    }
+}
+
+impl From<Position> for ZeroBasedPosition {
+    fn from(position: Position) -> Self {
+        Self(usize::from(position) - 1)
+    }
+}
+
+impl From<ZeroBasedPosition> for Position {
+    fn from(position: ZeroBasedPosition) -> Self {
+        match Self::new(position.0 + 1) {
+            Some(p) => p,
+            None => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_usize_for_zero_based_position() {
+        assert_eq!(ZeroBasedPosition::try_from(0), Ok(ZeroBasedPosition(0)));
+        assert_eq!(
+            ZeroBasedPosition::try_from(usize::MAX),
+            Err(TryFromIntError(usize::MAX))
+        );
+    }
+
+    #[test]
+    fn test_from_position_for_zero_based_position() {
+        assert_eq!(
+            ZeroBasedPosition::from(Position::MIN),
+            ZeroBasedPosition::MIN
+        );
+        assert_eq!(
+            ZeroBasedPosition::from(Position::MAX),
+            ZeroBasedPosition::MAX
+        );
+    }
+
+    #[test]
+    fn test_from_zero_based_position_for_position() {
+        assert_eq!(Position::from(ZeroBasedPosition::MIN), Position::MIN);
+        assert_eq!(Position::from(ZeroBasedPosition::MAX), Position::MAX);
+    }
+}
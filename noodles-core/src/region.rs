@@ -1,8 +1,12 @@
 //! Genomic region.
 
+pub mod half_open_interval;
 pub mod interval;
+pub mod interval_set;
 
-pub use self::interval::Interval;
+pub use self::{
+    half_open_interval::HalfOpenInterval, interval::Interval, interval_set::IntervalSet,
+};
 
 use std::{
     error, fmt,
@@ -10,7 +14,7 @@ use std::{
     str::FromStr,
 };
 
-use super::Position;
+use super::{Position, SequenceDictionary};
 
 /// A genomic region.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -117,6 +121,108 @@ impl Region {
     pub fn interval(&self) -> Interval {
         self.interval
     }
+
+    /// Parses a region, resolving its reference sequence name using a sequence dictionary.
+    ///
+    /// Unlike [`FromStr`], this handles reference sequence names that include `:` or `-`, e.g.,
+    /// `HLA-A*01:01` or `chrUn:KI270742v1`. As in htslib, the input is resolved as
+    ///
+    ///   * `{name}` or `{name}:interval`, where the name in braces is taken as is; otherwise,
+    ///   * the whole input as a reference sequence name, and
+    ///   * `name:interval`, split at the last `:`.
+    ///
+    /// Reference sequence names must be in the dictionary. Neither the whole input nor the split
+    /// takes precedence: if both resolve, e.g., `HLA-A*01:01` when `HLA-A*01` is also in the
+    /// dictionary, the input is ambiguous ([`ParseError::Ambiguous`]), and braces must be used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{
+    ///     region::ParseError, sequence_dictionary::Sequence, Position, Region,
+    ///     SequenceDictionary,
+    /// };
+    ///
+    /// let dictionary: SequenceDictionary = [
+    ///     Sequence::new("sq0", Some(8)),
+    ///     Sequence::new("HLA-A*01:01", Some(13)),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let start = Position::try_from(2)?;
+    /// let end = Position::try_from(5)?;
+    ///
+    /// assert_eq!(
+    ///     Region::parse_with_dictionary("sq0:2-5", &dictionary),
+    ///     Ok(Region::new("sq0", start..=end))
+    /// );
+    ///
+    /// assert_eq!(
+    ///     Region::parse_with_dictionary("HLA-A*01:01", &dictionary),
+    ///     Ok(Region::new("HLA-A*01:01", ..))
+    /// );
+    ///
+    /// assert_eq!(
+    ///     Region::parse_with_dictionary("{HLA-A*01:01}:2-5", &dictionary),
+    ///     Ok(Region::new("HLA-A*01:01", start..=end))
+    /// );
+    ///
+    /// assert_eq!(
+    ///     Region::parse_with_dictionary("sq1", &dictionary),
+    ///     Err(ParseError::MissingReferenceSequence)
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn parse_with_dictionary(
+        s: &str,
+        dictionary: &SequenceDictionary,
+    ) -> Result<Self, ParseError> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let contains = |name| dictionary.get(name).is_some();
+
+        if let Some(t) = s.strip_prefix('{') {
+            let (name, suffix) = t.split_once('}').ok_or(ParseError::Invalid)?;
+
+            if !contains(name) {
+                return Err(ParseError::MissingReferenceSequence);
+            }
+
+            let interval = if suffix.is_empty() {
+                Interval::from(..)
+            } else {
+                suffix
+                    .strip_prefix(':')
+                    .ok_or(ParseError::Invalid)?
+                    .parse()
+                    .map_err(ParseError::InvalidInterval)?
+            };
+
+            return Ok(Self::new(name, interval));
+        }
+
+        let is_name = contains(s);
+
+        if let Some((name, suffix)) = s.rsplit_once(':') {
+            if contains(name) {
+                match suffix.parse::<Interval>() {
+                    Ok(_) if is_name => return Err(ParseError::Ambiguous),
+                    Ok(interval) => return Ok(Self::new(name, interval)),
+                    Err(e) if !is_name => return Err(ParseError::InvalidInterval(e)),
+                    Err(_) => {}
+                }
+            }
+        }
+
+        if is_name {
+            Ok(Self::new(s, ..))
+        } else {
+            Err(ParseError::MissingReferenceSequence)
+        }
+    }
 }
 
 impl fmt::Display for Region {
@@ -143,6 +249,8 @@ pub enum ParseError {
     Invalid,
     /// The interval is invalid.
     InvalidInterval(interval::ParseError),
+    /// The reference sequence is not in the sequence dictionary.
+    MissingReferenceSequence,
 }
 
 impl error::Error for ParseError {}
@@ -154,6 +262,7 @@ impl fmt::Display for ParseError {
             Self::Ambiguous => f.write_str("ambiguous input"),
             Self::Invalid => f.write_str("invalid input"),
             Self::InvalidInterval(e) => write!(f, "invalid interval: {}", e),
+            Self::MissingReferenceSequence => f.write_str("missing reference sequence"),
         }
     }
 }
@@ -207,4 +316,60 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_parse_with_dictionary() -> Result<(), crate::position::TryFromIntError> {
+        use crate::sequence_dictionary::Sequence;
+
+        let dictionary: SequenceDictionary = [
+            Sequence::new("sq0", Some(8)),
+            Sequence::new("chrUn:KI270742v1", Some(13)),
+            Sequence::new("HLA-A*01:01", Some(21)),
+            Sequence::new("HLA-A*01", Some(34)),
+        ]
+        .into_iter()
+        .collect();
+
+        let parse = |s| Region::parse_with_dictionary(s, &dictionary);
+
+        let start = Position::try_from(5)?;
+        let end = Position::try_from(8)?;
+
+        assert_eq!(parse("sq0"), Ok(Region::new("sq0", ..)));
+        assert_eq!(parse("sq0:"), Ok(Region::new("sq0", ..)));
+        assert_eq!(parse("sq0:5"), Ok(Region::new("sq0", start..)));
+        assert_eq!(parse("sq0:5-8"), Ok(Region::new("sq0", start..=end)));
+
+        assert_eq!(
+            parse("chrUn:KI270742v1"),
+            Ok(Region::new("chrUn:KI270742v1", ..))
+        );
+        assert_eq!(
+            parse("chrUn:KI270742v1:5-8"),
+            Ok(Region::new("chrUn:KI270742v1", start..=end))
+        );
+
+        assert_eq!(parse("HLA-A*01:01"), Err(ParseError::Ambiguous));
+        assert_eq!(parse("{HLA-A*01:01}"), Ok(Region::new("HLA-A*01:01", ..)));
+        assert_eq!(
+            parse("{HLA-A*01}:01"),
+            Ok(Region::new("HLA-A*01", Position::try_from(1)?..))
+        );
+        assert_eq!(
+            parse("{HLA-A*01:01}:5-8"),
+            Ok(Region::new("HLA-A*01:01", start..=end))
+        );
+
+        assert_eq!(parse(""), Err(ParseError::Empty));
+        assert_eq!(parse("{sq0"), Err(ParseError::Invalid));
+        assert_eq!(parse("{sq0}5-8"), Err(ParseError::Invalid));
+        assert_eq!(parse("sq1"), Err(ParseError::MissingReferenceSequence));
+        assert_eq!(parse("{sq1}"), Err(ParseError::MissingReferenceSequence));
+        assert!(matches!(
+            parse("sq0:x"),
+            Err(ParseError::InvalidInterval(_))
+        ));
+
+        Ok(())
+    }
 }
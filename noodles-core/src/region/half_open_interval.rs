@@ -0,0 +1,254 @@
+//! 0-based, half-open interval.
+
+use std::{error, fmt};
+
+use super::Interval;
+use crate::{position::ZeroBasedPosition, Position};
+
+/// A 0-based, half-open interval ([start, end)).
+///
+/// The start is inclusive, and the end is exclusive. An interval where the start is equal to the
+/// end is empty, e.g., [0, 0).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct HalfOpenInterval {
+    start: ZeroBasedPosition,
+    end: ZeroBasedPosition,
+}
+
+impl HalfOpenInterval {
+    /// Creates a 0-based, half-open interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{position::ZeroBasedPosition, region::HalfOpenInterval};
+    ///
+    /// let start = ZeroBasedPosition::try_from(7)?;
+    /// let end = ZeroBasedPosition::try_from(13)?;
+    /// let interval = HalfOpenInterval::new(start, end);
+    /// # Ok::<_, noodles_core::position::zero_based::TryFromIntError>(())
+    /// ```
+    pub fn new(start: ZeroBasedPosition, end: ZeroBasedPosition) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns the start (inclusive).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{position::ZeroBasedPosition, region::HalfOpenInterval};
+    ///
+    /// let start = ZeroBasedPosition::try_from(7)?;
+    /// let interval = HalfOpenInterval::new(start, ZeroBasedPosition::try_from(13)?);
+    /// assert_eq!(interval.start(), start);
+    /// # Ok::<_, noodles_core::position::zero_based::TryFromIntError>(())
+    /// ```
+    pub fn start(&self) -> ZeroBasedPosition {
+        self.start
+    }
+
+    /// Returns the end (exclusive).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{position::ZeroBasedPosition, region::HalfOpenInterval};
+    ///
+    /// let end = ZeroBasedPosition::try_from(13)?;
+    /// let interval = HalfOpenInterval::new(ZeroBasedPosition::try_from(7)?, end);
+    /// assert_eq!(interval.end(), end);
+    /// # Ok::<_, noodles_core::position::zero_based::TryFromIntError>(())
+    /// ```
+    pub fn end(&self) -> ZeroBasedPosition {
+        self.end
+    }
+
+    /// Returns the number of bases in the interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{position::ZeroBasedPosition, region::HalfOpenInterval};
+    ///
+    /// let interval = HalfOpenInterval::new(
+    ///     ZeroBasedPosition::try_from(7)?,
+    ///     ZeroBasedPosition::try_from(13)?,
+    /// );
+    /// assert_eq!(interval.len(), 6);
+    /// # Ok::<_, noodles_core::position::zero_based::TryFromIntError>(())
+    /// ```
+    pub fn len(&self) -> usize {
+        usize::from(self.end).saturating_sub(usize::from(self.start))
+    }
+
+    /// Returns whether the interval is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{position::ZeroBasedPosition, region::HalfOpenInterval};
+    ///
+    /// let interval = HalfOpenInterval::new(
+    ///     ZeroBasedPosition::try_from(7)?,
+    ///     ZeroBasedPosition::try_from(13)?,
+    /// );
+    /// assert!(!interval.is_empty());
+    ///
+    /// let interval = HalfOpenInterval::new(ZeroBasedPosition::MIN, ZeroBasedPosition::MIN);
+    /// assert!(interval.is_empty());
+    /// # Ok::<_, noodles_core::position::zero_based::TryFromIntError>(())
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Display for HalfOpenInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+impl From<Interval> for HalfOpenInterval {
+    /// Converts a 1-based, closed interval to a 0-based, half-open interval.
+    ///
+    /// An unbounded start or end is resolved to the minimum or maximum 0-based position,
+    /// respectively. Because the exclusive end is one past the last base, an end at
+    /// [`Position::MAX`] is also clamped to [`ZeroBasedPosition::MAX`].
+    fn from(interval: Interval) -> Self {
+        let start = interval
+            .start()
+            .map(ZeroBasedPosition::from)
+            .unwrap_or(ZeroBasedPosition::MIN);
+
+        let end = interval
+            .end()
+            .and_then(|position| ZeroBasedPosition::new(usize::from(position)))
+            .unwrap_or(ZeroBasedPosition::MAX);
+
+        Self { start, end }
+    }
+}
+
+/// An error returned when a 0-based, half-open interval fails to convert to a 1-based, closed
+/// interval.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TryFromHalfOpenIntervalError(HalfOpenInterval);
+
+impl error::Error for TryFromHalfOpenIntervalError {}
+
+impl fmt::Display for TryFromHalfOpenIntervalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid half-open interval: expected non-empty, got {}",
+            self.0
+        )
+    }
+}
+
+impl TryFrom<HalfOpenInterval> for Interval {
+    type Error = TryFromHalfOpenIntervalError;
+
+    /// Converts a 0-based, half-open interval to a 1-based, closed interval.
+    ///
+    /// A closed interval cannot represent an empty interval, so this fails if the half-open
+    /// interval is empty.
+    fn try_from(interval: HalfOpenInterval) -> Result<Self, Self::Error> {
+        if interval.is_empty() {
+            return Err(TryFromHalfOpenIntervalError(interval));
+        }
+
+        let start = Position::from(interval.start);
+
+        // The end is greater than the start, so it is never 0.
+        let end = Position::new(usize::from(interval.end))
+            .ok_or(TryFromHalfOpenIntervalError(interval))?;
+
+        Ok(Self::from(start..=end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
+        let interval = HalfOpenInterval::new(
+            ZeroBasedPosition::try_from(7)?,
+            ZeroBasedPosition::try_from(13)?,
+        );
+        assert_eq!(interval.to_string(), "[7, 13)");
+
+        let interval = HalfOpenInterval::new(ZeroBasedPosition::MIN, ZeroBasedPosition::MIN);
+        assert_eq!(interval.to_string(), "[0, 0)");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_interval_for_half_open_interval() -> Result<(), Box<dyn std::error::Error>> {
+        let interval = Interval::from(Position::try_from(8)?..=Position::try_from(13)?);
+        assert_eq!(
+            HalfOpenInterval::from(interval),
+            HalfOpenInterval::new(
+                ZeroBasedPosition::try_from(7)?,
+                ZeroBasedPosition::try_from(13)?
+            )
+        );
+
+        let interval = Interval::from(Position::MIN..=Position::MIN);
+        assert_eq!(
+            HalfOpenInterval::from(interval),
+            HalfOpenInterval::new(ZeroBasedPosition::MIN, ZeroBasedPosition::try_from(1)?)
+        );
+
+        assert_eq!(
+            HalfOpenInterval::from(Interval::from(..)),
+            HalfOpenInterval::new(ZeroBasedPosition::MIN, ZeroBasedPosition::MAX)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_half_open_interval_for_interval() -> Result<(), Box<dyn std::error::Error>> {
+        let interval = HalfOpenInterval::new(
+            ZeroBasedPosition::try_from(7)?,
+            ZeroBasedPosition::try_from(13)?,
+        );
+        assert_eq!(
+            Interval::try_from(interval),
+            Ok(Interval::from(
+                Position::try_from(8)?..=Position::try_from(13)?
+            ))
+        );
+
+        let interval = HalfOpenInterval::new(ZeroBasedPosition::MIN, ZeroBasedPosition::MIN);
+        assert_eq!(
+            Interval::try_from(interval),
+            Err(TryFromHalfOpenIntervalError(interval))
+        );
+
+        let interval = HalfOpenInterval::new(
+            ZeroBasedPosition::try_from(13)?,
+            ZeroBasedPosition::try_from(13)?,
+        );
+        assert_eq!(
+            Interval::try_from(interval),
+            Err(TryFromHalfOpenIntervalError(interval))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let expected = Interval::from(Position::try_from(8)?..=Position::try_from(13)?);
+        let actual = Interval::try_from(HalfOpenInterval::from(expected))?;
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+}
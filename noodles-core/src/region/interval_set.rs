@@ -0,0 +1,568 @@
+//! Genomic region interval set.
+
+use std::cmp;
+
+use super::Interval;
+use crate::Position;
+
+/// A set of closed intervals on a single sequence.
+///
+/// Intervals are kept sorted by start position, and overlapping or adjacent intervals are merged.
+/// Unbounded starts and ends are resolved to [`Position::MIN`] and [`Position::MAX`],
+/// respectively.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IntervalSet {
+    intervals: Vec<(Position, Position)>,
+}
+
+impl IntervalSet {
+    /// Creates an empty interval set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region::IntervalSet;
+    /// let intervals = IntervalSet::new();
+    /// assert!(intervals.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of disjoint intervals in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::IntervalSet, Position};
+    ///
+    /// let intervals: IntervalSet = [
+    ///     (Position::try_from(8)?..=Position::try_from(13)?).into(),
+    ///     (Position::try_from(13)?..=Position::try_from(21)?).into(),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// assert_eq!(intervals.len(), 1);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Returns whether the set has no intervals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region::IntervalSet;
+    /// let intervals = IntervalSet::new();
+    /// assert!(intervals.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns an iterator over the disjoint intervals in the set, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::{Interval, IntervalSet}, Position};
+    ///
+    /// let mut intervals = IntervalSet::new();
+    /// intervals.insert(Position::try_from(21)?..=Position::try_from(34)?);
+    /// intervals.insert(Position::try_from(8)?..=Position::try_from(13)?);
+    ///
+    /// assert_eq!(
+    ///     intervals.iter().collect::<Vec<_>>(),
+    ///     [
+    ///         Interval::from(Position::try_from(8)?..=Position::try_from(13)?),
+    ///         Interval::from(Position::try_from(21)?..=Position::try_from(34)?),
+    ///     ]
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.intervals
+            .iter()
+            .map(|&(start, end)| Interval::from(start..=end))
+    }
+
+    /// Adds an interval to the set.
+    ///
+    /// An interval where the start is greater than the end is empty and ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::IntervalSet, Position};
+    ///
+    /// let mut intervals = IntervalSet::new();
+    /// intervals.insert(Position::try_from(8)?..=Position::try_from(13)?);
+    /// intervals.insert(Position::try_from(5)?..=Position::try_from(8)?);
+    ///
+    /// assert_eq!(intervals.len(), 1);
+    /// assert_eq!(intervals.covered_len(), 9);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn insert<I>(&mut self, interval: I)
+    where
+        I: Into<Interval>,
+    {
+        let interval = interval.into();
+
+        let start = interval.start().unwrap_or(Position::MIN);
+        let end = interval.end().unwrap_or(Position::MAX);
+
+        if start > end {
+            return;
+        }
+
+        // The first interval that is not strictly before (and not adjacent to) the new interval.
+        let i = self
+            .intervals
+            .partition_point(|&(_, e)| usize::from(e).saturating_add(1) < usize::from(start));
+
+        // The first interval that is strictly after (and not adjacent to) the new interval.
+        let j = i + self.intervals[i..]
+            .partition_point(|&(s, _)| usize::from(s) <= usize::from(end).saturating_add(1));
+
+        if i == j {
+            self.intervals.insert(i, (start, end));
+        } else {
+            let merged_start = cmp::min(start, self.intervals[i].0);
+            let merged_end = cmp::max(end, self.intervals[j - 1].1);
+            self.intervals.splice(i..j, [(merged_start, merged_end)]);
+        }
+    }
+
+    /// Returns whether the given position is in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::IntervalSet, Position};
+    ///
+    /// let mut intervals = IntervalSet::new();
+    /// intervals.insert(Position::try_from(8)?..=Position::try_from(13)?);
+    ///
+    /// assert!(intervals.contains(Position::try_from(8)?));
+    /// assert!(!intervals.contains(Position::try_from(21)?));
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn contains(&self, position: Position) -> bool {
+        let i = self.intervals.partition_point(|&(_, end)| end < position);
+
+        self.intervals
+            .get(i)
+            .map(|&(start, _)| start <= position)
+            .unwrap_or(false)
+    }
+
+    /// Returns whether the given interval intersects any interval in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::IntervalSet, Position};
+    ///
+    /// let mut intervals = IntervalSet::new();
+    /// intervals.insert(Position::try_from(8)?..=Position::try_from(13)?);
+    ///
+    /// assert!(intervals.intersects(Position::try_from(13)?..=Position::try_from(21)?));
+    /// assert!(!intervals.intersects(Position::try_from(21)?..=Position::try_from(34)?));
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn intersects<I>(&self, interval: I) -> bool
+    where
+        I: Into<Interval>,
+    {
+        let interval = interval.into();
+
+        let start = interval.start().unwrap_or(Position::MIN);
+        let end = interval.end().unwrap_or(Position::MAX);
+
+        let i = self.intervals.partition_point(|&(_, e)| e < start);
+
+        self.intervals
+            .get(i)
+            .map(|&(s, _)| s <= end)
+            .unwrap_or(false)
+    }
+
+    /// Returns the total number of positions covered by the set.
+    ///
+    /// This saturates at `usize::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::IntervalSet, Position};
+    ///
+    /// let mut intervals = IntervalSet::new();
+    /// intervals.insert(Position::try_from(8)?..=Position::try_from(13)?);
+    /// intervals.insert(Position::try_from(21)?..=Position::try_from(34)?);
+    ///
+    /// assert_eq!(intervals.covered_len(), 20);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn covered_len(&self) -> usize {
+        self.intervals.iter().fold(0, |n, &(start, end)| {
+            n.saturating_add(usize::from(end) - usize::from(start) + 1)
+        })
+    }
+
+    /// Returns the union of this set and another set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::IntervalSet, Position};
+    ///
+    /// let a: IntervalSet = [(Position::try_from(8)?..=Position::try_from(13)?).into()]
+    ///     .into_iter()
+    ///     .collect();
+    /// let b: IntervalSet = [(Position::try_from(10)?..=Position::try_from(21)?).into()]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let expected: IntervalSet = [(Position::try_from(8)?..=Position::try_from(21)?).into()]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// assert_eq!(a.union(&b), expected);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        set.extend(other.iter());
+        set
+    }
+
+    /// Returns the intersection of this set and another set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::IntervalSet, Position};
+    ///
+    /// let a: IntervalSet = [(Position::try_from(8)?..=Position::try_from(13)?).into()]
+    ///     .into_iter()
+    ///     .collect();
+    /// let b: IntervalSet = [(Position::try_from(10)?..=Position::try_from(21)?).into()]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let expected: IntervalSet = [(Position::try_from(10)?..=Position::try_from(13)?).into()]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// assert_eq!(a.intersection(&b), expected);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+
+        let mut a = self.intervals.iter().peekable();
+        let mut b = other.intervals.iter().peekable();
+
+        while let (Some(&&(a_start, a_end)), Some(&&(b_start, b_end))) = (a.peek(), b.peek()) {
+            let start = cmp::max(a_start, b_start);
+            let end = cmp::min(a_end, b_end);
+
+            if start <= end {
+                intervals.push((start, end));
+            }
+
+            if a_end < b_end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// Returns the positions in this set that are not in another set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::IntervalSet, Position};
+    ///
+    /// let a: IntervalSet = [(Position::try_from(8)?..=Position::try_from(21)?).into()]
+    ///     .into_iter()
+    ///     .collect();
+    /// let b: IntervalSet = [(Position::try_from(10)?..=Position::try_from(13)?).into()]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let expected: IntervalSet = [
+    ///     (Position::try_from(8)?..=Position::try_from(9)?).into(),
+    ///     (Position::try_from(14)?..=Position::try_from(21)?).into(),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// assert_eq!(a.difference(&b), expected);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+
+        let mut b = other.intervals.iter().peekable();
+
+        for &(a_start, a_end) in &self.intervals {
+            // The start of the part of the current interval that is not yet removed, if any.
+            let mut start = Some(a_start);
+
+            while let (Some(s), Some(&&(b_start, b_end))) = (start, b.peek()) {
+                if b_end < s {
+                    b.next();
+                    continue;
+                }
+
+                if b_start > a_end {
+                    break;
+                }
+
+                if b_start > s {
+                    let left_end = usize::from(b_start) - 1;
+                    intervals.extend(Position::new(left_end).map(|p| (s, p)));
+                }
+
+                // An interval in `other` that ends past this one can overlap the next one, so
+                // it is only consumed when it ends inside this one.
+                if b_end < a_end {
+                    start = b_end.checked_add(1);
+                    b.next();
+                } else {
+                    start = None;
+                }
+            }
+
+            if let Some(s) = start {
+                intervals.push((s, a_end));
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// Returns the positions in [1, `length`] that are not in this set.
+    ///
+    /// `length` is typically the length of the reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::IntervalSet, Position};
+    ///
+    /// let intervals: IntervalSet = [(Position::try_from(8)?..=Position::try_from(13)?).into()]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let expected: IntervalSet = [
+    ///     (Position::try_from(1)?..=Position::try_from(7)?).into(),
+    ///     (Position::try_from(14)?..=Position::try_from(21)?).into(),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// assert_eq!(intervals.complement(21), expected);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn complement(&self, length: usize) -> Self {
+        match Position::new(length) {
+            Some(end) => {
+                let mut all = Self::new();
+                all.insert(Position::MIN..=end);
+                all.difference(self)
+            }
+            None => Self::new(),
+        }
+    }
+
+    /// Returns a set where each interval is extended by `n` positions on both sides.
+    ///
+    /// Starts saturate at [`Position::MIN`], and ends saturate at [`Position::MAX`]. To clamp
+    /// ends to a sequence length, intersect the result with the full sequence interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::IntervalSet, Position};
+    ///
+    /// let intervals: IntervalSet = [
+    ///     (Position::try_from(3)?..=Position::try_from(5)?).into(),
+    ///     (Position::try_from(13)?..=Position::try_from(21)?).into(),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let expected: IntervalSet = [
+    ///     (Position::try_from(1)?..=Position::try_from(7)?).into(),
+    ///     (Position::try_from(11)?..=Position::try_from(23)?).into(),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// assert_eq!(intervals.pad(2), expected);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn pad(&self, n: usize) -> Self {
+        self.intervals
+            .iter()
+            .map(|&(start, end)| {
+                let start = usize::from(start)
+                    .checked_sub(n)
+                    .and_then(Position::new)
+                    .unwrap_or(Position::MIN);
+
+                let end = end.checked_add(n).unwrap_or(Position::MAX);
+
+                Interval::from(start..=end)
+            })
+            .collect()
+    }
+}
+
+impl Extend<Interval> for IntervalSet {
+    fn extend<T: IntoIterator<Item = Interval>>(&mut self, iter: T) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item = Interval>>(iter: T) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_set(intervals: &[(usize, usize)]) -> IntervalSet {
+        intervals
+            .iter()
+            .map(|&(start, end)| {
+                let start = Position::new(start).unwrap();
+                let end = Position::new(end).unwrap();
+                Interval::from(start..=end)
+            })
+            .collect()
+    }
+
+    fn to_tuples(set: &IntervalSet) -> Vec<(usize, usize)> {
+        set.intervals
+            .iter()
+            .map(|&(start, end)| (usize::from(start), usize::from(end)))
+            .collect()
+    }
+
+    #[test]
+    fn test_insert() -> Result<(), crate::position::TryFromIntError> {
+        let mut set = build_set(&[(8, 13), (21, 34), (55, 89)]);
+
+        set.insert(Position::try_from(14)?..=Position::try_from(20)?);
+        assert_eq!(to_tuples(&set), [(8, 34), (55, 89)]);
+
+        set.insert(Position::try_from(1)?..=Position::try_from(5)?);
+        assert_eq!(to_tuples(&set), [(1, 5), (8, 34), (55, 89)]);
+
+        set.insert(Position::try_from(4)?..=Position::try_from(60)?);
+        assert_eq!(to_tuples(&set), [(1, 89)]);
+
+        set.insert(Position::try_from(144)?..);
+        assert_eq!(to_tuples(&set), [(1, 89), (144, usize::MAX)]);
+
+        set.insert(Position::try_from(233)?..=Position::try_from(144)?);
+        assert_eq!(to_tuples(&set), [(1, 89), (144, usize::MAX)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_contains() -> Result<(), crate::position::TryFromIntError> {
+        let set = build_set(&[(8, 13), (21, 34)]);
+
+        assert!(!set.contains(Position::try_from(5)?));
+        assert!(set.contains(Position::try_from(8)?));
+        assert!(set.contains(Position::try_from(13)?));
+        assert!(!set.contains(Position::try_from(14)?));
+        assert!(set.contains(Position::try_from(34)?));
+        assert!(!set.contains(Position::try_from(55)?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_covered_len() {
+        assert_eq!(IntervalSet::new().covered_len(), 0);
+        assert_eq!(build_set(&[(8, 13), (21, 34)]).covered_len(), 20);
+
+        let set: IntervalSet = [Interval::from(..)].into_iter().collect();
+        assert_eq!(set.covered_len(), usize::MAX);
+    }
+
+    #[test]
+    fn test_union() {
+        let a = build_set(&[(1, 5), (21, 34)]);
+        let b = build_set(&[(6, 8), (30, 55)]);
+        assert_eq!(to_tuples(&a.union(&b)), [(1, 8), (21, 55)]);
+    }
+
+    #[test]
+    fn test_intersection() {
+        let a = build_set(&[(1, 5), (8, 13), (21, 34)]);
+        let b = build_set(&[(3, 10), (13, 21), (55, 89)]);
+        assert_eq!(
+            to_tuples(&a.intersection(&b)),
+            [(3, 5), (8, 10), (13, 13), (21, 21)]
+        );
+
+        assert!(a.intersection(&IntervalSet::new()).is_empty());
+    }
+
+    #[test]
+    fn test_difference() {
+        let a = build_set(&[(1, 13), (21, 34)]);
+        let b = build_set(&[(1, 2), (5, 8), (13, 25)]);
+        assert_eq!(to_tuples(&a.difference(&b)), [(3, 4), (9, 12), (26, 34)]);
+
+        assert_eq!(a.difference(&IntervalSet::new()), a);
+        assert!(a.difference(&a).is_empty());
+
+        let b = build_set(&[(5, 25)]);
+        assert_eq!(to_tuples(&a.difference(&b)), [(1, 4), (26, 34)]);
+
+        let b = build_set(&[(13, 13), (21, 21), (34, 34)]);
+        assert_eq!(to_tuples(&a.difference(&b)), [(1, 12), (22, 33)]);
+    }
+
+    #[test]
+    fn test_complement() {
+        let set = build_set(&[(1, 5), (8, 13)]);
+        assert_eq!(to_tuples(&set.complement(21)), [(6, 7), (14, 21)]);
+        assert_eq!(to_tuples(&set.complement(10)), [(6, 7)]);
+        assert!(set.complement(0).is_empty());
+        assert_eq!(to_tuples(&IntervalSet::new().complement(8)), [(1, 8)]);
+    }
+
+    #[test]
+    fn test_pad() {
+        let set = build_set(&[(3, 5), (13, 21)]);
+        assert_eq!(to_tuples(&set.pad(0)), [(3, 5), (13, 21)]);
+        assert_eq!(to_tuples(&set.pad(3)), [(1, 8), (10, 24)]);
+
+        let set = build_set(&[(8, usize::MAX - 1)]);
+        assert_eq!(to_tuples(&set.pad(2)), [(6, usize::MAX)]);
+    }
+}
@@ -0,0 +1,435 @@
+//! Genomic region set.
+
+use std::collections::HashMap;
+
+use super::{region::IntervalSet, Region};
+
+/// A set of genomic regions.
+///
+/// This is a collection of interval sets keyed by reference sequence name. Reference sequences
+/// are kept in order of first insertion, and the intervals on each reference sequence are merged
+/// (see [`IntervalSet`]).
+#[derive(Clone, Debug, Default)]
+pub struct RegionSet {
+    names: Vec<String>,
+    interval_sets: HashMap<String, IntervalSet>,
+}
+
+impl RegionSet {
+    /// Creates an empty region set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::RegionSet;
+    /// let regions = RegionSet::new();
+    /// assert!(regions.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether the set has no regions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::RegionSet;
+    /// let regions = RegionSet::new();
+    /// assert!(regions.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.interval_sets.values().all(|set| set.is_empty())
+    }
+
+    /// Returns the interval set of the given reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Position, Region, RegionSet};
+    ///
+    /// let mut regions = RegionSet::new();
+    /// regions.insert(Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?));
+    ///
+    /// assert!(regions.get("sq0").is_some());
+    /// assert!(regions.get("sq1").is_none());
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&IntervalSet> {
+        self.interval_sets.get(name)
+    }
+
+    /// Returns an iterator over the reference sequence names and their interval sets.
+    ///
+    /// Reference sequences are in order of first insertion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Position, Region, RegionSet};
+    ///
+    /// let mut regions = RegionSet::new();
+    /// regions.insert(Region::new("sq1", Position::try_from(8)?..=Position::try_from(13)?));
+    /// regions.insert(Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?));
+    ///
+    /// let names: Vec<_> = regions.interval_sets().map(|(name, _)| name).collect();
+    /// assert_eq!(names, ["sq1", "sq0"]);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn interval_sets(&self) -> impl Iterator<Item = (&str, &IntervalSet)> {
+        self.names
+            .iter()
+            .filter_map(|name| self.interval_sets.get(name).map(|set| (name.as_str(), set)))
+    }
+
+    /// Returns an iterator over the disjoint regions in the set.
+    ///
+    /// Regions are grouped by reference sequence, in order of first insertion, and sorted by
+    /// start position. This can be collected and passed to readers that take a list of regions,
+    /// e.g., `query_many`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Position, Region, RegionSet};
+    ///
+    /// let mut regions = RegionSet::new();
+    /// regions.insert(Region::new("sq0", Position::try_from(21)?..=Position::try_from(34)?));
+    /// regions.insert(Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?));
+    /// regions.insert(Region::new("sq0", Position::try_from(13)?..=Position::try_from(21)?));
+    ///
+    /// let actual: Vec<_> = regions.iter().collect();
+    /// let expected = [Region::new("sq0", Position::try_from(8)?..=Position::try_from(34)?)];
+    /// assert_eq!(actual, expected);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = Region> + '_ {
+        self.interval_sets()
+            .flat_map(|(name, set)| set.iter().map(move |interval| Region::new(name, interval)))
+    }
+
+    /// Adds a region to the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Position, Region, RegionSet};
+    ///
+    /// let mut regions = RegionSet::new();
+    /// regions.insert(Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?));
+    ///
+    /// assert!(!regions.is_empty());
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn insert(&mut self, region: Region) {
+        self.entry(region.name()).insert(region.interval());
+    }
+
+    /// Returns the total number of positions covered by the set.
+    ///
+    /// This saturates at `usize::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Position, Region, RegionSet};
+    ///
+    /// let mut regions = RegionSet::new();
+    /// regions.insert(Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?));
+    /// regions.insert(Region::new("sq1", Position::try_from(8)?..=Position::try_from(13)?));
+    ///
+    /// assert_eq!(regions.covered_len(), 12);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn covered_len(&self) -> usize {
+        self.interval_sets
+            .values()
+            .fold(0, |n, set| n.saturating_add(set.covered_len()))
+    }
+
+    /// Returns the union of this set and another set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Position, Region, RegionSet};
+    ///
+    /// let a: RegionSet = [Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?)]
+    ///     .into_iter()
+    ///     .collect();
+    /// let b: RegionSet = [Region::new("sq1", Position::try_from(8)?..=Position::try_from(13)?)]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// assert_eq!(a.union(&b).iter().count(), 2);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+
+        for (name, other_set) in other.interval_sets() {
+            let entry = set.entry(name);
+            *entry = entry.union(other_set);
+        }
+
+        set
+    }
+
+    /// Returns the intersection of this set and another set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Position, Region, RegionSet};
+    ///
+    /// let a: RegionSet = [Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?)]
+    ///     .into_iter()
+    ///     .collect();
+    /// let b: RegionSet = [Region::new("sq0", Position::try_from(10)?..=Position::try_from(21)?)]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let actual: Vec<_> = a.intersection(&b).iter().collect();
+    /// let expected = [Region::new("sq0", Position::try_from(10)?..=Position::try_from(13)?)];
+    /// assert_eq!(actual, expected);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.map(|name, set| {
+            other
+                .get(name)
+                .map(|other_set| set.intersection(other_set))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns the regions in this set that are not in another set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Position, Region, RegionSet};
+    ///
+    /// let a: RegionSet = [Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?)]
+    ///     .into_iter()
+    ///     .collect();
+    /// let b: RegionSet = [Region::new("sq0", Position::try_from(10)?..=Position::try_from(21)?)]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let actual: Vec<_> = a.difference(&b).iter().collect();
+    /// let expected = [Region::new("sq0", Position::try_from(8)?..=Position::try_from(9)?)];
+    /// assert_eq!(actual, expected);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn difference(&self, other: &Self) -> Self {
+        self.map(|name, set| match other.get(name) {
+            Some(other_set) => set.difference(other_set),
+            None => set.clone(),
+        })
+    }
+
+    /// Returns the regions of the given reference sequences that are not in this set.
+    ///
+    /// `reference_sequences` is a list of reference sequence names and lengths, e.g., from a
+    /// sequence dictionary. The result is in the same order. Regions on reference sequences that
+    /// are not in the list are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Position, Region, RegionSet};
+    ///
+    /// let regions: RegionSet = [Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?)]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let actual: Vec<_> = regions.complement([("sq0", 21), ("sq1", 8)]).iter().collect();
+    /// let expected = [
+    ///     Region::new("sq0", Position::try_from(1)?..=Position::try_from(7)?),
+    ///     Region::new("sq0", Position::try_from(14)?..=Position::try_from(21)?),
+    ///     Region::new("sq1", Position::try_from(1)?..=Position::try_from(8)?),
+    /// ];
+    /// assert_eq!(actual, expected);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn complement<I, N>(&self, reference_sequences: I) -> Self
+    where
+        I: IntoIterator<Item = (N, usize)>,
+        N: AsRef<str>,
+    {
+        let mut set = Self::new();
+
+        for (name, length) in reference_sequences {
+            let name = name.as_ref();
+
+            let complement = match self.get(name) {
+                Some(interval_set) => interval_set.complement(length),
+                None => IntervalSet::new().complement(length),
+            };
+
+            *set.entry(name) = complement;
+        }
+
+        set
+    }
+
+    /// Returns a set where each region is extended by `n` positions on both sides.
+    ///
+    /// See [`IntervalSet::pad`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Position, Region, RegionSet};
+    ///
+    /// let regions: RegionSet = [Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?)]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let actual: Vec<_> = regions.pad(5).iter().collect();
+    /// let expected = [Region::new("sq0", Position::try_from(3)?..=Position::try_from(18)?)];
+    /// assert_eq!(actual, expected);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn pad(&self, n: usize) -> Self {
+        self.map(|_, set| set.pad(n))
+    }
+
+    fn entry(&mut self, name: &str) -> &mut IntervalSet {
+        if !self.interval_sets.contains_key(name) {
+            self.names.push(name.into());
+        }
+
+        self.interval_sets.entry(name.into()).or_default()
+    }
+
+    fn map<F>(&self, mut f: F) -> Self
+    where
+        F: FnMut(&str, &IntervalSet) -> IntervalSet,
+    {
+        let mut set = Self::new();
+
+        for (name, interval_set) in self.interval_sets() {
+            *set.entry(name) = f(name, interval_set);
+        }
+
+        set
+    }
+}
+
+impl PartialEq for RegionSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for RegionSet {}
+
+impl Extend<Region> for RegionSet {
+    fn extend<T: IntoIterator<Item = Region>>(&mut self, iter: T) {
+        for region in iter {
+            self.insert(region);
+        }
+    }
+}
+
+impl FromIterator<Region> for RegionSet {
+    fn from_iter<T: IntoIterator<Item = Region>>(iter: T) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Position;
+
+    fn region(name: &str, start: usize, end: usize) -> Region {
+        let start = Position::new(start).unwrap();
+        let end = Position::new(end).unwrap();
+        Region::new(name, start..=end)
+    }
+
+    #[test]
+    fn test_iter() {
+        let regions: RegionSet = [
+            region("sq1", 8, 13),
+            region("sq0", 21, 34),
+            region("sq1", 1, 5),
+            region("sq0", 1, 8),
+        ]
+        .into_iter()
+        .collect();
+
+        let actual: Vec<_> = regions.iter().collect();
+        let expected = [
+            region("sq1", 1, 5),
+            region("sq1", 8, 13),
+            region("sq0", 1, 8),
+            region("sq0", 21, 34),
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: RegionSet = [region("sq0", 1, 13), region("sq1", 8, 13)]
+            .into_iter()
+            .collect();
+        let b: RegionSet = [region("sq0", 8, 21), region("sq2", 1, 5)]
+            .into_iter()
+            .collect();
+
+        let expected: RegionSet = [
+            region("sq0", 1, 21),
+            region("sq1", 8, 13),
+            region("sq2", 1, 5),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(a.union(&b), expected);
+
+        let expected: RegionSet = [region("sq0", 8, 13)].into_iter().collect();
+        assert_eq!(a.intersection(&b), expected);
+
+        let expected: RegionSet = [region("sq0", 1, 7), region("sq1", 8, 13)]
+            .into_iter()
+            .collect();
+        assert_eq!(a.difference(&b), expected);
+    }
+
+    #[test]
+    fn test_complement() {
+        let regions: RegionSet = [region("sq0", 1, 13), region("sq2", 8, 13)]
+            .into_iter()
+            .collect();
+
+        let actual = regions.complement([("sq0", 13), ("sq1", 8), ("sq2", 21)]);
+        let expected: RegionSet = [
+            region("sq1", 1, 8),
+            region("sq2", 1, 7),
+            region("sq2", 14, 21),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(actual, expected);
+        assert_eq!(actual.covered_len(), 23);
+    }
+
+    #[test]
+    fn test_is_empty() {
+        assert!(RegionSet::new().is_empty());
+
+        let regions: RegionSet = [region("sq0", 1, 13)].into_iter().collect();
+        assert!(!regions.is_empty());
+
+        // An empty interval set on a reference sequence does not make the region set non-empty.
+        assert!(regions.difference(&regions).is_empty());
+    }
+}
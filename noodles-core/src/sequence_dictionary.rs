@@ -0,0 +1,443 @@
+//! Sequence dictionary.
+
+pub mod difference;
+pub mod sequence;
+
+pub use self::{difference::Difference, sequence::Sequence};
+
+use std::{collections::HashMap, slice};
+
+/// A sequence dictionary.
+///
+/// A sequence dictionary is an ordered list of reference sequence names and lengths, with
+/// optional MD5 checksums and alternative names. It is a common representation of, e.g., the
+/// reference sequences in a SAM header, the contigs in a VCF header, the records in a FASTA
+/// index, and the reference sequence names in a tabix index header. The format crates provide
+/// conversions to and from their types.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SequenceDictionary {
+    sequences: Vec<Sequence>,
+    indices: HashMap<String, usize>,
+    // Alternative names and normalized primary and alternative names. Each maps to the first
+    // sequence with the name.
+    alternative_name_indices: HashMap<String, usize>,
+    normalized_name_indices: HashMap<String, usize>,
+}
+
+impl SequenceDictionary {
+    /// Creates an empty sequence dictionary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::SequenceDictionary;
+    /// let dictionary = SequenceDictionary::new();
+    /// assert!(dictionary.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of sequences in the dictionary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{sequence_dictionary::Sequence, SequenceDictionary};
+    /// let dictionary: SequenceDictionary = [Sequence::new("sq0", Some(13))].into_iter().collect();
+    /// assert_eq!(dictionary.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    /// Returns whether the dictionary has no sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::SequenceDictionary;
+    /// let dictionary = SequenceDictionary::new();
+    /// assert!(dictionary.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+
+    /// Returns an iterator over the sequences in the dictionary, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{sequence_dictionary::Sequence, SequenceDictionary};
+    ///
+    /// let dictionary: SequenceDictionary = [
+    ///     Sequence::new("sq0", Some(8)),
+    ///     Sequence::new("sq1", Some(13)),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let names: Vec<_> = dictionary.iter().map(|sequence| sequence.name()).collect();
+    /// assert_eq!(names, ["sq0", "sq1"]);
+    /// ```
+    pub fn iter(&self) -> slice::Iter<'_, Sequence> {
+        self.sequences.iter()
+    }
+
+    /// Returns the sequence with the given name.
+    ///
+    /// This only matches primary names. See [`Self::find`] to also match aliases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{sequence_dictionary::Sequence, SequenceDictionary};
+    /// let dictionary: SequenceDictionary = [Sequence::new("sq0", Some(13))].into_iter().collect();
+    /// assert!(dictionary.get("sq0").is_some());
+    /// assert!(dictionary.get("sq1").is_none());
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&Sequence> {
+        self.get_index_of(name).map(|i| &self.sequences[i])
+    }
+
+    /// Returns the index of the sequence with the given name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{sequence_dictionary::Sequence, SequenceDictionary};
+    ///
+    /// let dictionary: SequenceDictionary = [
+    ///     Sequence::new("sq0", Some(8)),
+    ///     Sequence::new("sq1", Some(13)),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// assert_eq!(dictionary.get_index_of("sq1"), Some(1));
+    /// ```
+    pub fn get_index_of(&self, name: &str) -> Option<usize> {
+        self.indices.get(name).copied()
+    }
+
+    /// Returns the index and sequence with the given name or alias.
+    ///
+    /// A name is matched, in order, to
+    ///
+    ///   1. a primary name,
+    ///   2. an alternative name of a sequence (e.g., from the SAM header `@SQ` `AN` field),
+    ///      or
+    ///   3. a primary or alternative name when ignoring a `chr` prefix and treating `M` and `MT`
+    ///      as the same name, e.g., `chr1` and `1` or `chrM` and `MT`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{sequence_dictionary::Sequence, SequenceDictionary};
+    ///
+    /// let dictionary: SequenceDictionary = [
+    ///     Sequence::new("chr1", Some(8)),
+    ///     Sequence::new("chrM", Some(13)),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// assert_eq!(dictionary.find("1").map(|(i, _)| i), Some(0));
+    /// assert_eq!(dictionary.find("MT").map(|(i, _)| i), Some(1));
+    /// assert!(dictionary.find("2").is_none());
+    /// ```
+    pub fn find(&self, name: &str) -> Option<(usize, &Sequence)> {
+        self.get_index_of(name)
+            .or_else(|| self.alternative_name_indices.get(name).copied())
+            .or_else(|| {
+                self.normalized_name_indices
+                    .get(normalize_name(name))
+                    .copied()
+            })
+            .map(|i| (i, &self.sequences[i]))
+    }
+
+    /// Adds a sequence to the dictionary.
+    ///
+    /// If a sequence with the same name exists, it is replaced, keeping its position, and the old
+    /// sequence is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{sequence_dictionary::Sequence, SequenceDictionary};
+    ///
+    /// let mut dictionary = SequenceDictionary::new();
+    /// assert!(dictionary.insert(Sequence::new("sq0", None)).is_none());
+    /// assert!(dictionary.insert(Sequence::new("sq0", Some(13))).is_some());
+    ///
+    /// assert_eq!(dictionary.len(), 1);
+    /// ```
+    pub fn insert(&mut self, sequence: Sequence) -> Option<Sequence> {
+        match self.get_index_of(sequence.name()) {
+            Some(i) => {
+                let old_sequence = std::mem::replace(&mut self.sequences[i], sequence);
+
+                // The replaced sequence's aliases can be shadowing those of later sequences.
+                self.alternative_name_indices.clear();
+                self.normalized_name_indices.clear();
+
+                for j in 0..self.sequences.len() {
+                    self.index_aliases(j);
+                }
+
+                Some(old_sequence)
+            }
+            None => {
+                let i = self.sequences.len();
+                self.indices.insert(sequence.name().into(), i);
+                self.sequences.push(sequence);
+                self.index_aliases(i);
+                None
+            }
+        }
+    }
+
+    /// Compares this (expected) dictionary to another (actual) dictionary.
+    ///
+    /// Sequences are matched by name or alias (see [`Self::find`]). Lengths and MD5 checksums of
+    /// matched sequences are only compared when both are set. The order of the sequences is not
+    /// compared.
+    ///
+    /// An empty list means the dictionaries are the same. Use [`Difference::is_incompatible`] to
+    /// ignore differences that are only aliased names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{
+    ///     sequence_dictionary::{Difference, Sequence},
+    ///     SequenceDictionary,
+    /// };
+    ///
+    /// let expected: SequenceDictionary = [
+    ///     Sequence::new("chr1", Some(8)),
+    ///     Sequence::new("chr2", Some(13)),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let actual: SequenceDictionary = [Sequence::new("1", Some(8))].into_iter().collect();
+    ///
+    /// assert_eq!(
+    ///     expected.compare(&actual),
+    ///     [
+    ///         Difference::AliasedName {
+    ///             name: String::from("chr1"),
+    ///             actual_name: String::from("1"),
+    ///         },
+    ///         Difference::MissingSequence { name: String::from("chr2") },
+    ///     ]
+    /// );
+    /// ```
+    pub fn compare(&self, actual: &Self) -> Vec<Difference> {
+        let mut differences = Vec::new();
+        let mut is_matched = vec![false; actual.len()];
+
+        for sequence in self.iter() {
+            let name = sequence.name();
+
+            let (i, actual_sequence) = match actual.find(name) {
+                Some(m) => m,
+                None => {
+                    differences.push(Difference::MissingSequence { name: name.into() });
+                    continue;
+                }
+            };
+
+            is_matched[i] = true;
+
+            if actual_sequence.name() != name {
+                differences.push(Difference::AliasedName {
+                    name: name.into(),
+                    actual_name: actual_sequence.name().into(),
+                });
+            }
+
+            if let (Some(expected), Some(actual)) = (sequence.length(), actual_sequence.length()) {
+                if expected != actual {
+                    differences.push(Difference::LengthMismatch {
+                        name: name.into(),
+                        expected,
+                        actual,
+                    });
+                }
+            }
+
+            if let (Some(expected), Some(actual)) =
+                (sequence.md5_checksum(), actual_sequence.md5_checksum())
+            {
+                if expected != actual {
+                    differences.push(Difference::Md5ChecksumMismatch {
+                        name: name.into(),
+                        expected,
+                        actual,
+                    });
+                }
+            }
+        }
+
+        for (sequence, is_matched) in actual.iter().zip(is_matched) {
+            if !is_matched {
+                differences.push(Difference::UnexpectedSequence {
+                    name: sequence.name().into(),
+                });
+            }
+        }
+
+        differences
+    }
+
+    fn index_aliases(&mut self, i: usize) {
+        let sequence = &self.sequences[i];
+
+        for name in sequence.alternative_names() {
+            self.alternative_name_indices
+                .entry(name.clone())
+                .or_insert(i);
+        }
+
+        for name in sequence.names() {
+            self.normalized_name_indices
+                .entry(normalize_name(name).into())
+                .or_insert(i);
+        }
+    }
+}
+
+impl Extend<Sequence> for SequenceDictionary {
+    fn extend<T: IntoIterator<Item = Sequence>>(&mut self, iter: T) {
+        for sequence in iter {
+            self.insert(sequence);
+        }
+    }
+}
+
+impl FromIterator<Sequence> for SequenceDictionary {
+    fn from_iter<T: IntoIterator<Item = Sequence>>(iter: T) -> Self {
+        let mut dictionary = Self::new();
+        dictionary.extend(iter);
+        dictionary
+    }
+}
+
+impl<'a> IntoIterator for &'a SequenceDictionary {
+    type Item = &'a Sequence;
+    type IntoIter = slice::Iter<'a, Sequence>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+fn normalize_name(name: &str) -> &str {
+    match name.strip_prefix("chr").unwrap_or(name) {
+        "M" => "MT",
+        s => s,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let mut sq2 = Sequence::new("sq2", Some(21));
+        sq2.alternative_names_mut().push(String::from("2"));
+
+        let dictionary: SequenceDictionary = [
+            Sequence::new("chr1", Some(8)),
+            Sequence::new("MT", Some(13)),
+            sq2,
+        ]
+        .into_iter()
+        .collect();
+
+        let find = |name| dictionary.find(name).map(|(i, _)| i);
+
+        assert_eq!(find("chr1"), Some(0));
+        assert_eq!(find("1"), Some(0));
+        assert_eq!(find("chrM"), Some(1));
+        assert_eq!(find("M"), Some(1));
+        assert_eq!(find("2"), Some(2));
+        assert_eq!(find("chr2"), Some(2));
+        assert_eq!(find("3"), None);
+    }
+
+    #[test]
+    fn test_find_after_replacing_a_sequence() {
+        let mut sq0 = Sequence::new("sq0", Some(8));
+        sq0.alternative_names_mut().push(String::from("0"));
+
+        let mut sq1 = Sequence::new("sq1", Some(13));
+        sq1.alternative_names_mut().push(String::from("0"));
+
+        let mut dictionary: SequenceDictionary = [sq0, sq1].into_iter().collect();
+        assert_eq!(dictionary.find("0").map(|(i, _)| i), Some(0));
+
+        dictionary.insert(Sequence::new("sq0", Some(8)));
+        assert_eq!(dictionary.find("0").map(|(i, _)| i), Some(1));
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut expected_sq0 = Sequence::new("sq0", Some(8));
+        *expected_sq0.md5_checksum_mut() = Some([0; 16]);
+
+        let mut actual_sq0 = Sequence::new("sq0", Some(8));
+        *actual_sq0.md5_checksum_mut() = Some([1; 16]);
+
+        let expected: SequenceDictionary = [
+            expected_sq0,
+            Sequence::new("sq1", Some(13)),
+            Sequence::new("sq2", Some(21)),
+            Sequence::new("chrX", None),
+        ]
+        .into_iter()
+        .collect();
+
+        let actual: SequenceDictionary = [
+            Sequence::new("X", Some(34)),
+            actual_sq0,
+            Sequence::new("sq1", Some(55)),
+            Sequence::new("sq3", Some(89)),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            expected.compare(&actual),
+            [
+                Difference::Md5ChecksumMismatch {
+                    name: String::from("sq0"),
+                    expected: [0; 16],
+                    actual: [1; 16],
+                },
+                Difference::LengthMismatch {
+                    name: String::from("sq1"),
+                    expected: 13,
+                    actual: 55,
+                },
+                Difference::MissingSequence {
+                    name: String::from("sq2"),
+                },
+                Difference::AliasedName {
+                    name: String::from("chrX"),
+                    actual_name: String::from("X"),
+                },
+                Difference::UnexpectedSequence {
+                    name: String::from("sq3"),
+                },
+            ]
+        );
+
+        assert!(expected.compare(&expected).is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+//! Sequence dictionary difference.
+
+use std::fmt;
+
+/// A difference between two sequence dictionaries.
+///
+/// This is returned by [`super::SequenceDictionary::compare`], where the first dictionary is
+/// "expected" and the second is "actual".
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Difference {
+    /// A sequence in the expected dictionary is not in the actual dictionary.
+    MissingSequence {
+        /// The name in the expected dictionary.
+        name: String,
+    },
+    /// A sequence in the actual dictionary is not in the expected dictionary.
+    UnexpectedSequence {
+        /// The name in the actual dictionary.
+        name: String,
+    },
+    /// A sequence was matched using an alias rather than its name.
+    AliasedName {
+        /// The name in the expected dictionary.
+        name: String,
+        /// The name in the actual dictionary.
+        actual_name: String,
+    },
+    /// The lengths of matching sequences differ.
+    LengthMismatch {
+        /// The name in the expected dictionary.
+        name: String,
+        /// The expected length.
+        expected: usize,
+        /// The actual length.
+        actual: usize,
+    },
+    /// The MD5 checksums of matching sequences differ.
+    Md5ChecksumMismatch {
+        /// The name in the expected dictionary.
+        name: String,
+        /// The expected MD5 checksum.
+        expected: [u8; 16],
+        /// The actual MD5 checksum.
+        actual: [u8; 16],
+    },
+}
+
+impl Difference {
+    /// Returns whether this difference makes the dictionaries incompatible.
+    ///
+    /// Aliased names are compatible; all other differences are not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::sequence_dictionary::Difference;
+    ///
+    /// let difference = Difference::AliasedName {
+    ///     name: String::from("chr1"),
+    ///     actual_name: String::from("1"),
+    /// };
+    /// assert!(!difference.is_incompatible());
+    ///
+    /// let difference = Difference::MissingSequence { name: String::from("chr1") };
+    /// assert!(difference.is_incompatible());
+    /// ```
+    pub fn is_incompatible(&self) -> bool {
+        !matches!(self, Self::AliasedName { .. })
+    }
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSequence { name } => write!(f, "missing sequence: {}", name),
+            Self::UnexpectedSequence { name } => write!(f, "unexpected sequence: {}", name),
+            Self::AliasedName { name, actual_name } => {
+                write!(f, "sequence {} matched alias {}", name, actual_name)
+            }
+            Self::LengthMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "length mismatch for sequence {}: expected {}, got {}",
+                name, expected, actual
+            ),
+            Self::Md5ChecksumMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "MD5 checksum mismatch for sequence {}: expected {}, got {}",
+                name,
+                Hex(expected),
+                Hex(actual)
+            ),
+        }
+    }
+}
+
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in self.0 {
+            write!(f, "{:02x}", b)?;
+        }
+
+        Ok(())
+    }
+}
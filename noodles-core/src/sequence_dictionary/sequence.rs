@@ -0,0 +1,139 @@
+//! Sequence dictionary sequence.
+
+/// A sequence in a sequence dictionary.
+///
+/// This is the common subset of reference sequence records across formats, e.g., a SAM header
+/// `@SQ` record, a VCF header `contig` record, or a FASTA index record.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sequence {
+    name: String,
+    length: Option<usize>,
+    md5_checksum: Option<[u8; 16]>,
+    alternative_names: Vec<String>,
+}
+
+impl Sequence {
+    /// Creates a sequence dictionary sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::sequence_dictionary::Sequence;
+    /// let sequence = Sequence::new("sq0", Some(13));
+    /// ```
+    pub fn new<N>(name: N, length: Option<usize>) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            length,
+            md5_checksum: None,
+            alternative_names: Vec::new(),
+        }
+    }
+
+    /// Returns the name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::sequence_dictionary::Sequence;
+    /// let sequence = Sequence::new("sq0", Some(13));
+    /// assert_eq!(sequence.name(), "sq0");
+    /// ```
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::sequence_dictionary::Sequence;
+    /// let sequence = Sequence::new("sq0", Some(13));
+    /// assert_eq!(sequence.length(), Some(13));
+    /// ```
+    pub fn length(&self) -> Option<usize> {
+        self.length
+    }
+
+    /// Returns a mutable reference to the length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::sequence_dictionary::Sequence;
+    ///
+    /// let mut sequence = Sequence::new("sq0", None);
+    /// *sequence.length_mut() = Some(13);
+    ///
+    /// assert_eq!(sequence.length(), Some(13));
+    /// ```
+    pub fn length_mut(&mut self) -> &mut Option<usize> {
+        &mut self.length
+    }
+
+    /// Returns the MD5 checksum of the sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::sequence_dictionary::Sequence;
+    /// let sequence = Sequence::new("sq0", Some(13));
+    /// assert!(sequence.md5_checksum().is_none());
+    /// ```
+    pub fn md5_checksum(&self) -> Option<[u8; 16]> {
+        self.md5_checksum
+    }
+
+    /// Returns a mutable reference to the MD5 checksum of the sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::sequence_dictionary::Sequence;
+    ///
+    /// let mut sequence = Sequence::new("sq0", Some(13));
+    /// *sequence.md5_checksum_mut() = Some([0; 16]);
+    ///
+    /// assert_eq!(sequence.md5_checksum(), Some([0; 16]));
+    /// ```
+    pub fn md5_checksum_mut(&mut self) -> &mut Option<[u8; 16]> {
+        &mut self.md5_checksum
+    }
+
+    /// Returns the alternative names of the sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::sequence_dictionary::Sequence;
+    /// let sequence = Sequence::new("sq0", Some(13));
+    /// assert!(sequence.alternative_names().is_empty());
+    /// ```
+    pub fn alternative_names(&self) -> &[String] {
+        &self.alternative_names
+    }
+
+    /// Returns a mutable reference to the alternative names of the sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::sequence_dictionary::Sequence;
+    ///
+    /// let mut sequence = Sequence::new("chr1", Some(13));
+    /// sequence.alternative_names_mut().push(String::from("1"));
+    ///
+    /// assert_eq!(sequence.alternative_names(), ["1"]);
+    /// ```
+    pub fn alternative_names_mut(&mut self) -> &mut Vec<String> {
+        &mut self.alternative_names
+    }
+
+    pub(super) fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name.as_str()).chain(self.alternative_names.iter().map(|s| s.as_str()))
+    }
+}
@@ -5,9 +5,10 @@ pub mod r#async;
 
 mod reader;
 pub mod record;
+pub mod verify;
 mod writer;
 
-pub use self::{reader::Reader, record::Record, writer::Writer};
+pub use self::{reader::Reader, record::Record, verify::verify, writer::Writer};
 
 #[cfg(feature = "async")]
 pub use self::r#async::{Reader as AsyncReader, Writer as AsyncWriter};
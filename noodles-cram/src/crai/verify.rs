@@ -0,0 +1,191 @@
+//! CRAM index verification.
+
+use std::{
+    cmp::Ordering,
+    error, fmt,
+    io::{self, Read, Seek},
+};
+
+use super::{Index, Record};
+use crate::{indexer::build_index, Reader};
+
+/// A CRAM index verification error.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// A slice in the data has no matching index record.
+    MissingRecord(Record),
+    /// An index record does not match any slice in the data.
+    UnexpectedRecord(Record),
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingRecord(record) => write!(
+                f,
+                "missing index record for slice at offset {} (landmark {})",
+                record.offset(),
+                record.landmark()
+            ),
+            Self::UnexpectedRecord(record) => write!(
+                f,
+                "index record at offset {} (landmark {}) does not match any slice",
+                record.offset(),
+                record.landmark()
+            ),
+        }
+    }
+}
+
+/// Verifies a CRAM index against the data containers in a CRAM file.
+///
+/// The reader must be at the start of the stream. Each slice is read to compute its index
+/// record(s), i.e., its reference sequence ID, alignment start and span, container offset,
+/// landmark, and slice length. These are compared to the records in the index.
+///
+/// An empty list means the index is consistent with the data.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_cram::{self as cram, crai};
+///
+/// let mut reader = File::open("sample.cram").map(cram::Reader::new)?;
+/// let index = crai::read("sample.cram.crai")?;
+///
+/// for error in crai::verify(&mut reader, &index)? {
+///     eprintln!("{}", error);
+/// }
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn verify<R>(reader: &mut Reader<R>, index: &[Record]) -> io::Result<Vec<Error>>
+where
+    R: Read + Seek,
+{
+    reader.read_file_definition()?;
+    reader.read_file_header()?;
+
+    let expected = build_index(reader)?;
+    Ok(diff(expected, index.to_vec()))
+}
+
+fn diff(mut expected: Index, mut actual: Index) -> Vec<Error> {
+    expected.sort_by(cmp_records);
+    actual.sort_by(cmp_records);
+
+    let mut errors = Vec::new();
+
+    let mut expected = expected.into_iter().peekable();
+    let mut actual = actual.into_iter().peekable();
+
+    loop {
+        let ordering = match (expected.peek(), actual.peek()) {
+            (Some(e), Some(a)) => cmp_records(e, a),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => break,
+        };
+
+        match ordering {
+            Ordering::Less => errors.extend(expected.next().map(Error::MissingRecord)),
+            Ordering::Greater => errors.extend(actual.next().map(Error::UnexpectedRecord)),
+            Ordering::Equal => {
+                expected.next();
+                actual.next();
+            }
+        }
+    }
+
+    errors
+}
+
+fn cmp_records(a: &Record, b: &Record) -> Ordering {
+    fn key(record: &Record) -> (u64, u64, Option<usize>, Option<usize>, usize, u64) {
+        (
+            record.offset(),
+            record.landmark(),
+            record.reference_sequence_id(),
+            record.alignment_start().map(usize::from),
+            record.alignment_span(),
+            record.slice_length(),
+        )
+    }
+
+    key(a).cmp(&key(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles_sam as sam;
+
+    use super::*;
+    use crate::Writer;
+
+    fn build_data() -> io::Result<Vec<u8>> {
+        let header = sam::Header::default();
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+        writer.write_record(&header, crate::Record::default())?;
+        writer.try_finish(&header)?;
+
+        Ok(writer.get_ref().clone())
+    }
+
+    #[test]
+    fn test_verify() -> io::Result<()> {
+        let data = build_data()?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+        let index = build_index(&mut reader)?;
+        assert_eq!(index.len(), 1);
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        assert!(verify(&mut reader, &index)?.is_empty());
+
+        let record = &index[0];
+        let stale_record = Record::new(
+            record.reference_sequence_id(),
+            record.alignment_start(),
+            record.alignment_span(),
+            record.offset() + 1,
+            record.landmark(),
+            record.slice_length(),
+        );
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        assert_eq!(
+            verify(&mut reader, std::slice::from_ref(&stale_record))?,
+            [
+                Error::MissingRecord(record.clone()),
+                Error::UnexpectedRecord(stale_record),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_diff() {
+        let a = Record::new(None, None, 0, 8, 13, 21);
+        let b = Record::new(None, None, 0, 34, 13, 21);
+
+        assert!(diff(vec![a.clone()], vec![a.clone()]).is_empty());
+        assert_eq!(
+            diff(vec![a.clone()], Vec::new()),
+            [Error::MissingRecord(a.clone())]
+        );
+        assert_eq!(
+            diff(vec![a.clone()], vec![b.clone(), a]),
+            [Error::UnexpectedRecord(b)]
+        );
+    }
+}
@@ -1,4 +1,10 @@
-use std::{cmp, collections::HashMap, fs::File, io, path::Path};
+use std::{
+    cmp,
+    collections::HashMap,
+    fs::File,
+    io::{self, Read, Seek},
+    path::Path,
+};
 
 use noodles_core::Position;
 
@@ -26,6 +32,14 @@ where
     reader.read_file_definition()?;
     reader.read_file_header()?;
 
+    build_index(&mut reader)
+}
+
+// Builds an index from the data containers that follow the current stream position.
+pub(crate) fn build_index<R>(reader: &mut Reader<R>) -> io::Result<crai::Index>
+where
+    R: Read + Seek,
+{
     let mut index = Vec::new();
     let mut container_position = reader.position()?;
 
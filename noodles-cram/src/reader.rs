@@ -10,10 +10,14 @@ mod records;
 
 pub use self::records::Records;
 
-use std::io::{self, Read, Seek, SeekFrom};
+use std::{
+    collections::HashMap,
+    io::{self, Read, Seek, SeekFrom},
+    slice,
+};
 
 use bytes::BytesMut;
-use noodles_core::Region;
+use noodles_core::{region::IntervalSet, Region};
 use noodles_fasta as fasta;
 use noodles_sam as sam;
 
@@ -301,23 +305,73 @@ where
         index: &'a crai::Index,
         region: &Region,
     ) -> io::Result<Query<'_, R>> {
-        let reference_sequence_id = header
-            .reference_sequences()
-            .get_index_of(region.name())
-            .ok_or_else(|| {
-                io::Error::new(
-                    io::ErrorKind::InvalidInput,
-                    "invalid reference sequence name",
-                )
-            })?;
+        self.query_many(
+            reference_sequence_repository,
+            header,
+            index,
+            slice::from_ref(region),
+        )
+    }
+
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// Each container is read at most once. Records are returned in file order, and a record that
+    /// intersects more than one region is only returned once.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_cram::{self as cram, crai};
+    /// use noodles_fasta as fasta;
+    ///
+    /// let mut reader = File::open("sample.cram").map(cram::Reader::new)?;
+    /// reader.read_file_definition()?;
+    ///
+    /// let repository = fasta::Repository::default();
+    /// let header = reader.read_file_header()?.parse()?;
+    /// let index = crai::read("sample.cram.crai")?;
+    /// let regions = ["sq0:8-13".parse()?, "sq0:21-34".parse()?];
+    /// let query = reader.query_many(&repository, &header, &index, &regions)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_many<'a>(
+        &'a mut self,
+        reference_sequence_repository: &'a fasta::Repository,
+        header: &'a sam::Header,
+        index: &'a crai::Index,
+        regions: &[Region],
+    ) -> io::Result<Query<'a, R>> {
+        let mut interval_sets: HashMap<usize, IntervalSet> = HashMap::new();
+
+        for region in regions {
+            let reference_sequence_id = header
+                .reference_sequences()
+                .get_index_of(region.name())
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "invalid reference sequence name",
+                    )
+                })?;
+
+            interval_sets
+                .entry(reference_sequence_id)
+                .or_default()
+                .insert(region.interval());
+        }
 
         Ok(Query::new(
             self,
             reference_sequence_repository,
             header,
             index,
-            reference_sequence_id,
-            region.interval(),
+            interval_sets,
         ))
     }
 }
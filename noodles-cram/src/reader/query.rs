@@ -1,9 +1,10 @@
 use std::{
+    collections::HashMap,
     io::{self, Read, Seek, SeekFrom},
     slice, vec,
 };
 
-use noodles_core::region::Interval;
+use noodles_core::{region::IntervalSet, Position};
 use noodles_fasta as fasta;
 use noodles_sam as sam;
 
@@ -12,7 +13,7 @@ use crate::{crai, Record};
 
 /// An iterator over records that intersect a given region.
 ///
-/// This is created by calling [`Reader::query`].
+/// This is created by calling [`Reader::query`] or [`Reader::query_many`].
 pub struct Query<'a, R>
 where
     R: Read + Seek,
@@ -23,9 +24,9 @@ where
     header: &'a sam::Header,
 
     index: slice::Iter<'a, crai::Record>,
+    last_offset: Option<u64>,
 
-    reference_sequence_id: usize,
-    interval: Interval,
+    interval_sets: HashMap<usize, IntervalSet>,
 
     records: vec::IntoIter<Record>,
 }
@@ -39,8 +40,7 @@ where
         reference_sequence_repository: &'a fasta::Repository,
         header: &'a sam::Header,
         index: &'a crai::Index,
-        reference_sequence_id: usize,
-        interval: Interval,
+        interval_sets: HashMap<usize, IntervalSet>,
     ) -> Self {
         Self {
             reader,
@@ -49,9 +49,9 @@ where
             header,
 
             index: index.iter(),
+            last_offset: None,
 
-            reference_sequence_id,
-            interval,
+            interval_sets,
 
             records: Vec::new().into_iter(),
         }
@@ -60,10 +60,18 @@ where
     fn read_next_container(&mut self) -> Option<io::Result<()>> {
         let index_record = self.index.next()?;
 
-        if index_record.reference_sequence_id() != Some(self.reference_sequence_id) {
+        if !index_record_intersects(index_record, &self.interval_sets) {
             return Some(Ok(()));
         }
 
+        // Index records of slices in the same container share an offset. Each container is
+        // only read once.
+        if self.last_offset == Some(index_record.offset()) {
+            return Some(Ok(()));
+        }
+
+        self.last_offset = Some(index_record.offset());
+
         if let Err(e) = self.reader.seek(SeekFrom::Start(index_record.offset())) {
             return Some(Err(e));
         }
@@ -118,10 +126,18 @@ where
         loop {
             match self.records.next() {
                 Some(r) => {
-                    if let (Some(start), Some(end)) = (r.alignment_start(), r.alignment_end()) {
-                        let alignment_interval = (start..=end).into();
-
-                        if self.interval.intersects(alignment_interval) {
+                    if let (Some(id), Some(start), Some(end)) = (
+                        r.reference_sequence_id(),
+                        r.alignment_start(),
+                        r.alignment_end(),
+                    ) {
+                        let is_in_any_interval = self
+                            .interval_sets
+                            .get(&id)
+                            .map(|interval_set| interval_set.intersects(start..=end))
+                            .unwrap_or(false);
+
+                        if is_in_any_interval {
                             return Some(Ok(r));
                         }
                     }
@@ -135,3 +151,64 @@ where
         }
     }
 }
+
+fn index_record_intersects(
+    index_record: &crai::Record,
+    interval_sets: &HashMap<usize, IntervalSet>,
+) -> bool {
+    let interval_set = match index_record
+        .reference_sequence_id()
+        .and_then(|id| interval_sets.get(&id))
+    {
+        Some(interval_set) => interval_set,
+        None => return false,
+    };
+
+    let start = match index_record.alignment_start() {
+        Some(start) => start,
+        None => return true,
+    };
+
+    let end = usize::from(start)
+        .checked_add(index_record.alignment_span())
+        .and_then(|n| n.checked_sub(1))
+        .and_then(Position::new);
+
+    match end {
+        Some(end) if end >= start => interval_set.intersects(start..=end),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_record_intersects() -> Result<(), noodles_core::position::TryFromIntError> {
+        use noodles_core::region::Interval;
+
+        fn build_interval_sets(id: usize, interval: Interval) -> HashMap<usize, IntervalSet> {
+            [(id, [interval].into_iter().collect())]
+                .into_iter()
+                .collect()
+        }
+
+        let index_record = crai::Record::new(Some(0), Position::new(8), 6, 0, 0, 0);
+
+        let interval_sets = build_interval_sets(0, (Position::try_from(13)?..).into());
+        assert!(index_record_intersects(&index_record, &interval_sets));
+
+        let interval_sets = build_interval_sets(0, (Position::try_from(14)?..).into());
+        assert!(!index_record_intersects(&index_record, &interval_sets));
+
+        let interval_sets = build_interval_sets(1, Interval::from(..));
+        assert!(!index_record_intersects(&index_record, &interval_sets));
+
+        let index_record = crai::Record::new(None, None, 0, 0, 0, 0);
+        let interval_sets = build_interval_sets(0, Interval::from(..));
+        assert!(!index_record_intersects(&index_record, &interval_sets));
+
+        Ok(())
+    }
+}
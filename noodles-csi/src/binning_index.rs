@@ -1,13 +1,16 @@
 //! Binning index utilities.
 
+pub mod merge;
+mod partition;
 mod reference_sequence_ext;
+pub mod verify;
 
 pub use reference_sequence_ext::ReferenceSequenceExt;
 
-use std::io;
+use std::{io, num::NonZeroUsize};
 
 use noodles_bgzf as bgzf;
-use noodles_core::region::Interval;
+use noodles_core::{region::Interval, Region};
 
 use super::index::reference_sequence::bin::Chunk;
 
@@ -16,6 +19,20 @@ pub trait BinningIndex {
     /// The returned output indexed reference sequence.
     type ReferenceSequence: ReferenceSequenceExt;
 
+    /// Returns the number of bits for the minimum interval.
+    ///
+    /// The default implementation returns 14, the fixed value used by BAI and tabix.
+    fn min_shift(&self) -> u8 {
+        14
+    }
+
+    /// Returns the depth of the binning index.
+    ///
+    /// The default implementation returns 5, the fixed value used by BAI and tabix.
+    fn depth(&self) -> u8 {
+        5
+    }
+
     /// Returns a list of indexed reference sequences.
     fn reference_sequences(&self) -> &[Self::ReferenceSequence];
 
@@ -37,6 +54,51 @@ pub trait BinningIndex {
             .rev()
             .find_map(|rs| rs.first_record_in_last_linear_bin_start_position())
     }
+
+    /// Partitions the genome into parts of roughly equal data volume.
+    ///
+    /// The data volume along each reference sequence is estimated from the sizes of the chunks in
+    /// its bins, scaled by the total size given by its metadata, if any. This favors where records
+    /// actually are rather than reference sequence lengths, e.g., for exome or targeted sequencing
+    /// data.
+    ///
+    /// `reference_sequence_names` are the names of the reference sequences in the index, in order.
+    /// Each part is a list of regions, as a part can span multiple reference sequences. Together,
+    /// the parts cover every reference sequence, and region boundaries are aligned to the smallest
+    /// bin size, i.e., no bin in the lowest level is split.
+    ///
+    /// A record that overlaps a boundary overlaps regions in two parts. To process each record
+    /// once, only keep records that start in a part's region.
+    ///
+    /// At most `count` parts are returned. Fewer parts are returned if there is not enough data to
+    /// divide.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use noodles_core::Region;
+    /// use noodles_csi::{self as csi, BinningIndex};
+    ///
+    /// let index = csi::Index::default();
+    /// let count = NonZeroUsize::try_from(4)?;
+    /// let parts = index.partition(["sq0"], count)?;
+    ///
+    /// assert_eq!(parts, [vec![Region::new("sq0", ..)]]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn partition<I, N>(
+        &self,
+        reference_sequence_names: I,
+        count: NonZeroUsize,
+    ) -> io::Result<Vec<Vec<Region>>>
+    where
+        I: IntoIterator<Item = N>,
+        N: AsRef<str>,
+    {
+        partition::partition(self, reference_sequence_names, count)
+    }
 }
 
 /// Merges a list of chunks into a list of non-overlapping chunks.
@@ -137,6 +199,39 @@ pub fn optimize_chunks(chunks: &[Chunk], min_offset: bgzf::VirtualPosition) -> V
     merged_chunks
 }
 
+/// Returns the 0-based start position of the bin with the given ID.
+///
+/// `min_shift` and `depth` are the binning index parameters. This returns `None` if the bin is not
+/// in the binning scheme, e.g., the metadata pseudo-bin, or if the start position overflows.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_csi::binning_index::bin_start;
+///
+/// assert_eq!(bin_start(0, 14, 5), Some(0));
+/// assert_eq!(bin_start(2, 14, 5), Some(1 << 26));
+/// assert_eq!(bin_start(4682, 14, 5), Some(1 << 14));
+/// assert_eq!(bin_start(37450, 14, 5), None);
+/// ```
+pub fn bin_start(id: usize, min_shift: u8, depth: u8) -> Option<usize> {
+    let mut level_start = 0usize;
+
+    for level in 0..=depth {
+        let level_size = 1usize.checked_shl(3 * u32::from(level))?;
+        let level_end = level_start.checked_add(level_size)?;
+
+        if id < level_end {
+            let shift = u32::from(min_shift) + 3 * u32::from(depth - level);
+            return (id - level_start).checked_mul(1usize.checked_shl(shift)?);
+        }
+
+        level_start = level_end;
+    }
+
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,151 @@
+//! Binning index merging utilities.
+//!
+//! These are shared by the index merging functions of the binning index formats, i.e.,
+//! [`crate::index::merge`], `tabix::index::merge`, and `bam::bai::index::merge`. Each merges the
+//! indexes of BGZF streams that were concatenated (see [`bgzf::concat`]) by shifting their virtual
+//! positions by the compressed position at which each stream starts.
+//!
+//! # Reference sequence matching
+//!
+//! CSI and BAI indexes do not store reference sequence names. Their reference sequences are
+//! matched by position in the index, i.e., the concatenated streams are expected to share the
+//! same reference sequence dictionary. Tabix indexes store reference sequence names, so their
+//! reference sequences are matched by name, and the merged names are in order of first
+//! appearance.
+
+use std::{cmp, io};
+
+use noodles_bgzf as bgzf;
+
+use crate::index::reference_sequence::{bin::Chunk, Metadata};
+
+/// Shifts a virtual position by the compressed position of the start of its stream.
+///
+/// This returns an error if the shifted virtual position overflows.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bgzf as bgzf;
+/// use noodles_csi::binning_index::merge::shift;
+///
+/// let position = bgzf::VirtualPosition::try_from((5, 3))?;
+/// let expected = bgzf::VirtualPosition::try_from((13, 3))?;
+/// assert_eq!(shift(position, 8)?, expected);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn shift(position: bgzf::VirtualPosition, offset: u64) -> io::Result<bgzf::VirtualPosition> {
+    position
+        .checked_shift(offset)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "virtual position overflow"))
+}
+
+/// Shifts the start and end positions of chunks by the compressed position of the start of their
+/// stream.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bgzf as bgzf;
+/// use noodles_csi::{binning_index::merge::shift_chunks, index::reference_sequence::bin::Chunk};
+///
+/// let chunks = [Chunk::new(
+///     bgzf::VirtualPosition::try_from((0, 5))?,
+///     bgzf::VirtualPosition::try_from((5, 0))?,
+/// )];
+///
+/// let expected = [Chunk::new(
+///     bgzf::VirtualPosition::try_from((8, 5))?,
+///     bgzf::VirtualPosition::try_from((13, 0))?,
+/// )];
+///
+/// assert_eq!(shift_chunks(&chunks, 8)?, expected);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn shift_chunks(chunks: &[Chunk], offset: u64) -> io::Result<Vec<Chunk>> {
+    chunks
+        .iter()
+        .map(|chunk| {
+            let start = shift(chunk.start(), offset)?;
+            let end = shift(chunk.end(), offset)?;
+            Ok(Chunk::new(start, end))
+        })
+        .collect()
+}
+
+/// Merges reference sequence metadata into previously merged metadata, if any.
+///
+/// The metadata positions are first shifted by the compressed position of the start of their
+/// stream. The merged positions span both inputs, and the record counts are summed.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bgzf as bgzf;
+/// use noodles_csi::{binning_index::merge::merge_metadata, index::reference_sequence::Metadata};
+///
+/// let metadata = Metadata::new(
+///     bgzf::VirtualPosition::try_from((0, 5))?,
+///     bgzf::VirtualPosition::try_from((8, 0))?,
+///     2,
+///     1,
+/// );
+///
+/// let merged = merge_metadata(None, &metadata, 0)?;
+/// let merged = merge_metadata(Some(merged), &metadata, 8)?;
+///
+/// assert_eq!(
+///     merged,
+///     Metadata::new(
+///         bgzf::VirtualPosition::try_from((0, 5))?,
+///         bgzf::VirtualPosition::try_from((16, 0))?,
+///         4,
+///         2,
+///     )
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn merge_metadata(
+    merged: Option<Metadata>,
+    metadata: &Metadata,
+    offset: u64,
+) -> io::Result<Metadata> {
+    let start_position = shift(metadata.start_position(), offset)?;
+    let end_position = shift(metadata.end_position(), offset)?;
+
+    Ok(match merged {
+        Some(m) => Metadata::new(
+            cmp::min(m.start_position(), start_position),
+            cmp::max(m.end_position(), end_position),
+            m.mapped_record_count() + metadata.mapped_record_count(),
+            m.unmapped_record_count() + metadata.unmapped_record_count(),
+        ),
+        None => Metadata::new(
+            start_position,
+            end_position,
+            metadata.mapped_record_count(),
+            metadata.unmapped_record_count(),
+        ),
+    })
+}
+
+/// Sums optional record counts.
+///
+/// Missing counts are skipped. This returns `None` if all counts are missing.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_csi::binning_index::merge::sum_counts;
+/// assert_eq!(sum_counts([None, None]), None);
+/// assert_eq!(sum_counts([Some(2), None, Some(3)]), Some(5));
+/// ```
+pub fn sum_counts<I>(counts: I) -> Option<u64>
+where
+    I: IntoIterator<Item = Option<u64>>,
+{
+    counts.into_iter().fold(None, |sum, n| match (sum, n) {
+        (Some(a), Some(b)) => Some(a + b),
+        (a, b) => a.or(b),
+    })
+}
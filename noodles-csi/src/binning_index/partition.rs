@@ -0,0 +1,354 @@
+use std::{collections::BTreeMap, io, num::NonZeroUsize};
+
+use noodles_core::{Position, Region};
+
+use super::{bin_start, BinningIndex, ReferenceSequenceExt};
+use crate::index::reference_sequence::parent_id;
+
+// A position in the genome: a reference sequence index and a 0-based position.
+type Cut = (usize, usize);
+
+// An interval of a reference sequence, [start, end), with a constant data density.
+struct Segment {
+    start: usize,
+    end: usize,
+    density: f64,
+}
+
+pub(super) fn partition<B, I, N>(
+    index: &B,
+    reference_sequence_names: I,
+    count: NonZeroUsize,
+) -> io::Result<Vec<Vec<Region>>>
+where
+    B: BinningIndex + ?Sized,
+    I: IntoIterator<Item = N>,
+    N: AsRef<str>,
+{
+    let names: Vec<_> = reference_sequence_names
+        .into_iter()
+        .map(|name| name.as_ref().to_string())
+        .collect();
+
+    if names.len() < index.reference_sequences().len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "missing reference sequence names",
+        ));
+    }
+
+    let min_shift = index.min_shift();
+    let depth = index.depth();
+
+    let profiles: Vec<_> = index
+        .reference_sequences()
+        .iter()
+        .map(|reference_sequence| build_profile(reference_sequence, min_shift, depth))
+        .collect();
+
+    let cuts = find_cuts(&profiles, count.get(), 1 << min_shift);
+
+    build_partitions(&names, &cuts)
+}
+
+// Estimates the data density along a reference sequence.
+//
+// The volume of a bin is the sum of the virtual position distances of its chunks and is spread
+// evenly over the interval the bin covers. If the reference sequence has metadata, the volumes
+// are scaled to total the distance between its start and end positions.
+fn build_profile<R>(reference_sequence: &R, min_shift: u8, depth: u8) -> Vec<Segment>
+where
+    R: ReferenceSequenceExt,
+{
+    let mut deltas: BTreeMap<usize, f64> = BTreeMap::new();
+    let mut total_volume = 0.0;
+
+    for (id, chunks) in reference_sequence.bin_chunks() {
+        let volume: u64 = chunks
+            .iter()
+            .map(|chunk| u64::from(chunk.end()).saturating_sub(u64::from(chunk.start())))
+            .sum();
+
+        let (start, end) = match bin_interval(id, min_shift, depth) {
+            Some(interval) => interval,
+            None => continue,
+        };
+
+        if volume == 0 {
+            continue;
+        }
+
+        let density = volume as f64 / (end - start) as f64;
+        *deltas.entry(start).or_default() += density;
+        *deltas.entry(end).or_default() -= density;
+        total_volume += volume as f64;
+    }
+
+    let scale = match reference_sequence.metadata() {
+        Some(metadata) if total_volume > 0.0 => {
+            let start = u64::from(metadata.start_position());
+            let end = u64::from(metadata.end_position());
+            let volume = end.saturating_sub(start) as f64;
+
+            if volume > 0.0 {
+                volume / total_volume
+            } else {
+                1.0
+            }
+        }
+        _ => 1.0,
+    };
+
+    let mut segments = Vec::new();
+    let mut density = 0.0;
+    let mut prev_position = None;
+
+    for (position, delta) in deltas {
+        if let Some(start) = prev_position {
+            if density > 0.0 {
+                segments.push(Segment {
+                    start,
+                    end: position,
+                    density: density * scale,
+                });
+            }
+        }
+
+        density += delta;
+        prev_position = Some(position);
+    }
+
+    segments
+}
+
+// Returns the 0-based interval, [start, end), covered by a bin.
+fn bin_interval(id: usize, min_shift: u8, depth: u8) -> Option<(usize, usize)> {
+    let start = bin_start(id, min_shift, depth)?;
+
+    let mut level = 0;
+    let mut i = id;
+
+    while let Some(parent_id) = parent_id(i) {
+        level += 1;
+        i = parent_id;
+    }
+
+    let shift = u32::from(min_shift) + 3 * u32::from(depth - level);
+    let len = 1usize.checked_shl(shift)?;
+    let end = start.checked_add(len)?;
+
+    Some((start, end))
+}
+
+// Finds the positions that divide the total volume into `count` parts.
+//
+// Positions are aligned to the smallest bin size (`window_size`). Cuts that would create an empty
+// partition are dropped.
+fn find_cuts(profiles: &[Vec<Segment>], count: usize, window_size: usize) -> Vec<Cut> {
+    let total_volume: f64 = profiles
+        .iter()
+        .flatten()
+        .map(|segment| segment.density * (segment.end - segment.start) as f64)
+        .sum();
+
+    let mut cuts: Vec<Cut> = Vec::new();
+
+    if total_volume <= 0.0 {
+        return cuts;
+    }
+
+    let mut targets = (1..count).map(|k| total_volume * k as f64 / count as f64);
+    let mut target = targets.next();
+    let mut volume = 0.0;
+
+    for (i, segments) in profiles.iter().enumerate() {
+        for segment in segments {
+            let segment_volume = segment.density * (segment.end - segment.start) as f64;
+
+            while let Some(t) = target {
+                if t >= volume + segment_volume {
+                    break;
+                }
+
+                let offset = ((t - volume) / segment.density) as usize;
+                let position = round_to_multiple(segment.start + offset, window_size);
+                let cut = (i, position.clamp(segment.start, segment.end));
+
+                if cuts.last().map(|&last| cut > last).unwrap_or(cut > (0, 0)) {
+                    cuts.push(cut);
+                }
+
+                target = targets.next();
+            }
+
+            volume += segment_volume;
+        }
+    }
+
+    cuts
+}
+
+fn round_to_multiple(n: usize, m: usize) -> usize {
+    (n + m / 2) / m * m
+}
+
+fn build_partitions(names: &[String], cuts: &[Cut]) -> io::Result<Vec<Vec<Region>>> {
+    let mut partitions = Vec::with_capacity(cuts.len() + 1);
+
+    let mut start = (0, 0);
+
+    for &end in cuts.iter().chain([(names.len(), 0)].iter()) {
+        let mut regions = Vec::new();
+
+        for (i, name) in names.iter().enumerate().take(end.0 + 1).skip(start.0) {
+            let region_start = if i == start.0 { start.1 } else { 0 };
+            let region_end = if i == end.0 { Some(end.1) } else { None };
+
+            if let Some(region) = build_region(name, region_start, region_end)? {
+                regions.push(region);
+            }
+        }
+
+        partitions.push(regions);
+        start = end;
+    }
+
+    Ok(partitions)
+}
+
+// Builds a region from a 0-based, half-open interval. An unbounded end is given as `None`.
+fn build_region(name: &str, start: usize, end: Option<usize>) -> io::Result<Option<Region>> {
+    let to_position = |n: usize| {
+        Position::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    };
+
+    match end {
+        Some(end) if end <= start => Ok(None),
+        Some(end) => {
+            let start = to_position(start + 1)?;
+            let end = to_position(end)?;
+            Ok(Some(Region::new(name, start..=end)))
+        }
+        None if start == 0 => Ok(Some(Region::new(name, ..))),
+        None => {
+            let start = to_position(start + 1)?;
+            Ok(Some(Region::new(name, start..)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_bgzf as bgzf;
+
+        use crate::{
+            index::{
+                reference_sequence::{bin::Chunk, Bin},
+                ReferenceSequence,
+            },
+            Index,
+        };
+
+        fn build_bin(id: usize, start: u64, end: u64) -> Bin {
+            let start = bgzf::VirtualPosition::try_from((start, 0)).unwrap();
+            let end = bgzf::VirtualPosition::try_from((end, 0)).unwrap();
+            Bin::new(id, start, vec![Chunk::new(start, end)])
+        }
+
+        // sq0 has 3 windows of data, and sq1 has 1 window of data.
+        let index = Index::builder()
+            .set_reference_sequences(vec![
+                ReferenceSequence::new(
+                    vec![
+                        build_bin(4681, 0, 100),
+                        build_bin(4682, 100, 200),
+                        build_bin(4683, 200, 300),
+                    ],
+                    None,
+                ),
+                ReferenceSequence::new(vec![build_bin(4681, 300, 400)], None),
+            ])
+            .build();
+
+        let count = NonZeroUsize::try_from(2)?;
+        let parts = index.partition(["sq0", "sq1"], count)?;
+
+        assert_eq!(
+            parts,
+            [
+                vec![Region::new(
+                    "sq0",
+                    Position::try_from(1)?..=Position::try_from(2 << 14)?
+                )],
+                vec![
+                    Region::new("sq0", Position::try_from((2 << 14) + 1)?..),
+                    Region::new("sq1", ..),
+                ],
+            ]
+        );
+
+        assert!(matches!(
+            index.partition(["sq0"], count),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bin_interval() {
+        assert_eq!(bin_interval(0, 14, 5), Some((0, 1 << 29)));
+        assert_eq!(bin_interval(1, 14, 5), Some((0, 1 << 26)));
+        assert_eq!(bin_interval(4681, 14, 5), Some((0, 1 << 14)));
+        assert_eq!(bin_interval(4682, 14, 5), Some((1 << 14, 2 << 14)));
+        assert_eq!(bin_interval(37449, 14, 5), None);
+    }
+
+    #[test]
+    fn test_find_cuts() {
+        let profiles = vec![
+            vec![Segment {
+                start: 0,
+                end: 4 << 14,
+                density: 1.0,
+            }],
+            Vec::new(),
+            vec![Segment {
+                start: 0,
+                end: 4 << 14,
+                density: 1.0,
+            }],
+        ];
+
+        assert_eq!(
+            find_cuts(&profiles, 4, 1 << 14),
+            [(0, 2 << 14), (2, 0), (2, 2 << 14)]
+        );
+
+        assert!(find_cuts(&[Vec::new()], 4, 1 << 14).is_empty());
+    }
+
+    #[test]
+    fn test_build_partitions() -> Result<(), Box<dyn std::error::Error>> {
+        let names = vec![String::from("sq0"), String::from("sq1")];
+        let partitions = build_partitions(&names, &[(0, 1 << 14), (1, 0)])?;
+
+        let start = Position::try_from(1)?;
+        let end = Position::try_from(1 << 14)?;
+
+        assert_eq!(
+            partitions,
+            [
+                vec![Region::new("sq0", start..=end)],
+                vec![Region::new("sq0", Position::try_from((1 << 14) + 1)?..)],
+                vec![Region::new("sq1", ..)],
+            ]
+        );
+
+        Ok(())
+    }
+}
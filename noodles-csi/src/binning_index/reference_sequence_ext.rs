@@ -1,12 +1,27 @@
 use noodles_bgzf as bgzf;
 
-use crate::index::reference_sequence::Metadata;
+use crate::index::reference_sequence::{bin::Chunk, Metadata};
 
 /// An extension that adds methods to binning index reference sequence types.
 pub trait ReferenceSequenceExt {
+    /// Returns the ID and chunks of each bin.
+    ///
+    /// This does not include the metadata pseudo-bin.
+    fn bin_chunks(&self) -> Box<dyn Iterator<Item = (usize, &[Chunk])> + '_>;
+
     /// Returns the optional metadata for the reference sequence.
     fn metadata(&self) -> Option<&Metadata>;
 
     /// Returns the start position of the first record in the last linear bin.
     fn first_record_in_last_linear_bin_start_position(&self) -> Option<bgzf::VirtualPosition>;
+
+    /// Returns the linear index as a list of window indices and minimum offsets.
+    ///
+    /// Each window is `2^min_shift` bases. An offset is the start position of the first record
+    /// that overlaps the window. Unset entries are not included, and the list is sorted by window.
+    ///
+    /// The default implementation returns an empty list, i.e., no linear index.
+    fn linear_index(&self, _min_shift: u8, _depth: u8) -> Vec<(usize, bgzf::VirtualPosition)> {
+        Vec::new()
+    }
 }
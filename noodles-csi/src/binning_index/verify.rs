@@ -0,0 +1,758 @@
+//! Binning index verification.
+//!
+//! A [`Verifier`] cross-checks a binning index against a full scan of its associated data file.
+//! This catches stale or mismatched indexes, which otherwise silently return wrong query results.
+
+use std::{collections::HashMap, error, fmt, io};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+
+use super::{BinningIndex, ReferenceSequenceExt};
+use crate::index::reference_sequence::{bin::Chunk, parent_id, reg2bin};
+
+/// An index verification error.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// A record has a reference sequence ID that is not in the index.
+    InvalidReferenceSequenceId {
+        /// The start position of the record.
+        position: bgzf::VirtualPosition,
+        /// The reference sequence ID of the record.
+        reference_sequence_id: usize,
+    },
+    /// A chunk start or end position is not at a record boundary.
+    InvalidChunkBoundary {
+        /// The reference sequence ID of the bin.
+        reference_sequence_id: usize,
+        /// The bin ID.
+        bin_id: usize,
+        /// The chunk start or end position.
+        position: bgzf::VirtualPosition,
+    },
+    /// A record is not in any chunk of its bin or the bin's ancestors.
+    RecordNotInBin {
+        /// The start position of the record.
+        position: bgzf::VirtualPosition,
+        /// The reference sequence ID of the record.
+        reference_sequence_id: usize,
+        /// The bin ID computed from the record's interval.
+        bin_id: usize,
+    },
+    /// A linear index offset is less than the offset of a preceding window.
+    NonMonotonicLinearIndex {
+        /// The reference sequence ID.
+        reference_sequence_id: usize,
+        /// The window index.
+        window: usize,
+    },
+    /// A record that overlaps a window starts before the window's linear index offset.
+    InvalidLinearIndexOffset {
+        /// The start position of the record.
+        position: bgzf::VirtualPosition,
+        /// The reference sequence ID of the record.
+        reference_sequence_id: usize,
+        /// The window index.
+        window: usize,
+    },
+    /// The metadata mapped record count does not match the data.
+    MappedRecordCountMismatch {
+        /// The reference sequence ID.
+        reference_sequence_id: usize,
+        /// The count in the index.
+        expected: u64,
+        /// The count in the data.
+        actual: u64,
+    },
+    /// The metadata unmapped record count does not match the data.
+    UnmappedRecordCountMismatch {
+        /// The reference sequence ID.
+        reference_sequence_id: usize,
+        /// The count in the index.
+        expected: u64,
+        /// The count in the data.
+        actual: u64,
+    },
+    /// The unplaced, unmapped record count does not match the data.
+    UnplacedUnmappedRecordCountMismatch {
+        /// The count in the index.
+        expected: u64,
+        /// The count in the data.
+        actual: u64,
+    },
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidReferenceSequenceId {
+                position,
+                reference_sequence_id,
+            } => write!(
+                f,
+                "record at {:?} has an invalid reference sequence ID: {}",
+                position, reference_sequence_id
+            ),
+            Self::InvalidChunkBoundary {
+                reference_sequence_id,
+                bin_id,
+                position,
+            } => write!(
+                f,
+                "chunk position {:?} in bin {} of reference sequence {} is not at a record boundary",
+                position, bin_id, reference_sequence_id
+            ),
+            Self::RecordNotInBin {
+                position,
+                reference_sequence_id,
+                bin_id,
+            } => write!(
+                f,
+                "record at {:?} is not in bin {} of reference sequence {}",
+                position, bin_id, reference_sequence_id
+            ),
+            Self::NonMonotonicLinearIndex {
+                reference_sequence_id,
+                window,
+            } => write!(
+                f,
+                "linear index of reference sequence {} decreases at window {}",
+                reference_sequence_id, window
+            ),
+            Self::InvalidLinearIndexOffset {
+                position,
+                reference_sequence_id,
+                window,
+            } => write!(
+                f,
+                "record at {:?} starts before the linear index offset of window {} of reference sequence {}",
+                position, window, reference_sequence_id
+            ),
+            Self::MappedRecordCountMismatch {
+                reference_sequence_id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "mapped record count mismatch in reference sequence {}: expected {}, got {}",
+                reference_sequence_id, expected, actual
+            ),
+            Self::UnmappedRecordCountMismatch {
+                reference_sequence_id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "unmapped record count mismatch in reference sequence {}: expected {}, got {}",
+                reference_sequence_id, expected, actual
+            ),
+            Self::UnplacedUnmappedRecordCountMismatch { expected, actual } => write!(
+                f,
+                "unplaced, unmapped record count mismatch: expected {}, got {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+#[derive(Default)]
+struct RecordCounts {
+    mapped: u64,
+    unmapped: u64,
+}
+
+struct ChunkBound {
+    position: bgzf::VirtualPosition,
+    reference_sequence_id: usize,
+    bin_id: usize,
+}
+
+/// A binning index verifier.
+///
+/// Every record in the data file is added, in file order, with its position in the file and
+/// alignment context (see [`Verifier::add_record`]). The following are checked:
+///
+///   * every chunk start and end position is at a record boundary;
+///   * every record is in a chunk of the bin computed from its interval, or of one of the bin's
+///     ancestors, as bins may be merged into their parents;
+///   * the linear index is nondecreasing, and no record that overlaps a window starts before the
+///     window's offset; and
+///   * the metadata mapped and unmapped record counts and the unplaced, unmapped record count
+///     match the data.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bgzf as bgzf;
+/// use noodles_core::Position;
+/// use noodles_csi::{self as csi, binning_index::verify::Verifier, index::reference_sequence::bin::Chunk};
+///
+/// let chunk = Chunk::new(bgzf::VirtualPosition::from(144), bgzf::VirtualPosition::from(233));
+/// let alignment_context = Some((0, Position::try_from(8)?, Position::try_from(13)?, true));
+///
+/// let mut indexer = csi::Index::indexer();
+/// indexer.add_record(alignment_context, chunk)?;
+/// let index = indexer.build(1);
+///
+/// let mut verifier = Verifier::new(&index);
+/// verifier.add_record(alignment_context, chunk)?;
+/// assert!(verifier.finish().is_empty());
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct Verifier<'a, I> {
+    index: &'a I,
+    chunk_bounds: Vec<ChunkBound>,
+    chunk_bound_index: usize,
+    bins: Vec<HashMap<usize, Vec<Chunk>>>,
+    linear_indices: Vec<Vec<(usize, bgzf::VirtualPosition)>>,
+    record_counts: Vec<RecordCounts>,
+    unplaced_unmapped_record_count: u64,
+    last_position: Option<bgzf::VirtualPosition>,
+    errors: Vec<Error>,
+}
+
+impl<'a, I> Verifier<'a, I>
+where
+    I: BinningIndex,
+{
+    /// Creates a binning index verifier.
+    ///
+    /// Problems found in the index alone, i.e., a decreasing linear index, are recorded
+    /// immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{self as csi, binning_index::verify::Verifier};
+    /// let index = csi::Index::default();
+    /// let verifier = Verifier::new(&index);
+    /// ```
+    pub fn new(index: &'a I) -> Self {
+        let reference_sequences = index.reference_sequences();
+
+        let mut chunk_bounds = Vec::new();
+        let mut bins = Vec::with_capacity(reference_sequences.len());
+        let mut linear_indices = Vec::with_capacity(reference_sequences.len());
+        let mut errors = Vec::new();
+
+        for (reference_sequence_id, reference_sequence) in reference_sequences.iter().enumerate() {
+            let mut reference_sequence_bins = HashMap::new();
+
+            for (bin_id, chunks) in reference_sequence.bin_chunks() {
+                for chunk in chunks {
+                    for position in [chunk.start(), chunk.end()] {
+                        chunk_bounds.push(ChunkBound {
+                            position,
+                            reference_sequence_id,
+                            bin_id,
+                        });
+                    }
+                }
+
+                let mut chunks = chunks.to_vec();
+                chunks.sort_unstable_by_key(|chunk| chunk.start());
+                reference_sequence_bins.insert(bin_id, chunks);
+            }
+
+            bins.push(reference_sequence_bins);
+
+            let linear_index = reference_sequence.linear_index(index.min_shift(), index.depth());
+            verify_linear_index_order(reference_sequence_id, &linear_index, &mut errors);
+            linear_indices.push(linear_index);
+        }
+
+        chunk_bounds.sort_unstable_by_key(|bound| bound.position);
+
+        let record_counts = reference_sequences
+            .iter()
+            .map(|_| RecordCounts::default())
+            .collect();
+
+        Self {
+            index,
+            chunk_bounds,
+            chunk_bound_index: 0,
+            bins,
+            linear_indices,
+            record_counts,
+            unplaced_unmapped_record_count: 0,
+            last_position: None,
+            errors,
+        }
+    }
+
+    /// Adds a record.
+    ///
+    /// The chunk is the start and end position of the record in the data file. The alignment
+    /// context is the reference sequence ID, start position, end position, and whether the record
+    /// is mapped. If it is `None`, the record is counted as unplaced and unmapped.
+    ///
+    /// Records must be added in file order. This returns an error if the chunk starts before the
+    /// end of the previous record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::{self as csi, binning_index::verify::Verifier, index::reference_sequence::bin::Chunk};
+    ///
+    /// let index = csi::Index::default();
+    /// let mut verifier = Verifier::new(&index);
+    ///
+    /// let chunk = Chunk::new(bgzf::VirtualPosition::from(144), bgzf::VirtualPosition::from(233));
+    /// verifier.add_record(None, chunk)?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn add_record(
+        &mut self,
+        alignment_context: Option<(usize, Position, Position, bool)>,
+        chunk: Chunk,
+    ) -> io::Result<()> {
+        if let Some(last_position) = self.last_position {
+            if chunk.start() < last_position {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "records are not in file order",
+                ));
+            }
+
+            // Records may be separated by data that is not indexed, e.g., comment lines.
+            self.verify_chunk_bounds_before(last_position);
+        }
+
+        self.verify_chunk_bounds_before(chunk.start());
+        self.last_position = Some(chunk.end());
+
+        let (reference_sequence_id, start, end, is_mapped) = match alignment_context {
+            Some(context) => context,
+            None => {
+                self.unplaced_unmapped_record_count += 1;
+                return Ok(());
+            }
+        };
+
+        let record_counts = match self.record_counts.get_mut(reference_sequence_id) {
+            Some(record_counts) => record_counts,
+            None => {
+                self.errors.push(Error::InvalidReferenceSequenceId {
+                    position: chunk.start(),
+                    reference_sequence_id,
+                });
+
+                return Ok(());
+            }
+        };
+
+        if is_mapped {
+            record_counts.mapped += 1;
+        } else {
+            record_counts.unmapped += 1;
+        }
+
+        self.verify_record_bin(reference_sequence_id, start, end, chunk);
+        self.verify_record_linear_index(reference_sequence_id, start, end, chunk);
+
+        Ok(())
+    }
+
+    /// Finishes verification and returns the list of errors.
+    ///
+    /// An empty list means the index is consistent with the data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{self as csi, binning_index::verify::Verifier};
+    /// let index = csi::Index::default();
+    /// let verifier = Verifier::new(&index);
+    /// assert!(verifier.finish().is_empty());
+    /// ```
+    pub fn finish(mut self) -> Vec<Error> {
+        if let Some(last_position) = self.last_position {
+            self.verify_chunk_bounds_before(last_position);
+        }
+
+        for bound in &self.chunk_bounds[self.chunk_bound_index..] {
+            self.errors.push(Error::InvalidChunkBoundary {
+                reference_sequence_id: bound.reference_sequence_id,
+                bin_id: bound.bin_id,
+                position: bound.position,
+            });
+        }
+
+        for (reference_sequence_id, (reference_sequence, record_counts)) in self
+            .index
+            .reference_sequences()
+            .iter()
+            .zip(&self.record_counts)
+            .enumerate()
+        {
+            if let Some(metadata) = reference_sequence.metadata() {
+                if metadata.mapped_record_count() != record_counts.mapped {
+                    self.errors.push(Error::MappedRecordCountMismatch {
+                        reference_sequence_id,
+                        expected: metadata.mapped_record_count(),
+                        actual: record_counts.mapped,
+                    });
+                }
+
+                if metadata.unmapped_record_count() != record_counts.unmapped {
+                    self.errors.push(Error::UnmappedRecordCountMismatch {
+                        reference_sequence_id,
+                        expected: metadata.unmapped_record_count(),
+                        actual: record_counts.unmapped,
+                    });
+                }
+            }
+        }
+
+        if let Some(n) = self.index.unplaced_unmapped_record_count() {
+            if n != self.unplaced_unmapped_record_count {
+                self.errors
+                    .push(Error::UnplacedUnmappedRecordCountMismatch {
+                        expected: n,
+                        actual: self.unplaced_unmapped_record_count,
+                    });
+            }
+        }
+
+        self.errors
+    }
+
+    // Reports chunk bounds before the given record boundary and skips the ones at it.
+    fn verify_chunk_bounds_before(&mut self, position: bgzf::VirtualPosition) {
+        while let Some(bound) = self.chunk_bounds.get(self.chunk_bound_index) {
+            if bound.position >= position {
+                break;
+            }
+
+            self.errors.push(Error::InvalidChunkBoundary {
+                reference_sequence_id: bound.reference_sequence_id,
+                bin_id: bound.bin_id,
+                position: bound.position,
+            });
+
+            self.chunk_bound_index += 1;
+        }
+
+        self.skip_chunk_bounds_at(position);
+    }
+
+    fn skip_chunk_bounds_at(&mut self, position: bgzf::VirtualPosition) {
+        while let Some(bound) = self.chunk_bounds.get(self.chunk_bound_index) {
+            if bound.position != position {
+                break;
+            }
+
+            self.chunk_bound_index += 1;
+        }
+    }
+
+    fn verify_record_bin(
+        &mut self,
+        reference_sequence_id: usize,
+        start: Position,
+        end: Position,
+        chunk: Chunk,
+    ) {
+        let bins = &self.bins[reference_sequence_id];
+        let bin_id = reg2bin(start, end, self.index.min_shift(), self.index.depth());
+
+        let mut id = Some(bin_id);
+
+        while let Some(i) = id {
+            if let Some(chunks) = bins.get(&i) {
+                if contains(chunks, chunk) {
+                    return;
+                }
+            }
+
+            id = parent_id(i);
+        }
+
+        self.errors.push(Error::RecordNotInBin {
+            position: chunk.start(),
+            reference_sequence_id,
+            bin_id,
+        });
+    }
+
+    fn verify_record_linear_index(
+        &mut self,
+        reference_sequence_id: usize,
+        start: Position,
+        end: Position,
+        chunk: Chunk,
+    ) {
+        let min_shift = self.index.min_shift();
+        let start_window = (usize::from(start) - 1) >> min_shift;
+        let end_window = (usize::from(end) - 1) >> min_shift;
+
+        let linear_index = &self.linear_indices[reference_sequence_id];
+        let i = linear_index.partition_point(|(window, _)| *window < start_window);
+
+        for &(window, offset) in &linear_index[i..] {
+            if window > end_window {
+                break;
+            }
+
+            if offset > chunk.start() {
+                self.errors.push(Error::InvalidLinearIndexOffset {
+                    position: chunk.start(),
+                    reference_sequence_id,
+                    window,
+                });
+
+                break;
+            }
+        }
+    }
+}
+
+fn verify_linear_index_order(
+    reference_sequence_id: usize,
+    linear_index: &[(usize, bgzf::VirtualPosition)],
+    errors: &mut Vec<Error>,
+) {
+    let mut max_offset = bgzf::VirtualPosition::default();
+    let mut window_max_offset = bgzf::VirtualPosition::default();
+    let mut current_window = None;
+
+    for &(window, offset) in linear_index {
+        if current_window != Some(window) {
+            max_offset = max_offset.max(window_max_offset);
+            current_window = Some(window);
+        }
+
+        if offset < max_offset {
+            errors.push(Error::NonMonotonicLinearIndex {
+                reference_sequence_id,
+                window,
+            });
+        }
+
+        window_max_offset = window_max_offset.max(offset);
+    }
+}
+
+fn contains(chunks: &[Chunk], chunk: Chunk) -> bool {
+    let i = chunks.partition_point(|c| c.start() <= chunk.start());
+
+    chunks[..i].iter().rev().any(|c| chunk.end() <= c.end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        index::{reference_sequence::Bin, ReferenceSequence},
+        Index,
+    };
+
+    type Record = (Option<(usize, Position, Position, bool)>, Chunk);
+
+    fn chunk(start: u64, end: u64) -> Chunk {
+        Chunk::new(
+            bgzf::VirtualPosition::from(start),
+            bgzf::VirtualPosition::from(end),
+        )
+    }
+
+    fn build_records() -> Result<Vec<Record>, Box<dyn std::error::Error>> {
+        Ok(vec![
+            (
+                Some((0, Position::try_from(8)?, Position::try_from(13)?, true)),
+                chunk(55, 89),
+            ),
+            (
+                Some((0, Position::try_from(21)?, Position::try_from(34)?, false)),
+                chunk(89, 144),
+            ),
+            (
+                Some((
+                    1,
+                    Position::try_from(16385)?,
+                    Position::try_from(16390)?,
+                    true,
+                )),
+                chunk(144, 233),
+            ),
+            (None, chunk(233, 377)),
+        ])
+    }
+
+    fn verify(index: &Index, records: &[Record]) -> io::Result<Vec<Error>> {
+        let mut verifier = Verifier::new(index);
+
+        for &(alignment_context, chunk) in records {
+            verifier.add_record(alignment_context, chunk)?;
+        }
+
+        Ok(verifier.finish())
+    }
+
+    #[test]
+    fn test_verify() -> Result<(), Box<dyn std::error::Error>> {
+        let records = build_records()?;
+
+        let mut indexer = Index::indexer();
+
+        for &(alignment_context, chunk) in &records {
+            indexer.add_record(alignment_context, chunk)?;
+        }
+
+        let index = indexer.build(2);
+
+        assert!(verify(&index, &records)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_with_unindexed_data() -> Result<(), Box<dyn std::error::Error>> {
+        let mut records = build_records()?;
+
+        // Simulate unindexed data, e.g., a comment line, between records.
+        records[1].1 = chunk(100, 144);
+
+        let mut indexer = Index::indexer();
+
+        for &(alignment_context, chunk) in &records {
+            indexer.add_record(alignment_context, chunk)?;
+        }
+
+        let index = indexer.build(2);
+
+        assert!(verify(&index, &records)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_with_stale_index() -> Result<(), Box<dyn std::error::Error>> {
+        let mut records = build_records()?;
+
+        let mut indexer = Index::indexer();
+
+        for &(alignment_context, chunk) in &records {
+            indexer.add_record(alignment_context, chunk)?;
+        }
+
+        let index = indexer.build(2);
+
+        // Simulate a record being inserted after the index was built.
+        records.insert(
+            2,
+            (
+                Some((
+                    0,
+                    Position::try_from(20000)?,
+                    Position::try_from(20005)?,
+                    true,
+                )),
+                chunk(144, 150),
+            ),
+        );
+        records[3].1 = chunk(150, 233);
+
+        let errors = verify(&index, &records)?;
+
+        assert_eq!(
+            errors,
+            [
+                Error::RecordNotInBin {
+                    position: bgzf::VirtualPosition::from(144),
+                    reference_sequence_id: 0,
+                    bin_id: 4682,
+                },
+                Error::MappedRecordCountMismatch {
+                    reference_sequence_id: 0,
+                    expected: 1,
+                    actual: 2,
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_with_invalid_chunk_boundary() -> Result<(), Box<dyn std::error::Error>> {
+        let records = build_records()?;
+
+        let index = Index::builder()
+            .set_reference_sequences(vec![
+                ReferenceSequence::new(
+                    vec![Bin::new(
+                        4681,
+                        bgzf::VirtualPosition::from(55),
+                        vec![chunk(55, 100)],
+                    )],
+                    None,
+                ),
+                ReferenceSequence::new(Vec::new(), None),
+            ])
+            .build();
+
+        let errors = verify(&index, &records)?;
+
+        assert_eq!(
+            errors,
+            [
+                Error::RecordNotInBin {
+                    position: bgzf::VirtualPosition::from(89),
+                    reference_sequence_id: 0,
+                    bin_id: 4681,
+                },
+                Error::InvalidChunkBoundary {
+                    reference_sequence_id: 0,
+                    bin_id: 4681,
+                    position: bgzf::VirtualPosition::from(100),
+                },
+                Error::RecordNotInBin {
+                    position: bgzf::VirtualPosition::from(144),
+                    reference_sequence_id: 1,
+                    bin_id: 4682,
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_linear_index_order() {
+        let mut errors = Vec::new();
+
+        let linear_index = [
+            (0, bgzf::VirtualPosition::from(8)),
+            (0, bgzf::VirtualPosition::from(13)),
+            (1, bgzf::VirtualPosition::from(13)),
+            (2, bgzf::VirtualPosition::from(5)),
+        ];
+
+        verify_linear_index_order(0, &linear_index, &mut errors);
+
+        assert_eq!(
+            errors,
+            [Error::NonMonotonicLinearIndex {
+                reference_sequence_id: 0,
+                window: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_add_record_with_unordered_records() {
+        let index = Index::default();
+        let mut verifier = Verifier::new(&index);
+
+        assert!(verifier.add_record(None, chunk(144, 233)).is_ok());
+        assert!(matches!(
+            verifier.add_record(None, chunk(55, 89)),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+}
@@ -1,15 +1,19 @@
 //! Coordinate-sorted index and fields.
 
 mod builder;
+mod indexer;
+mod merge;
 pub mod reference_sequence;
 
-pub use self::{builder::Builder, reference_sequence::ReferenceSequence};
+pub use self::{
+    builder::Builder, indexer::Indexer, merge::merge, reference_sequence::ReferenceSequence,
+};
 
 use std::io;
 
 use noodles_core::{region::Interval, Position};
 
-use super::{index::reference_sequence::bin::Chunk, BinningIndex};
+use super::{binning_index::optimize_chunks, index::reference_sequence::bin::Chunk, BinningIndex};
 
 /// A coordinate-sorted index (CSI).
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -34,6 +38,18 @@ impl Index {
         Builder::default()
     }
 
+    /// Returns an indexer to create an index from records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let indexer = csi::Index::indexer();
+    /// ```
+    pub fn indexer() -> Indexer {
+        Indexer::default()
+    }
+
     /// Returns the number of bits for the minimum interval.
     ///
     /// # Examples
@@ -94,6 +110,14 @@ impl Index {
 impl BinningIndex for Index {
     type ReferenceSequence = ReferenceSequence;
 
+    fn min_shift(&self) -> u8 {
+        self.min_shift
+    }
+
+    fn depth(&self) -> u8 {
+        self.depth
+    }
+
     /// Returns a list of indexed reference sequences.
     ///
     /// # Examples
@@ -134,6 +158,8 @@ impl BinningIndex for Index {
                 )
             })?;
 
+        let interval = interval.into();
+
         let query_bins = reference_sequence
             .query(self.min_shift(), self.depth(), interval)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
@@ -144,7 +170,11 @@ impl BinningIndex for Index {
             .copied()
             .collect();
 
-        Ok(chunks)
+        let (start, _) = resolve_interval(self.min_shift(), self.depth(), interval)?;
+        let min_offset = reference_sequence.min_offset(self.min_shift(), self.depth(), start);
+        let merged_chunks = optimize_chunks(&chunks, min_offset);
+
+        Ok(merged_chunks)
     }
 }
 
@@ -182,3 +212,38 @@ where
         Ok((start, end))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf as bgzf;
+
+    use super::*;
+    use crate::index::reference_sequence::Bin;
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        let vp = bgzf::VirtualPosition::from;
+
+        let index = Index::builder()
+            .set_min_shift(4)
+            .set_depth(2)
+            .set_reference_sequences(vec![ReferenceSequence::new(
+                vec![
+                    Bin::new(0, vp(0), vec![Chunk::new(vp(2), vp(3))]),
+                    Bin::new(
+                        1,
+                        vp(5),
+                        vec![Chunk::new(vp(5), vp(8)), Chunk::new(vp(7), vp(13))],
+                    ),
+                ],
+                None,
+            )])
+            .build();
+
+        let start = Position::try_from(8)?;
+        let chunks = index.query(0, start..=start)?;
+        assert_eq!(chunks, [Chunk::new(vp(5), vp(13))]);
+
+        Ok(())
+    }
+}
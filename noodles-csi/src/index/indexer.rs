@@ -0,0 +1,233 @@
+use std::{io, mem};
+
+use noodles_core::Position;
+
+use super::{
+    reference_sequence::{self, bin::Chunk},
+    Index, ReferenceSequence,
+};
+
+const DEFAULT_MIN_SHIFT: u8 = 14;
+const DEFAULT_DEPTH: u8 = 5;
+
+/// A coordinate-sorted index (CSI) indexer.
+///
+/// Records must be added in coordinate-sorted order.
+#[derive(Debug)]
+pub struct Indexer {
+    min_shift: u8,
+    depth: u8,
+    current_reference_sequence_id: Option<usize>,
+    reference_sequence_builder: Option<reference_sequence::Builder>,
+    reference_sequences: Vec<ReferenceSequence>,
+    unplaced_unmapped_record_count: u64,
+}
+
+impl Indexer {
+    /// Sets a min shift.
+    ///
+    /// This is the number of bits for the minimum interval. The default is 14.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let mut indexer = csi::Index::indexer();
+    /// indexer.set_min_shift(13);
+    /// assert_eq!(indexer.build(0).min_shift(), 13);
+    /// ```
+    pub fn set_min_shift(&mut self, min_shift: u8) {
+        self.min_shift = min_shift;
+    }
+
+    /// Sets a max depth.
+    ///
+    /// The default is 5.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let mut indexer = csi::Index::indexer();
+    /// indexer.set_depth(6);
+    /// assert_eq!(indexer.build(0).depth(), 6);
+    /// ```
+    pub fn set_depth(&mut self, depth: u8) {
+        self.depth = depth;
+    }
+
+    /// Adds a record.
+    ///
+    /// The alignment context is the reference sequence ID, start position, end position, and
+    /// whether the record is mapped. If it is `None`, the record is counted as unplaced and
+    /// unmapped.
+    ///
+    /// The min shift and depth must be set before any records are added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_core::Position;
+    /// use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+    ///
+    /// let mut indexer = csi::Index::indexer();
+    ///
+    /// let start = Position::try_from(8)?;
+    /// let end = Position::try_from(13)?;
+    ///
+    /// indexer.add_record(Some((0, start, end, true)), Chunk::new(
+    ///     bgzf::VirtualPosition::from(144),
+    ///     bgzf::VirtualPosition::from(233),
+    /// ))?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn add_record(
+        &mut self,
+        alignment_context: Option<(usize, Position, Position, bool)>,
+        chunk: Chunk,
+    ) -> io::Result<()> {
+        let (reference_sequence_id, start, end, is_mapped) = match alignment_context {
+            Some(context) => context,
+            None => {
+                self.unplaced_unmapped_record_count += 1;
+                return Ok(());
+            }
+        };
+
+        match self.current_reference_sequence_id {
+            Some(id) if reference_sequence_id < id => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "records are not coordinate-sorted",
+                ));
+            }
+            Some(id) if reference_sequence_id == id => {}
+            _ => self.add_reference_sequences_until(reference_sequence_id),
+        }
+
+        let (min_shift, depth) = (self.min_shift, self.depth);
+
+        self.reference_sequence_builder
+            .get_or_insert_with(|| reference_sequence::Builder::new(min_shift, depth))
+            .add_record(start, end, is_mapped, chunk)
+    }
+
+    fn add_reference_sequences_until(&mut self, reference_sequence_id: usize) {
+        if let Some(builder) = self.reference_sequence_builder.take() {
+            self.reference_sequences.push(builder.build());
+        }
+
+        while self.reference_sequences.len() < reference_sequence_id {
+            self.reference_sequences
+                .push(ReferenceSequence::new(Vec::new(), None));
+        }
+
+        self.current_reference_sequence_id = Some(reference_sequence_id);
+    }
+
+    /// Builds a coordinate-sorted index (CSI).
+    ///
+    /// Reference sequences with no records are given empty entries, up to
+    /// `reference_sequence_count`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{self as csi, BinningIndex};
+    /// let index = csi::Index::indexer().build(2);
+    /// assert_eq!(index.reference_sequences().len(), 2);
+    /// ```
+    pub fn build(mut self, reference_sequence_count: usize) -> Index {
+        if let Some(builder) = self.reference_sequence_builder.take() {
+            self.reference_sequences.push(builder.build());
+        }
+
+        let mut reference_sequences = mem::take(&mut self.reference_sequences);
+
+        if reference_sequences.len() < reference_sequence_count {
+            reference_sequences.resize(
+                reference_sequence_count,
+                ReferenceSequence::new(Vec::new(), None),
+            );
+        }
+
+        Index::builder()
+            .set_min_shift(self.min_shift)
+            .set_depth(self.depth)
+            .set_reference_sequences(reference_sequences)
+            .set_unplaced_unmapped_record_count(self.unplaced_unmapped_record_count)
+            .build()
+    }
+}
+
+impl Default for Indexer {
+    fn default() -> Self {
+        Self {
+            min_shift: DEFAULT_MIN_SHIFT,
+            depth: DEFAULT_DEPTH,
+            current_reference_sequence_id: None,
+            reference_sequence_builder: None,
+            reference_sequences: Vec::new(),
+            unplaced_unmapped_record_count: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf as bgzf;
+
+    use super::*;
+    use crate::BinningIndex;
+
+    #[test]
+    fn test_build() -> Result<(), Box<dyn std::error::Error>> {
+        let mut indexer = Indexer::default();
+
+        indexer.add_record(
+            Some((1, Position::try_from(8)?, Position::try_from(13)?, true)),
+            Chunk::new(
+                bgzf::VirtualPosition::from(144),
+                bgzf::VirtualPosition::from(233),
+            ),
+        )?;
+
+        indexer.add_record(
+            None,
+            Chunk::new(
+                bgzf::VirtualPosition::from(233),
+                bgzf::VirtualPosition::from(377),
+            ),
+        )?;
+
+        let index = indexer.build(3);
+
+        assert_eq!(index.reference_sequences().len(), 3);
+        assert!(index.reference_sequences()[0].bins().is_empty());
+        assert_eq!(index.reference_sequences()[1].bins().len(), 1);
+        assert!(index.reference_sequences()[2].bins().is_empty());
+        assert_eq!(index.unplaced_unmapped_record_count(), Some(1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_record_with_unsorted_records() -> Result<(), Box<dyn std::error::Error>> {
+        let mut indexer = Indexer::default();
+
+        let chunk = Chunk::new(
+            bgzf::VirtualPosition::from(144),
+            bgzf::VirtualPosition::from(233),
+        );
+
+        indexer.add_record(Some((1, Position::MIN, Position::MIN, true)), chunk)?;
+
+        assert!(matches!(
+            indexer.add_record(Some((0, Position::MIN, Position::MIN, true)), chunk),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+use std::{cmp, collections::BTreeMap, io};
+
+use noodles_bgzf as bgzf;
+
+use super::{
+    reference_sequence::{bin::Chunk, Bin},
+    Index, ReferenceSequence,
+};
+use crate::{
+    binning_index::{
+        merge::{merge_metadata, shift, shift_chunks, sum_counts},
+        ReferenceSequenceExt,
+    },
+    BinningIndex,
+};
+
+/// Merges indexes of BGZF streams that were concatenated.
+///
+/// `offsets` is the compressed position at which each indexed stream starts in the concatenated
+/// stream, e.g., as returned by [`bgzf::concat`]. Chunk, bin linear offset, and metadata virtual
+/// positions are shifted by their stream's offset, and record counts are summed.
+///
+/// The indexes must be given in the order their streams were concatenated and have the same
+/// minimum interval size, depth, and auxiliary data. Reference sequences are matched by their
+/// position in the index (see [`crate::binning_index::merge`]).
+///
+/// # Examples
+///
+/// ```
+/// use noodles_csi as csi;
+///
+/// let indexes = [csi::Index::default(), csi::Index::default()];
+/// let index = csi::index::merge(&indexes, &[0, 8])?;
+///
+/// assert_eq!(index, csi::Index::default());
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn merge(indexes: &[Index], offsets: &[u64]) -> io::Result<Index> {
+    if indexes.len() != offsets.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "index and offset counts mismatch",
+        ));
+    }
+
+    let first = match indexes.first() {
+        Some(index) => index,
+        None => return Ok(Index::default()),
+    };
+
+    for index in &indexes[1..] {
+        if index.min_shift() != first.min_shift()
+            || index.depth() != first.depth()
+            || index.aux() != first.aux()
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "index parameters mismatch",
+            ));
+        }
+    }
+
+    let reference_sequence_count = indexes
+        .iter()
+        .map(|index| index.reference_sequences().len())
+        .max()
+        .unwrap_or_default();
+
+    let mut reference_sequences = Vec::with_capacity(reference_sequence_count);
+
+    for i in 0..reference_sequence_count {
+        let shards = indexes.iter().zip(offsets).filter_map(|(index, &offset)| {
+            index.reference_sequences().get(i).map(|rs| (rs, offset))
+        });
+
+        reference_sequences.push(merge_reference_sequences(shards)?);
+    }
+
+    let mut builder = Index::builder()
+        .set_min_shift(first.min_shift())
+        .set_depth(first.depth())
+        .set_aux(first.aux().to_vec())
+        .set_reference_sequences(reference_sequences);
+
+    if let Some(n) = sum_counts(
+        indexes
+            .iter()
+            .map(|index| index.unplaced_unmapped_record_count()),
+    ) {
+        builder = builder.set_unplaced_unmapped_record_count(n);
+    }
+
+    Ok(builder.build())
+}
+
+fn merge_reference_sequences<'a, I>(shards: I) -> io::Result<ReferenceSequence>
+where
+    I: Iterator<Item = (&'a ReferenceSequence, u64)>,
+{
+    let mut bins: BTreeMap<usize, (bgzf::VirtualPosition, Vec<Chunk>)> = BTreeMap::new();
+    let mut metadata = None;
+
+    for (reference_sequence, offset) in shards {
+        for bin in reference_sequence.bins() {
+            let loffset = shift(bin.loffset(), offset)?;
+            let chunks = shift_chunks(bin.chunks(), offset)?;
+
+            let (merged_loffset, merged_chunks) = bins
+                .entry(bin.id())
+                .or_insert_with(|| (loffset, Vec::new()));
+
+            *merged_loffset = cmp::min(*merged_loffset, loffset);
+            merged_chunks.extend(chunks);
+        }
+
+        if let Some(m) = reference_sequence.metadata() {
+            metadata = Some(merge_metadata(metadata, m, offset)?);
+        }
+    }
+
+    let bins = bins
+        .into_iter()
+        .map(|(id, (loffset, chunks))| Bin::new(id, loffset, chunks))
+        .collect();
+
+    Ok(ReferenceSequence::new(bins, metadata))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::reference_sequence::Metadata;
+
+    fn build_index(
+        bin_id: usize,
+        start: u64,
+        end: u64,
+        reference_sequence_id: usize,
+        unplaced_unmapped_record_count: u64,
+    ) -> Index {
+        let start = bgzf::VirtualPosition::from(start);
+        let end = bgzf::VirtualPosition::from(end);
+
+        let mut reference_sequences =
+            vec![ReferenceSequence::new(Vec::new(), None); reference_sequence_id + 1];
+
+        reference_sequences[reference_sequence_id] = ReferenceSequence::new(
+            vec![Bin::new(bin_id, start, vec![Chunk::new(start, end)])],
+            Some(Metadata::new(start, end, 2, 1)),
+        );
+
+        Index::builder()
+            .set_reference_sequences(reference_sequences)
+            .set_unplaced_unmapped_record_count(unplaced_unmapped_record_count)
+            .build()
+    }
+
+    #[test]
+    fn test_merge() -> io::Result<()> {
+        let vp = |compressed, uncompressed| {
+            bgzf::VirtualPosition::try_from((compressed, uncompressed)).unwrap()
+        };
+
+        let indexes = [
+            build_index(4681, u64::from(vp(0, 5)), u64::from(vp(8, 0)), 0, 3),
+            build_index(4681, u64::from(vp(0, 5)), u64::from(vp(5, 0)), 0, 5),
+            build_index(4681, u64::from(vp(0, 5)), u64::from(vp(3, 0)), 1, 0),
+        ];
+
+        let actual = merge(&indexes, &[0, 8, 13])?;
+
+        let expected = Index::builder()
+            .set_reference_sequences(vec![
+                ReferenceSequence::new(
+                    vec![Bin::new(
+                        4681,
+                        vp(0, 5),
+                        vec![
+                            Chunk::new(vp(0, 5), vp(8, 0)),
+                            Chunk::new(vp(8, 5), vp(13, 0)),
+                        ],
+                    )],
+                    Some(Metadata::new(vp(0, 5), vp(13, 0), 4, 2)),
+                ),
+                ReferenceSequence::new(
+                    vec![Bin::new(
+                        4681,
+                        vp(13, 5),
+                        vec![Chunk::new(vp(13, 5), vp(16, 0))],
+                    )],
+                    Some(Metadata::new(vp(13, 5), vp(16, 0), 2, 1)),
+                ),
+            ])
+            .set_unplaced_unmapped_record_count(8)
+            .build();
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_with_mismatched_parameters() {
+        let indexes = [Index::default(), Index::builder().set_depth(6).build()];
+        assert!(matches!(
+            merge(&indexes, &[0, 8]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            merge(&[Index::default()], &[]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+}
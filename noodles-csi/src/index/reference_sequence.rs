@@ -1,8 +1,11 @@
 //! Coordinate-sorted index (CSI) reference sequence and fields.
 
 pub mod bin;
+mod builder;
 mod metadata;
 
+pub(crate) use self::builder::Builder;
+
 pub use self::{bin::Bin, metadata::Metadata};
 
 use std::{io, num::NonZeroUsize};
@@ -12,7 +15,7 @@ use noodles_bgzf as bgzf;
 use noodles_core::{region::Interval, Position};
 
 use super::resolve_interval;
-use crate::binning_index::ReferenceSequenceExt;
+use crate::binning_index::{bin_start, ReferenceSequenceExt};
 
 /// A CSI reference sequence.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -138,6 +141,10 @@ impl ReferenceSequence {
 }
 
 impl ReferenceSequenceExt for ReferenceSequence {
+    fn bin_chunks(&self) -> Box<dyn Iterator<Item = (usize, &[bin::Chunk])> + '_> {
+        Box::new(self.bins().iter().map(|bin| (bin.id(), bin.chunks())))
+    }
+
     /// Returns the optional metadata for the reference sequence.
     ///
     /// # Examples
@@ -190,6 +197,50 @@ impl ReferenceSequenceExt for ReferenceSequence {
     fn first_record_in_last_linear_bin_start_position(&self) -> Option<bgzf::VirtualPosition> {
         self.bins().iter().map(|bin| bin.loffset()).max()
     }
+
+    /// Returns the linear index as a list of window indices and minimum offsets.
+    ///
+    /// A CSI has no separate linear index. Instead, each bin has the offset of the window at its
+    /// start (`loffset`), so a window can have more than one entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::{
+    ///     binning_index::ReferenceSequenceExt,
+    ///     index::{reference_sequence::Bin, ReferenceSequence},
+    /// };
+    ///
+    /// let bins = vec![
+    ///     Bin::new(0, bgzf::VirtualPosition::from(8), Vec::new()),
+    ///     Bin::new(10, bgzf::VirtualPosition::from(13), Vec::new()),
+    /// ];
+    /// let reference_sequence = ReferenceSequence::new(bins, None);
+    ///
+    /// assert_eq!(
+    ///     reference_sequence.linear_index(4, 2),
+    ///     [
+    ///         (0, bgzf::VirtualPosition::from(8)),
+    ///         (1, bgzf::VirtualPosition::from(13)),
+    ///     ]
+    /// );
+    /// ```
+    fn linear_index(&self, min_shift: u8, depth: u8) -> Vec<(usize, bgzf::VirtualPosition)> {
+        let mut linear_index: Vec<_> = self
+            .bins()
+            .iter()
+            .filter(|bin| bin.loffset() != bgzf::VirtualPosition::default())
+            .filter_map(|bin| {
+                let window = bin_start(bin.id(), min_shift, depth)? >> min_shift;
+                Some((window, bin.loffset()))
+            })
+            .collect();
+
+        linear_index.sort_unstable();
+
+        linear_index
+    }
 }
 
 const M: usize = match NonZeroUsize::new(8) {
@@ -198,12 +249,12 @@ const M: usize = match NonZeroUsize::new(8) {
 };
 
 // parent of i = floor((i - 1) / M)
-fn parent_id(id: usize) -> Option<usize> {
+pub(crate) fn parent_id(id: usize) -> Option<usize> {
     (id > 0).then(|| (id - 1) / M)
 }
 
 // `CSIv1.pdf` (2020-07-21)
-fn reg2bin(start: Position, end: Position, min_shift: u8, depth: u8) -> usize {
+pub(crate) fn reg2bin(start: Position, end: Position, min_shift: u8, depth: u8) -> usize {
     // [beg, end), 0-based
     let beg = usize::from(start) - 1;
     let end = usize::from(end);
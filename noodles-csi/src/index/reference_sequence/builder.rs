@@ -0,0 +1,210 @@
+use std::{cmp, collections::HashMap, io};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+
+use super::{bin::Chunk, reg2bin, Bin, Metadata, ReferenceSequence};
+use crate::binning_index::bin_start;
+
+#[derive(Debug)]
+pub(crate) struct Builder {
+    min_shift: u8,
+    depth: u8,
+    bins: HashMap<usize, Vec<Chunk>>,
+    intervals: Vec<Option<bgzf::VirtualPosition>>,
+    start_position: bgzf::VirtualPosition,
+    end_position: bgzf::VirtualPosition,
+    mapped_record_count: u64,
+    unmapped_record_count: u64,
+}
+
+impl Builder {
+    pub fn new(min_shift: u8, depth: u8) -> Self {
+        Self {
+            min_shift,
+            depth,
+            bins: HashMap::new(),
+            intervals: Vec::new(),
+            start_position: bgzf::VirtualPosition::max(),
+            end_position: bgzf::VirtualPosition::default(),
+            mapped_record_count: 0,
+            unmapped_record_count: 0,
+        }
+    }
+
+    pub fn add_record(
+        &mut self,
+        start: Position,
+        end: Position,
+        is_mapped: bool,
+        chunk: Chunk,
+    ) -> io::Result<()> {
+        let max_position = ReferenceSequence::max_position(self.min_shift, self.depth)?;
+
+        if end > max_position {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "record end position ({}) exceeds the max position of the binning index ({})",
+                    end, max_position
+                ),
+            ));
+        }
+
+        self.update_bins(start, end, chunk);
+        self.update_linear_index(start, end, chunk);
+        self.update_metadata(is_mapped, chunk);
+
+        Ok(())
+    }
+
+    pub fn build(self) -> ReferenceSequence {
+        if self.bins.is_empty() {
+            return ReferenceSequence::new(Vec::new(), None);
+        }
+
+        let mut bins: Vec<_> = self
+            .bins
+            .into_iter()
+            .map(|(id, chunks)| {
+                let loffset = bin_start(id, self.min_shift, self.depth)
+                    .and_then(|start| self.intervals.get(start >> self.min_shift))
+                    .copied()
+                    .flatten()
+                    .unwrap_or_default();
+
+                Bin::new(id, loffset, chunks)
+            })
+            .collect();
+
+        bins.sort_unstable_by_key(|bin| bin.id());
+
+        let metadata = Metadata::new(
+            self.start_position,
+            self.end_position,
+            self.mapped_record_count,
+            self.unmapped_record_count,
+        );
+
+        ReferenceSequence::new(bins, Some(metadata))
+    }
+
+    // See § 5.1.2 "Reducing small chunks" in SAMv1.pdf (2020-07-19).
+    fn update_bins(&mut self, start: Position, end: Position, chunk: Chunk) {
+        let id = reg2bin(start, end, self.min_shift, self.depth);
+        let chunks = self.bins.entry(id).or_default();
+
+        if let Some(last_chunk) = chunks.last_mut() {
+            if chunk.start() <= last_chunk.end() {
+                *last_chunk = Chunk::new(last_chunk.start(), chunk.end());
+                return;
+            }
+        }
+
+        chunks.push(chunk);
+    }
+
+    fn update_linear_index(&mut self, start: Position, end: Position, chunk: Chunk) {
+        let start_window = (usize::from(start) - 1) >> self.min_shift;
+        let end_window = (usize::from(end) - 1) >> self.min_shift;
+
+        if end_window >= self.intervals.len() {
+            self.intervals.resize(end_window + 1, None);
+        }
+
+        for interval in &mut self.intervals[start_window..=end_window] {
+            interval.get_or_insert(chunk.start());
+        }
+    }
+
+    fn update_metadata(&mut self, is_mapped: bool, chunk: Chunk) {
+        if is_mapped {
+            self.mapped_record_count += 1;
+        } else {
+            self.unmapped_record_count += 1;
+        }
+
+        self.start_position = cmp::min(self.start_position, chunk.start());
+        self.end_position = cmp::max(self.end_position, chunk.end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build() -> Result<(), Box<dyn std::error::Error>> {
+        let mut builder = Builder::new(4, 2);
+
+        builder.add_record(
+            Position::try_from(2)?,
+            Position::try_from(5)?,
+            true,
+            Chunk::new(
+                bgzf::VirtualPosition::from(55),
+                bgzf::VirtualPosition::from(89),
+            ),
+        )?;
+
+        builder.add_record(
+            Position::try_from(6)?,
+            Position::try_from(21)?,
+            false,
+            Chunk::new(
+                bgzf::VirtualPosition::from(89),
+                bgzf::VirtualPosition::from(144),
+            ),
+        )?;
+
+        let actual = builder.build();
+
+        let expected = ReferenceSequence::new(
+            vec![
+                Bin::new(
+                    1,
+                    bgzf::VirtualPosition::from(55),
+                    vec![Chunk::new(
+                        bgzf::VirtualPosition::from(89),
+                        bgzf::VirtualPosition::from(144),
+                    )],
+                ),
+                Bin::new(
+                    9,
+                    bgzf::VirtualPosition::from(55),
+                    vec![Chunk::new(
+                        bgzf::VirtualPosition::from(55),
+                        bgzf::VirtualPosition::from(89),
+                    )],
+                ),
+            ],
+            Some(Metadata::new(
+                bgzf::VirtualPosition::from(55),
+                bgzf::VirtualPosition::from(144),
+                1,
+                1,
+            )),
+        );
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_record_with_out_of_range_end() -> Result<(), Box<dyn std::error::Error>> {
+        let mut builder = Builder::new(4, 2);
+
+        let chunk = Chunk::new(
+            bgzf::VirtualPosition::from(55),
+            bgzf::VirtualPosition::from(89),
+        );
+
+        assert!(matches!(
+            builder.add_record(Position::MIN, Position::try_from(1024)?, true, chunk),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
@@ -2,6 +2,8 @@ mod field;
 
 use std::{error, fmt, str::FromStr};
 
+use noodles_core::sequence_dictionary::Sequence;
+
 use self::field::Field;
 
 const FIELD_DELIMITER: char = '\t';
@@ -62,6 +64,12 @@ impl Record {
     }
 }
 
+impl From<&Record> for Sequence {
+    fn from(record: &Record) -> Self {
+        Self::new(record.name(), usize::try_from(record.len()).ok())
+    }
+}
+
 /// An error returned when a raw FASTA index record fails to parse.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ParseError {
@@ -129,6 +137,12 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_from_record_for_sequence() {
+        let record = Record::new(String::from("sq0"), 13, 5, 80, 81);
+        assert_eq!(Sequence::from(&record), Sequence::new("sq0", Some(13)));
+    }
+
     #[test]
     fn test_from_str() {
         assert_eq!(
@@ -1,5 +1,6 @@
 //! Alignment record and fields.
 
+pub mod pileup;
 pub mod record;
 
 pub use self::record::Record;
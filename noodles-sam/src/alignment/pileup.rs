@@ -0,0 +1,572 @@
+//! Alignment pileup.
+
+mod builder;
+pub mod column;
+mod cursor;
+
+pub use self::{builder::Builder, column::Column};
+
+use std::{
+    collections::{hash_map, HashMap},
+    io, iter,
+};
+
+use noodles_core::Position;
+use noodles_fasta as fasta;
+
+use self::{
+    column::{entry::Kind as EntryKind, Entry},
+    cursor::Cursor,
+};
+use super::Record;
+use crate::{
+    record::{quality_scores::Score, sequence::Base, Flags, MappingQuality},
+    Header,
+};
+
+/// An alignment pileup.
+///
+/// A pileup is an iterator over reference positions covered by coordinate-sorted alignment
+/// records. Each position is yielded as a [`Column`] of the reads covering it, with their query
+/// positions, bases, quality scores, and alignment states.
+///
+/// Only positions covered by at least one included record are yielded. Records without a
+/// reference sequence ID, an alignment start, or CIGAR operations that consume the reference are
+/// skipped. An error is returned if the records are not coordinate-sorted.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::Position;
+/// use noodles_sam::{
+///     self as sam,
+///     alignment::{pileup::Pileup, Record},
+///     header::record::value::{map::ReferenceSequence, Map},
+///     record::Flags,
+/// };
+///
+/// let header = sam::Header::builder()
+///     .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+///     .build();
+///
+/// let records = [
+///     Record::builder()
+///         .set_flags(Flags::empty())
+///         .set_reference_sequence_id(0)
+///         .set_alignment_start(Position::try_from(1)?)
+///         .set_cigar("2M".parse()?)
+///         .set_sequence("AC".parse()?)
+///         .build(),
+///     Record::builder()
+///         .set_flags(Flags::empty())
+///         .set_reference_sequence_id(0)
+///         .set_alignment_start(Position::try_from(2)?)
+///         .set_cigar("2M".parse()?)
+///         .set_sequence("CG".parse()?)
+///         .build(),
+/// ];
+///
+/// let pileup = Pileup::new(&header, records.into_iter().map(Ok));
+///
+/// let depths = pileup
+///     .map(|result| result.map(|column| (usize::from(column.position()), column.entries().len())))
+///     .collect::<io::Result<Vec<_>>>()?;
+///
+/// assert_eq!(depths, [(1, 1), (2, 2), (3, 1)]);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct Pileup<'h, I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    header: &'h Header,
+    records: iter::Fuse<I>,
+    flags_filter: Flags,
+    min_mapping_quality: Option<MappingQuality>,
+    min_base_quality: Option<Score>,
+    max_depth: usize,
+    remove_overlapping_mates: bool,
+    reference_sequence_repository: Option<fasta::Repository>,
+    reference_sequence: Option<(usize, fasta::record::Sequence)>,
+    last_key: Option<(usize, Position)>,
+    pending: Option<Cursor>,
+    active: Vec<Cursor>,
+    position: Option<(usize, Position)>,
+}
+
+impl<'h, I> Pileup<'h, I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    /// Creates an alignment pileup with the default options.
+    ///
+    /// See [`Builder`] for the defaults.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam::{self as sam, alignment::{pileup::Pileup, Record}};
+    ///
+    /// let header = sam::Header::default();
+    /// let records = std::iter::empty::<io::Result<Record>>();
+    ///
+    /// let mut pileup = Pileup::new(&header, records);
+    /// assert!(pileup.next().is_none());
+    /// ```
+    pub fn new(header: &'h Header, records: I) -> Self {
+        Builder::default().build(header, records)
+    }
+
+    fn is_included(&self, record: &Record) -> bool {
+        if record.flags().intersects(self.flags_filter) {
+            return false;
+        }
+
+        match self.min_mapping_quality {
+            Some(min_mapping_quality) => record
+                .mapping_quality()
+                .map(|mapping_quality| mapping_quality >= min_mapping_quality)
+                .unwrap_or(false),
+            None => true,
+        }
+    }
+
+    fn fill_pending(&mut self) -> io::Result<()> {
+        while self.pending.is_none() {
+            let record = match self.records.next().transpose()? {
+                Some(record) => record,
+                None => return Ok(()),
+            };
+
+            let (reference_sequence_id, alignment_start) =
+                match (record.reference_sequence_id(), record.alignment_start()) {
+                    (Some(id), Some(start)) => (id, start),
+                    _ => continue,
+                };
+
+            let key = (reference_sequence_id, alignment_start);
+
+            if matches!(self.last_key, Some(last_key) if key < last_key) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "records are not coordinate-sorted",
+                ));
+            }
+
+            self.last_key = Some(key);
+
+            if reference_sequence_id >= self.header.reference_sequences().len() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid reference sequence ID",
+                ));
+            }
+
+            if self.is_included(&record) {
+                self.pending = Cursor::new(record, reference_sequence_id, alignment_start);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn reference_base(
+        &mut self,
+        reference_sequence_id: usize,
+        position: Position,
+    ) -> io::Result<Option<Base>> {
+        let repository = match &self.reference_sequence_repository {
+            Some(repository) => repository,
+            None => return Ok(None),
+        };
+
+        if !matches!(&self.reference_sequence, Some((id, _)) if *id == reference_sequence_id) {
+            let name = self
+                .header
+                .reference_sequences()
+                .get_index(reference_sequence_id)
+                .map(|(name, _)| name)
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "invalid reference sequence ID")
+                })?;
+
+            let sequence = repository.get(name).transpose()?.ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("missing reference sequence: {}", name),
+                )
+            })?;
+
+            self.reference_sequence = Some((reference_sequence_id, sequence));
+        }
+
+        self.reference_sequence
+            .as_ref()
+            .and_then(|(_, sequence)| sequence.get(position))
+            .map(|&n| Base::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))
+            .transpose()
+    }
+}
+
+impl<'h, I> Iterator for Pileup<'h, I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    type Item = io::Result<Column>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Err(e) = self.fill_pending() {
+            return Some(Err(e));
+        }
+
+        if self.active.is_empty() {
+            let cursor = self.pending.as_ref()?;
+            self.position = Some((cursor.reference_sequence_id(), cursor.start()));
+        }
+
+        let (reference_sequence_id, position) = self.position?;
+
+        loop {
+            match self.pending.take() {
+                Some(cursor)
+                    if cursor.reference_sequence_id() == reference_sequence_id
+                        && cursor.start() == position =>
+                {
+                    if self.active.len() < self.max_depth {
+                        self.active.push(cursor);
+                    }
+
+                    if let Err(e) = self.fill_pending() {
+                        return Some(Err(e));
+                    }
+                }
+                pending => {
+                    self.pending = pending;
+                    break;
+                }
+            }
+        }
+
+        let reference_base = match self.reference_base(reference_sequence_id, position) {
+            Ok(base) => base,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let mut entries: Vec<_> = self.active.iter().map(|cursor| cursor.entry()).collect();
+
+        if self.remove_overlapping_mates {
+            remove_overlapping_mates(&mut entries);
+        }
+
+        if let Some(min_base_quality) = self.min_base_quality {
+            entries.retain(|entry| {
+                entry
+                    .quality_score()
+                    .map(|score| score >= min_base_quality)
+                    .unwrap_or(true)
+            });
+        }
+
+        for cursor in &mut self.active {
+            cursor.advance();
+        }
+
+        self.active.retain(|cursor| cursor.end() > position);
+
+        self.position = position
+            .checked_add(1)
+            .map(|next_position| (reference_sequence_id, next_position));
+
+        Some(Ok(Column::new(
+            reference_sequence_id,
+            position,
+            reference_base,
+            entries,
+        )))
+    }
+}
+
+fn remove_overlapping_mates(entries: &mut Vec<Entry>) {
+    let mut is_kept = vec![true; entries.len()];
+    let mut mates = HashMap::new();
+
+    for (i, entry) in entries.iter().enumerate() {
+        let record = entry.record();
+
+        if !record.flags().is_segmented() {
+            continue;
+        }
+
+        let read_name = match record.read_name() {
+            Some(read_name) => read_name,
+            None => continue,
+        };
+
+        match mates.entry(read_name) {
+            hash_map::Entry::Vacant(e) => {
+                e.insert(i);
+            }
+            hash_map::Entry::Occupied(mut e) => {
+                let j = *e.get();
+
+                if rank(entry) > rank(&entries[j]) {
+                    is_kept[j] = false;
+                    e.insert(i);
+                } else {
+                    is_kept[i] = false;
+                }
+            }
+        }
+    }
+
+    let mut is_kept = is_kept.into_iter();
+    entries.retain(|_| is_kept.next().unwrap_or(true));
+}
+
+// Aligned bases rank above deletions and skips, and then by quality score.
+fn rank(entry: &Entry) -> (bool, Option<Score>) {
+    (entry.kind() == EntryKind::Aligned, entry.quality_score())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        header::record::value::{map::ReferenceSequence, Map},
+        record::{Cigar, Sequence},
+    };
+
+    type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+    fn build_header() -> Result<Header> {
+        Ok(Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 16)?)
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq1".parse()?, 16)?)
+            .build())
+    }
+
+    fn build_record(
+        reference_sequence_id: usize,
+        alignment_start: usize,
+        cigar: &str,
+    ) -> Result<Record> {
+        let cigar: Cigar = cigar.parse()?;
+        let sequence: Sequence = "A".repeat(cigar.read_length()).parse()?;
+
+        Ok(Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(reference_sequence_id)
+            .set_alignment_start(Position::try_from(alignment_start)?)
+            .set_cigar(cigar)
+            .set_sequence(sequence)
+            .build())
+    }
+
+    fn depths<I>(pileup: Pileup<'_, I>) -> io::Result<Vec<(usize, usize, usize)>>
+    where
+        I: Iterator<Item = io::Result<Record>>,
+    {
+        pileup
+            .map(|result| {
+                result.map(|column| {
+                    (
+                        column.reference_sequence_id(),
+                        usize::from(column.position()),
+                        column.entries().len(),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_next() -> Result<()> {
+        let header = build_header()?;
+        let records = [build_record(0, 1, "1S2M1I1M1D1M2N1M")?];
+
+        let actual = Pileup::new(&header, records.into_iter().map(Ok))
+            .map(|result| {
+                result.map(|column| {
+                    let entry = &column.entries()[0];
+
+                    (
+                        usize::from(column.position()),
+                        entry.kind(),
+                        entry.query_position(),
+                        entry.insertion_len(),
+                        entry.deletion_len(),
+                    )
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let expected = [
+            (1, EntryKind::Aligned, Some(1), 0, 0),
+            (2, EntryKind::Aligned, Some(2), 1, 0),
+            (3, EntryKind::Aligned, Some(4), 0, 1),
+            (4, EntryKind::Deletion, None, 0, 0),
+            (5, EntryKind::Aligned, Some(5), 0, 0),
+            (6, EntryKind::Skip, None, 0, 0),
+            (7, EntryKind::Skip, None, 0, 0),
+            (8, EntryKind::Aligned, Some(6), 0, 0),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_multiple_reference_sequences() -> Result<()> {
+        let header = build_header()?;
+
+        let records = [
+            build_record(0, 1, "2M")?,
+            build_record(0, 5, "1M")?,
+            build_record(1, 1, "1M")?,
+        ];
+
+        let pileup = Pileup::new(&header, records.into_iter().map(Ok));
+        assert_eq!(
+            depths(pileup)?,
+            [(0, 1, 1), (0, 2, 1), (0, 5, 1), (1, 1, 1)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_unsorted_records() -> Result<()> {
+        let header = build_header()?;
+        let records = [build_record(0, 2, "1M")?, build_record(0, 1, "1M")?];
+
+        let pileup = Pileup::new(&header, records.into_iter().map(Ok));
+
+        assert!(matches!(
+            depths(pileup),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_filters() -> Result<()> {
+        let header = build_header()?;
+
+        let mut duplicate = build_record(0, 1, "2M")?;
+        *duplicate.flags_mut() = Flags::DUPLICATE;
+
+        let mut low_mapping_quality = build_record(0, 1, "2M")?;
+        *low_mapping_quality.mapping_quality_mut() = Some(MappingQuality::try_from(10)?);
+
+        let missing_mapping_quality = build_record(0, 1, "2M")?;
+
+        let mut record = build_record(0, 1, "2M")?;
+        *record.mapping_quality_mut() = Some(MappingQuality::try_from(30)?);
+        *record.quality_scores_mut() = "!I".parse()?;
+
+        let records = [
+            duplicate,
+            low_mapping_quality,
+            missing_mapping_quality,
+            record,
+        ];
+
+        let pileup = Builder::default()
+            .set_min_mapping_quality(MappingQuality::try_from(20)?)
+            .set_min_base_quality(Score::try_from(20)?)
+            .build(&header, records.into_iter().map(Ok));
+
+        assert_eq!(depths(pileup)?, [(0, 1, 0), (0, 2, 1)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_max_depth() -> Result<()> {
+        let header = build_header()?;
+
+        let records = [
+            build_record(0, 1, "2M")?,
+            build_record(0, 1, "3M")?,
+            build_record(0, 1, "2M")?,
+            build_record(0, 2, "1M")?,
+            build_record(0, 3, "1M")?,
+        ];
+
+        let pileup = Builder::default()
+            .set_max_depth(2)
+            .build(&header, records.into_iter().map(Ok));
+
+        assert_eq!(depths(pileup)?, [(0, 1, 2), (0, 2, 2), (0, 3, 2)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_overlapping_mates() -> Result<()> {
+        let header = build_header()?;
+
+        let mut first_segment = build_record(0, 1, "3M")?;
+        *first_segment.flags_mut() = Flags::SEGMENTED | Flags::FIRST_SEGMENT;
+        *first_segment.read_name_mut() = Some("r0".parse()?);
+        *first_segment.quality_scores_mut() = "III".parse()?;
+
+        let mut last_segment = build_record(0, 2, "1D2M")?;
+        *last_segment.flags_mut() = Flags::SEGMENTED | Flags::LAST_SEGMENT;
+        *last_segment.read_name_mut() = Some("r0".parse()?);
+        *last_segment.quality_scores_mut() = "IJ".parse()?;
+
+        let records = [first_segment, last_segment];
+
+        let actual = Pileup::new(&header, records.clone().into_iter().map(Ok))
+            .map(|result| {
+                result.map(|column| {
+                    column
+                        .entries()
+                        .iter()
+                        .map(|entry| entry.record().flags().is_first_segment())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        assert_eq!(actual, [vec![true], vec![true], vec![true], vec![false]]);
+
+        let pileup = Builder::default()
+            .set_remove_overlapping_mates(false)
+            .build(&header, records.into_iter().map(Ok));
+
+        assert_eq!(
+            depths(pileup)?,
+            [(0, 1, 1), (0, 2, 2), (0, 3, 2), (0, 4, 1)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_reference_sequence_repository() -> Result<()> {
+        let header = build_header()?;
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            fasta::record::Definition::new("sq0", None),
+            fasta::record::Sequence::from(b"ACgt".to_vec()),
+        )]);
+
+        let records = [build_record(0, 2, "2M")?];
+
+        let actual = Builder::default()
+            .set_reference_sequence_repository(repository)
+            .build(&header, records.into_iter().map(Ok))
+            .map(|result| result.map(|column| column.reference_base()))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        assert_eq!(actual, [Some(Base::C), Some(Base::G)]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+use noodles_fasta as fasta;
+
+use super::Pileup;
+use crate::{
+    alignment::Record,
+    record::{quality_scores::Score, Flags, MappingQuality},
+    Header,
+};
+
+/// An alignment pileup builder.
+pub struct Builder {
+    flags_filter: Flags,
+    min_mapping_quality: Option<MappingQuality>,
+    min_base_quality: Option<Score>,
+    max_depth: usize,
+    remove_overlapping_mates: bool,
+    reference_sequence_repository: Option<fasta::Repository>,
+}
+
+impl Builder {
+    /// Sets the flags filter.
+    ///
+    /// Records with any of these flags set are skipped. By default, this is unmapped (`0x04`),
+    /// secondary (`0x0100`), QC fail (`0x0200`), and duplicate (`0x0400`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{alignment::pileup, record::Flags};
+    /// let builder = pileup::Builder::default().set_flags_filter(Flags::UNMAPPED);
+    /// ```
+    pub fn set_flags_filter(mut self, flags_filter: Flags) -> Self {
+        self.flags_filter = flags_filter;
+        self
+    }
+
+    /// Sets the minimum mapping quality.
+    ///
+    /// Records with a lower or missing mapping quality are skipped. By default, records are not
+    /// filtered by mapping quality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{alignment::pileup, record::MappingQuality};
+    ///
+    /// let builder = pileup::Builder::default()
+    ///     .set_min_mapping_quality(MappingQuality::try_from(20)?);
+    /// # Ok::<_, noodles_sam::record::mapping_quality::ParseError>(())
+    /// ```
+    pub fn set_min_mapping_quality(mut self, min_mapping_quality: MappingQuality) -> Self {
+        self.min_mapping_quality = Some(min_mapping_quality);
+        self
+    }
+
+    /// Sets the minimum base quality.
+    ///
+    /// Aligned bases with a lower quality score are removed from columns. Deletions, skips, and
+    /// bases of records without quality scores are kept. By default, entries are not filtered by
+    /// base quality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{alignment::pileup, record::quality_scores::Score};
+    /// let builder = pileup::Builder::default().set_min_base_quality(Score::try_from(13)?);
+    /// # Ok::<_, noodles_sam::record::quality_scores::score::TryFromUByteError>(())
+    /// ```
+    pub fn set_min_base_quality(mut self, min_base_quality: Score) -> Self {
+        self.min_base_quality = Some(min_base_quality);
+        self
+    }
+
+    /// Sets the maximum depth.
+    ///
+    /// Records starting at a reference position already covered by this many records are
+    /// skipped. By default, the depth is not limited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::pileup;
+    /// let builder = pileup::Builder::default().set_max_depth(8000);
+    /// ```
+    pub fn set_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets whether to remove overlapping mates.
+    ///
+    /// When set, only one entry is kept per column for segmented records with the same read name.
+    /// The entry with the higher base quality is kept, or the first one on a tie. Aligned bases
+    /// rank above deletions and skips. This prevents counting a template twice where its segments
+    /// overlap. By default, this is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::pileup;
+    /// let builder = pileup::Builder::default().set_remove_overlapping_mates(false);
+    /// ```
+    pub fn set_remove_overlapping_mates(mut self, remove_overlapping_mates: bool) -> Self {
+        self.remove_overlapping_mates = remove_overlapping_mates;
+        self
+    }
+
+    /// Sets the reference sequence repository.
+    ///
+    /// When set, each column includes the reference base at its position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta as fasta;
+    /// use noodles_sam::alignment::pileup;
+    ///
+    /// let repository = fasta::Repository::default();
+    /// let builder = pileup::Builder::default().set_reference_sequence_repository(repository);
+    /// ```
+    pub fn set_reference_sequence_repository(
+        mut self,
+        reference_sequence_repository: fasta::Repository,
+    ) -> Self {
+        self.reference_sequence_repository = Some(reference_sequence_repository);
+        self
+    }
+
+    /// Builds an alignment pileup over coordinate-sorted records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam::{self as sam, alignment::pileup};
+    ///
+    /// let header = sam::Header::default();
+    /// let records = std::iter::empty::<io::Result<sam::alignment::Record>>();
+    ///
+    /// let mut pileup = pileup::Builder::default().build(&header, records);
+    /// assert!(pileup.next().is_none());
+    /// ```
+    pub fn build<I>(self, header: &Header, records: I) -> Pileup<'_, I>
+    where
+        I: Iterator<Item = std::io::Result<Record>>,
+    {
+        Pileup {
+            header,
+            records: records.fuse(),
+            flags_filter: self.flags_filter,
+            min_mapping_quality: self.min_mapping_quality,
+            min_base_quality: self.min_base_quality,
+            max_depth: self.max_depth,
+            remove_overlapping_mates: self.remove_overlapping_mates,
+            reference_sequence_repository: self.reference_sequence_repository,
+            reference_sequence: None,
+            last_key: None,
+            pending: None,
+            active: Vec::new(),
+            position: None,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            flags_filter: Flags::UNMAPPED | Flags::SECONDARY | Flags::QC_FAIL | Flags::DUPLICATE,
+            min_mapping_quality: None,
+            min_base_quality: None,
+            max_depth: usize::MAX,
+            remove_overlapping_mates: true,
+            reference_sequence_repository: None,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Alignment pileup column.
+
+pub mod entry;
+
+pub use self::entry::Entry;
+
+use noodles_core::Position;
+
+use crate::record::sequence::Base;
+
+/// An alignment pileup column.
+///
+/// A column is the list of reads covering a reference position.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Column {
+    reference_sequence_id: usize,
+    position: Position,
+    reference_base: Option<Base>,
+    entries: Vec<Entry>,
+}
+
+impl Column {
+    pub(crate) fn new(
+        reference_sequence_id: usize,
+        position: Position,
+        reference_base: Option<Base>,
+        entries: Vec<Entry>,
+    ) -> Self {
+        Self {
+            reference_sequence_id,
+            position,
+            reference_base,
+            entries,
+        }
+    }
+
+    /// Returns the reference sequence ID.
+    pub fn reference_sequence_id(&self) -> usize {
+        self.reference_sequence_id
+    }
+
+    /// Returns the reference position.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the reference base.
+    ///
+    /// This is only set when the pileup has a reference sequence repository.
+    pub fn reference_base(&self) -> Option<Base> {
+        self.reference_base
+    }
+
+    /// Returns the entries.
+    ///
+    /// Entries are in the order their records were read.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+}
@@ -0,0 +1,95 @@
+//! Alignment pileup column entry.
+
+use std::sync::Arc;
+
+use crate::{
+    alignment::Record,
+    record::{quality_scores::Score, sequence::Base},
+};
+
+/// The alignment state of a read at a pileup column.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kind {
+    /// A read base is aligned to the reference position (`M`, `=`, or `X`).
+    Aligned,
+    /// The reference position is deleted from the read (`D`).
+    Deletion,
+    /// The reference position is skipped by the read (`N`).
+    Skip,
+}
+
+/// An alignment pileup column entry.
+///
+/// An entry is a read covering a reference position.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    record: Arc<Record>,
+    kind: Kind,
+    query_position: Option<usize>,
+    insertion_len: usize,
+    deletion_len: usize,
+}
+
+impl Entry {
+    pub(crate) fn new(
+        record: Arc<Record>,
+        kind: Kind,
+        query_position: Option<usize>,
+        insertion_len: usize,
+        deletion_len: usize,
+    ) -> Self {
+        Self {
+            record,
+            kind,
+            query_position,
+            insertion_len,
+            deletion_len,
+        }
+    }
+
+    /// Returns the alignment record.
+    pub fn record(&self) -> &Record {
+        &self.record
+    }
+
+    /// Returns the alignment state of the read at the reference position.
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Returns the 0-based position in the read sequence of the aligned base.
+    ///
+    /// This is only set when the entry kind is [`Kind::Aligned`].
+    pub fn query_position(&self) -> Option<usize> {
+        self.query_position
+    }
+
+    /// Returns the aligned base.
+    ///
+    /// This is only set when the entry kind is [`Kind::Aligned`] and the record has a sequence.
+    pub fn base(&self) -> Option<Base> {
+        self.query_position
+            .and_then(|i| self.record.sequence().as_ref().get(i).copied())
+    }
+
+    /// Returns the quality score of the aligned base.
+    ///
+    /// This is only set when the entry kind is [`Kind::Aligned`] and the record has quality
+    /// scores.
+    pub fn quality_score(&self) -> Option<Score> {
+        self.query_position
+            .and_then(|i| self.record.quality_scores().as_ref().get(i).copied())
+    }
+
+    /// Returns the length of the insertion (`I`) following the reference position.
+    ///
+    /// The inserted bases directly follow the query position in the read sequence.
+    pub fn insertion_len(&self) -> usize {
+        self.insertion_len
+    }
+
+    /// Returns the length of the deletion (`D`) following the reference position.
+    pub fn deletion_len(&self) -> usize {
+        self.deletion_len
+    }
+}
@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use noodles_core::Position;
+
+use super::column::{entry::Kind as EntryKind, Entry};
+use crate::{alignment::Record, record::cigar::op::Kind};
+
+/// The alignment state of a record at a reference position.
+///
+/// The cursor always points at a CIGAR operation that consumes the reference, until the alignment
+/// ends.
+pub(super) struct Cursor {
+    record: Arc<Record>,
+    reference_sequence_id: usize,
+    start: Position,
+    end: Position,
+    op_index: usize,
+    op_offset: usize,
+    read_position: usize,
+}
+
+impl Cursor {
+    /// Creates a cursor at the alignment start.
+    ///
+    /// This returns `None` if the alignment does not consume the reference.
+    pub fn new(record: Record, reference_sequence_id: usize, start: Position) -> Option<Self> {
+        let span = record.alignment_span();
+
+        if span == 0 {
+            return None;
+        }
+
+        let end = start.checked_add(span - 1)?;
+
+        let mut cursor = Self {
+            record: Arc::new(record),
+            reference_sequence_id,
+            start,
+            end,
+            op_index: 0,
+            op_offset: 0,
+            read_position: 0,
+        };
+
+        cursor.seek();
+
+        Some(cursor)
+    }
+
+    pub fn reference_sequence_id(&self) -> usize {
+        self.reference_sequence_id
+    }
+
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    pub fn end(&self) -> Position {
+        self.end
+    }
+
+    pub fn entry(&self) -> Entry {
+        let cigar = self.record.cigar();
+        let op = cigar[self.op_index];
+
+        let (kind, query_position) = match op.kind() {
+            Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch => {
+                (EntryKind::Aligned, Some(self.read_position))
+            }
+            Kind::Deletion => (EntryKind::Deletion, None),
+            _ => (EntryKind::Skip, None),
+        };
+
+        let mut insertion_len = 0;
+        let mut deletion_len = 0;
+
+        if self.op_offset + 1 == op.len() {
+            for next_op in &cigar[self.op_index + 1..] {
+                match next_op.kind() {
+                    Kind::Insertion => insertion_len += next_op.len(),
+                    Kind::Pad => {}
+                    Kind::Deletion => {
+                        deletion_len = next_op.len();
+                        break;
+                    }
+                    _ => break,
+                }
+            }
+        }
+
+        Entry::new(
+            self.record.clone(),
+            kind,
+            query_position,
+            insertion_len,
+            deletion_len,
+        )
+    }
+
+    /// Moves the cursor to the next reference position.
+    pub fn advance(&mut self) {
+        let op = match self.record.cigar().get(self.op_index) {
+            Some(op) => *op,
+            None => return,
+        };
+
+        if op.kind().consumes_read() {
+            self.read_position += 1;
+        }
+
+        self.op_offset += 1;
+
+        if self.op_offset >= op.len() {
+            self.op_index += 1;
+            self.op_offset = 0;
+            self.seek();
+        }
+    }
+
+    fn seek(&mut self) {
+        let cigar = self.record.cigar();
+
+        while let Some(op) = cigar.get(self.op_index) {
+            if op.kind().consumes_reference() && !op.is_empty() {
+                break;
+            }
+
+            if op.kind().consumes_read() {
+                self.read_position += op.len();
+            }
+
+            self.op_index += 1;
+        }
+    }
+}
@@ -1,8 +1,10 @@
 //! Alignment record.
 
 mod builder;
+pub mod calmd;
+pub mod validate;
 
-pub use self::builder::Builder;
+pub use self::{builder::Builder, validate::validate};
 
 use std::io;
 
@@ -0,0 +1,469 @@
+//! Alignment record mismatched positions (`MD`) and edit distance (`NM`) calculation.
+//!
+//! This is similar to `samtools calmd`.
+
+use std::{error, fmt, io};
+
+use noodles_fasta as fasta;
+
+use super::Record;
+use crate::{
+    record::{
+        cigar::op::Kind,
+        data::{
+            field::{Tag, Value},
+            Field,
+        },
+        sequence::Base,
+    },
+    Header,
+};
+
+/// An error returned when a record's `MD` or `NM` data field does not match the calculated value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// The mismatched positions (`MD`) are invalid.
+    InvalidMismatchedPositions {
+        /// The value in the record.
+        actual: Value,
+        /// The calculated value.
+        expected: String,
+    },
+    /// The edit distance (`NM`) is invalid.
+    InvalidEditDistance {
+        /// The value in the record.
+        actual: Value,
+        /// The calculated value.
+        expected: u32,
+    },
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMismatchedPositions { actual, expected } => write!(
+                f,
+                "invalid mismatched positions (MD): expected {}, got {}",
+                expected, actual
+            ),
+            Self::InvalidEditDistance { actual, expected } => write!(
+                f,
+                "invalid edit distance (NM): expected {}, got {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+/// Calculates the mismatched positions (`MD`) and edit distance (`NM`) of a record.
+///
+/// The record must be mapped and have a sequence. Its reference sequence is read from the given
+/// repository.
+///
+/// Alignment matches (`M`), sequence matches (`=`), and sequence mismatches (`X`) are compared
+/// base by base, case-insensitively. An `N` in either sequence is a mismatch, and `=` in the read
+/// sequence is a match. Insertions (`I`) and deletions (`D`) add their lengths to the edit
+/// distance. Skips (`N`), soft clips (`S`), hard clips (`H`), and padding (`P`) do not change
+/// either value.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::Position;
+/// use noodles_fasta::{self as fasta, record::{Definition, Sequence}};
+/// use noodles_sam::{
+///     self as sam,
+///     alignment::{record::calmd, Record},
+///     header::record::value::{map::ReferenceSequence, Map},
+///     record::Flags,
+/// };
+///
+/// let header = sam::Header::builder()
+///     .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+///     .build();
+///
+/// let repository = fasta::Repository::new(vec![fasta::Record::new(
+///     Definition::new("sq0", None),
+///     Sequence::from(b"ACGTACGT".to_vec()),
+/// )]);
+///
+/// let record = Record::builder()
+///     .set_flags(Flags::empty())
+///     .set_reference_sequence_id(0)
+///     .set_alignment_start(Position::try_from(2)?)
+///     .set_cigar("2M1I1M1D1M".parse()?)
+///     .set_sequence("CTATC".parse()?)
+///     .build();
+///
+/// assert_eq!(
+///     calmd::calculate(&header, &repository, &record)?,
+///     (String::from("1G1^A1"), 3)
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn calculate(
+    header: &Header,
+    repository: &fasta::Repository,
+    record: &Record,
+) -> io::Result<(String, u32)> {
+    if record.flags().is_unmapped() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "record is unmapped",
+        ));
+    }
+
+    let reference_sequence = record.reference_sequence(header).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "missing reference sequence ID")
+    })??;
+
+    let alignment_start = record
+        .alignment_start()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing alignment start"))?;
+
+    if record.sequence().is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "missing sequence",
+        ));
+    }
+
+    let name = reference_sequence.name();
+
+    let sequence = repository.get(name).transpose()?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("missing reference sequence: {}", name),
+        )
+    })?;
+
+    let start = usize::from(alignment_start) - 1;
+
+    let reference_bases = sequence.as_ref().get(start..).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "alignment start is past the reference sequence end",
+        )
+    })?;
+
+    calculate_with_reference_bases(record, reference_bases)
+}
+
+/// Calculates and sets the mismatched positions (`MD`) and edit distance (`NM`) of a record.
+///
+/// Unmapped records are not changed. See [`calculate`] for how the values are calculated.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::Position;
+/// use noodles_fasta::{self as fasta, record::{Definition, Sequence}};
+/// use noodles_sam::{
+///     self as sam,
+///     alignment::{record::calmd, Record},
+///     header::record::value::{map::ReferenceSequence, Map},
+///     record::{data::field::{Tag, Value}, Flags},
+/// };
+///
+/// let header = sam::Header::builder()
+///     .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+///     .build();
+///
+/// let repository = fasta::Repository::new(vec![fasta::Record::new(
+///     Definition::new("sq0", None),
+///     Sequence::from(b"ACGTACGT".to_vec()),
+/// )]);
+///
+/// let mut record = Record::builder()
+///     .set_flags(Flags::empty())
+///     .set_reference_sequence_id(0)
+///     .set_alignment_start(Position::try_from(2)?)
+///     .set_cigar("4M".parse()?)
+///     .set_sequence("CGTT".parse()?)
+///     .build();
+///
+/// calmd::update(&header, &repository, &mut record)?;
+///
+/// let data = record.data();
+/// assert_eq!(
+///     data.get(Tag::MismatchedPositions).map(|field| field.value()),
+///     Some(&Value::String(String::from("3A0")))
+/// );
+/// assert_eq!(
+///     data.get(Tag::EditDistance).map(|field| field.value()),
+///     Some(&Value::UInt8(1))
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn update(
+    header: &Header,
+    repository: &fasta::Repository,
+    record: &mut Record,
+) -> io::Result<()> {
+    if record.flags().is_unmapped() {
+        return Ok(());
+    }
+
+    let (mismatched_positions, edit_distance) = calculate(header, repository, record)?;
+
+    let data = record.data_mut();
+
+    data.insert(Field::new(
+        Tag::MismatchedPositions,
+        Value::String(mismatched_positions),
+    ));
+
+    data.insert(Field::new(Tag::EditDistance, Value::from(edit_distance)));
+
+    Ok(())
+}
+
+/// Verifies the mismatched positions (`MD`) and edit distance (`NM`) of a record.
+///
+/// Only the data fields present in the record are checked. Unmapped records are not checked. See
+/// [`calculate`] for how the expected values are calculated.
+///
+/// All errors are returned. The record is valid if the list is empty.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_core::Position;
+/// use noodles_fasta::{self as fasta, record::{Definition, Sequence}};
+/// use noodles_sam::{
+///     self as sam,
+///     alignment::{record::calmd, Record},
+///     header::record::value::{map::ReferenceSequence, Map},
+///     record::{data::field::Value, Flags},
+/// };
+///
+/// let header = sam::Header::builder()
+///     .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+///     .build();
+///
+/// let repository = fasta::Repository::new(vec![fasta::Record::new(
+///     Definition::new("sq0", None),
+///     Sequence::from(b"ACGTACGT".to_vec()),
+/// )]);
+///
+/// let record = Record::builder()
+///     .set_flags(Flags::empty())
+///     .set_reference_sequence_id(0)
+///     .set_alignment_start(Position::try_from(2)?)
+///     .set_cigar("4M".parse()?)
+///     .set_sequence("CGTT".parse()?)
+///     .set_data("MD:Z:3A0\tNM:i:0".parse()?)
+///     .build();
+///
+/// assert_eq!(
+///     calmd::verify(&header, &repository, &record)?,
+///     [calmd::Error::InvalidEditDistance {
+///         actual: Value::UInt8(0),
+///         expected: 1,
+///     }]
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn verify(
+    header: &Header,
+    repository: &fasta::Repository,
+    record: &Record,
+) -> io::Result<Vec<Error>> {
+    let mut errors = Vec::new();
+
+    let data = record.data();
+    let actual_mismatched_positions = data.get(Tag::MismatchedPositions);
+    let actual_edit_distance = data.get(Tag::EditDistance);
+
+    if record.flags().is_unmapped()
+        || (actual_mismatched_positions.is_none() && actual_edit_distance.is_none())
+    {
+        return Ok(errors);
+    }
+
+    let (mismatched_positions, edit_distance) = calculate(header, repository, record)?;
+
+    if let Some(field) = actual_mismatched_positions {
+        if field.value().as_str() != Some(&mismatched_positions) {
+            errors.push(Error::InvalidMismatchedPositions {
+                actual: field.value().clone(),
+                expected: mismatched_positions,
+            });
+        }
+    }
+
+    if let Some(field) = actual_edit_distance {
+        if field.value().as_int() != Some(i64::from(edit_distance)) {
+            errors.push(Error::InvalidEditDistance {
+                actual: field.value().clone(),
+                expected: edit_distance,
+            });
+        }
+    }
+
+    Ok(errors)
+}
+
+fn calculate_with_reference_bases(
+    record: &Record,
+    reference_bases: &[u8],
+) -> io::Result<(String, u32)> {
+    use std::fmt::Write;
+
+    fn out_of_range_error() -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "alignment is out of range of the sequence or reference sequence",
+        )
+    }
+
+    let read_bases = record.sequence().as_ref();
+
+    let mut mismatched_positions = String::new();
+    let mut edit_distance = 0;
+    let mut match_count = 0;
+
+    let mut read_position = 0;
+    let mut reference_position = 0;
+
+    for op in record.cigar().iter() {
+        let len = op.len();
+
+        match op.kind() {
+            Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch => {
+                let read_end = read_position + len;
+                let reference_end = reference_position + len;
+
+                let bases = read_bases
+                    .get(read_position..read_end)
+                    .zip(reference_bases.get(reference_position..reference_end))
+                    .ok_or_else(out_of_range_error)?;
+
+                for (&read_base, &reference_base) in bases.0.iter().zip(bases.1) {
+                    if is_match(read_base, reference_base) {
+                        match_count += 1;
+                    } else {
+                        write!(
+                            mismatched_positions,
+                            "{}{}",
+                            match_count,
+                            char::from(reference_base.to_ascii_uppercase())
+                        )
+                        .unwrap();
+
+                        match_count = 0;
+                        edit_distance += 1;
+                    }
+                }
+
+                read_position = read_end;
+                reference_position = reference_end;
+            }
+            Kind::Insertion => {
+                read_position += len;
+                edit_distance += len;
+            }
+            Kind::Deletion => {
+                let reference_end = reference_position + len;
+
+                let bases = reference_bases
+                    .get(reference_position..reference_end)
+                    .ok_or_else(out_of_range_error)?;
+
+                write!(mismatched_positions, "{}^", match_count).unwrap();
+
+                for &base in bases {
+                    mismatched_positions.push(char::from(base.to_ascii_uppercase()));
+                }
+
+                match_count = 0;
+                edit_distance += len;
+                reference_position = reference_end;
+            }
+            Kind::Skip => reference_position += len,
+            Kind::SoftClip => read_position += len,
+            Kind::HardClip | Kind::Pad => {}
+        }
+    }
+
+    write!(mismatched_positions, "{}", match_count).unwrap();
+
+    let edit_distance =
+        u32::try_from(edit_distance).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    Ok((mismatched_positions, edit_distance))
+}
+
+fn is_match(read_base: Base, reference_base: u8) -> bool {
+    let reference_base = reference_base.to_ascii_uppercase();
+
+    match read_base {
+        Base::Eq => true,
+        Base::N => false,
+        _ => reference_base != b'N' && u8::from(read_base) == reference_base,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calculate_from(
+        cigar: &str,
+        sequence: &str,
+        reference_bases: &[u8],
+    ) -> Result<(String, u32), Box<dyn std::error::Error>> {
+        let record = Record::builder()
+            .set_cigar(cigar.parse()?)
+            .set_sequence(sequence.parse()?)
+            .build();
+
+        calculate_with_reference_bases(&record, reference_bases).map_err(|e| e.into())
+    }
+
+    #[test]
+    fn test_calculate_with_reference_bases() -> Result<(), Box<dyn std::error::Error>> {
+        const REFERENCE_BASES: &[u8] = b"ACGTACGTAC";
+
+        assert_eq!(
+            calculate_from("4M", "ACGT", REFERENCE_BASES)?,
+            (String::from("4"), 0)
+        );
+        assert_eq!(
+            calculate_from("4M", "TCGA", REFERENCE_BASES)?,
+            (String::from("0A2T0"), 2)
+        );
+        assert_eq!(
+            calculate_from("2=1X1=", "ACTT", REFERENCE_BASES)?,
+            (String::from("2G1"), 1)
+        );
+        assert_eq!(
+            calculate_from("2S2M2I2M", "TTACGGGT", REFERENCE_BASES)?,
+            (String::from("4"), 2)
+        );
+        assert_eq!(
+            calculate_from("2M2D1D2M", "ACCG", REFERENCE_BASES)?,
+            (String::from("2^GT0^A2"), 3)
+        );
+        assert_eq!(
+            calculate_from("2M4N2M1H", "ACAC", REFERENCE_BASES)?,
+            (String::from("2G0T0"), 2)
+        );
+        assert_eq!(
+            calculate_from("3M", "ANg", b"aCg")?,
+            (String::from("1C1"), 1)
+        );
+        assert_eq!(calculate_from("3M", "A=G", b"ANG")?, (String::from("3"), 0));
+        assert_eq!(calculate_from("2M", "AC", b"AN")?, (String::from("1N0"), 1));
+
+        assert!(calculate_from("8M", "ACGT", REFERENCE_BASES).is_err());
+        assert!(calculate_from("4M", "ACGT", b"AC").is_err());
+
+        Ok(())
+    }
+}
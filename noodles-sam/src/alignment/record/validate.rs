@@ -0,0 +1,536 @@
+//! Alignment record validation.
+
+use std::{error, fmt};
+
+use noodles_core::Position;
+
+use super::Record;
+use crate::{
+    record::{data::field::Tag, Flags},
+    Header,
+};
+
+/// An alignment record validation error.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The reference sequence ID is not in the header.
+    InvalidReferenceSequenceId(usize),
+    /// The mate reference sequence ID is not in the header.
+    InvalidMateReferenceSequenceId(usize),
+    /// The record is mapped but has no reference sequence ID.
+    MissingReferenceSequenceId,
+    /// The record is mapped but has no alignment start.
+    MissingAlignmentStart,
+    /// The alignment start is past the end of the reference sequence.
+    AlignmentStartOutOfRange {
+        /// The alignment start.
+        alignment_start: Position,
+        /// The reference sequence length.
+        reference_sequence_length: usize,
+    },
+    /// The alignment end is past the end of the reference sequence.
+    AlignmentEndOutOfRange {
+        /// The alignment end.
+        alignment_end: Position,
+        /// The reference sequence length.
+        reference_sequence_length: usize,
+    },
+    /// The mate alignment start is past the end of the mate reference sequence.
+    MateAlignmentStartOutOfRange {
+        /// The mate alignment start.
+        mate_alignment_start: Position,
+        /// The mate reference sequence length.
+        reference_sequence_length: usize,
+    },
+    /// The record is unmapped but has a CIGAR.
+    UnmappedWithCigar,
+    /// The record is unmapped but has a nonzero mapping quality.
+    UnmappedWithMappingQuality(u8),
+    /// The record is not paired (segmented) but has mate flags set.
+    ///
+    /// The value is the set of mate flags.
+    UnpairedWithMateFlags(Flags),
+    /// The record is not paired but has a mate reference sequence ID or mate alignment start.
+    UnpairedWithMateFields,
+    /// The read length calculated from the CIGAR does not match the sequence length.
+    CigarReadLengthMismatch {
+        /// The read length calculated from the CIGAR.
+        cigar_read_length: usize,
+        /// The sequence length.
+        sequence_length: usize,
+    },
+    /// The quality scores length does not match the sequence length.
+    QualityScoresLengthMismatch {
+        /// The quality scores length.
+        quality_scores_length: usize,
+        /// The sequence length.
+        sequence_length: usize,
+    },
+    /// The read group (`RG`) is not in the header.
+    MissingReadGroup(String),
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidReferenceSequenceId(id) => {
+                write!(f, "invalid reference sequence ID: {}", id)
+            }
+            Self::InvalidMateReferenceSequenceId(id) => {
+                write!(f, "invalid mate reference sequence ID: {}", id)
+            }
+            Self::MissingReferenceSequenceId => {
+                f.write_str("mapped record is missing reference sequence ID")
+            }
+            Self::MissingAlignmentStart => f.write_str("mapped record is missing alignment start"),
+            Self::AlignmentStartOutOfRange {
+                alignment_start,
+                reference_sequence_length,
+            } => write!(
+                f,
+                "alignment start ({}) is past the reference sequence end ({})",
+                alignment_start, reference_sequence_length
+            ),
+            Self::AlignmentEndOutOfRange {
+                alignment_end,
+                reference_sequence_length,
+            } => write!(
+                f,
+                "alignment end ({}) is past the reference sequence end ({})",
+                alignment_end, reference_sequence_length
+            ),
+            Self::MateAlignmentStartOutOfRange {
+                mate_alignment_start,
+                reference_sequence_length,
+            } => write!(
+                f,
+                "mate alignment start ({}) is past the mate reference sequence end ({})",
+                mate_alignment_start, reference_sequence_length
+            ),
+            Self::UnmappedWithCigar => f.write_str("unmapped record has a CIGAR"),
+            Self::UnmappedWithMappingQuality(mapping_quality) => write!(
+                f,
+                "unmapped record has a nonzero mapping quality: {}",
+                mapping_quality
+            ),
+            Self::UnpairedWithMateFlags(flags) => write!(
+                f,
+                "unpaired record has mate flags set: {:#06x}",
+                u16::from(*flags)
+            ),
+            Self::UnpairedWithMateFields => f.write_str("unpaired record has mate fields"),
+            Self::CigarReadLengthMismatch {
+                cigar_read_length,
+                sequence_length,
+            } => write!(
+                f,
+                "CIGAR read length ({}) does not match sequence length ({})",
+                cigar_read_length, sequence_length
+            ),
+            Self::QualityScoresLengthMismatch {
+                quality_scores_length,
+                sequence_length,
+            } => write!(
+                f,
+                "quality scores length ({}) does not match sequence length ({})",
+                quality_scores_length, sequence_length
+            ),
+            Self::MissingReadGroup(id) => write!(f, "missing read group: {}", id),
+        }
+    }
+}
+
+/// Validates an alignment record against its header.
+///
+/// This checks that
+///
+///   * reference sequence IDs are in the header;
+///   * a mapped record has a reference sequence ID and alignment start, and its alignment does not
+///     extend past the end of the reference sequence;
+///   * an unmapped record has no CIGAR and a mapping quality of 0 or missing (255);
+///   * an unpaired record has no mate flags or mate fields;
+///   * the CIGAR read length and quality scores length match the sequence length, when each is
+///     set; and
+///   * the read group (`RG`) is in the header.
+///
+/// Records read by the SAM, BAM, and CRAM readers are all alignment records, so this works the
+/// same for each format.
+///
+/// All errors are returned. The record is valid if the list is empty.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{
+///     self as sam,
+///     alignment::{record::validate, Record},
+///     header::record::value::{map::ReferenceSequence, Map},
+///     record::Flags,
+/// };
+///
+/// let header = sam::Header::builder()
+///     .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+///     .build();
+///
+/// let record = Record::builder()
+///     .set_flags(Flags::UNMAPPED)
+///     .set_cigar("4M".parse()?)
+///     .set_sequence("ACGT".parse()?)
+///     .build();
+///
+/// assert_eq!(
+///     validate(&header, &record),
+///     [validate::Error::UnmappedWithCigar]
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn validate(header: &Header, record: &Record) -> Vec<Error> {
+    let mut errors = Vec::new();
+
+    validate_positions(header, record, &mut errors);
+    validate_flags(record, &mut errors);
+    validate_lengths(record, &mut errors);
+    validate_read_group(header, record, &mut errors);
+
+    errors
+}
+
+fn get_reference_sequence_length(header: &Header, id: usize) -> Option<usize> {
+    header
+        .reference_sequences()
+        .get_index(id)
+        .map(|(_, reference_sequence)| usize::from(reference_sequence.length()))
+}
+
+fn validate_positions(header: &Header, record: &Record, errors: &mut Vec<Error>) {
+    let flags = record.flags();
+
+    let reference_sequence_length = match record.reference_sequence_id() {
+        Some(id) => match get_reference_sequence_length(header, id) {
+            Some(len) => Some(len),
+            None => {
+                errors.push(Error::InvalidReferenceSequenceId(id));
+                None
+            }
+        },
+        None => {
+            if !flags.is_unmapped() {
+                errors.push(Error::MissingReferenceSequenceId);
+            }
+
+            None
+        }
+    };
+
+    if !flags.is_unmapped() {
+        match record.alignment_start() {
+            Some(alignment_start) => {
+                if let Some(len) = reference_sequence_length {
+                    if usize::from(alignment_start) > len {
+                        errors.push(Error::AlignmentStartOutOfRange {
+                            alignment_start,
+                            reference_sequence_length: len,
+                        });
+                    } else if let Some(alignment_end) = record.alignment_end() {
+                        if usize::from(alignment_end) > len {
+                            errors.push(Error::AlignmentEndOutOfRange {
+                                alignment_end,
+                                reference_sequence_length: len,
+                            });
+                        }
+                    }
+                }
+            }
+            None => errors.push(Error::MissingAlignmentStart),
+        }
+    }
+
+    if let Some(id) = record.mate_reference_sequence_id() {
+        match get_reference_sequence_length(header, id) {
+            Some(len) => {
+                if let Some(mate_alignment_start) = record.mate_alignment_start() {
+                    if usize::from(mate_alignment_start) > len {
+                        errors.push(Error::MateAlignmentStartOutOfRange {
+                            mate_alignment_start,
+                            reference_sequence_length: len,
+                        });
+                    }
+                }
+            }
+            None => errors.push(Error::InvalidMateReferenceSequenceId(id)),
+        }
+    }
+}
+
+fn validate_flags(record: &Record, errors: &mut Vec<Error>) {
+    let flags = record.flags();
+
+    if flags.is_unmapped() {
+        if !record.cigar().is_empty() {
+            errors.push(Error::UnmappedWithCigar);
+        }
+
+        if let Some(mapping_quality) = record.mapping_quality() {
+            if mapping_quality.get() != 0 {
+                errors.push(Error::UnmappedWithMappingQuality(mapping_quality.get()));
+            }
+        }
+    }
+
+    if !flags.is_segmented() {
+        let mate_flags = flags
+            & (Flags::PROPERLY_ALIGNED
+                | Flags::MATE_UNMAPPED
+                | Flags::MATE_REVERSE_COMPLEMENTED
+                | Flags::FIRST_SEGMENT
+                | Flags::LAST_SEGMENT);
+
+        if !mate_flags.is_empty() {
+            errors.push(Error::UnpairedWithMateFlags(mate_flags));
+        }
+
+        if record.mate_reference_sequence_id().is_some() || record.mate_alignment_start().is_some()
+        {
+            errors.push(Error::UnpairedWithMateFields);
+        }
+    }
+}
+
+fn validate_lengths(record: &Record, errors: &mut Vec<Error>) {
+    let sequence_length = record.sequence().len();
+
+    if sequence_length == 0 {
+        return;
+    }
+
+    if !record.cigar().is_empty() {
+        let cigar_read_length = record.cigar().read_length();
+
+        if cigar_read_length != sequence_length {
+            errors.push(Error::CigarReadLengthMismatch {
+                cigar_read_length,
+                sequence_length,
+            });
+        }
+    }
+
+    let quality_scores_length = record.quality_scores().len();
+
+    if quality_scores_length > 0 && quality_scores_length != sequence_length {
+        errors.push(Error::QualityScoresLengthMismatch {
+            quality_scores_length,
+            sequence_length,
+        });
+    }
+}
+
+fn validate_read_group(header: &Header, record: &Record, errors: &mut Vec<Error>) {
+    if let Some(id) = record
+        .data()
+        .get(Tag::ReadGroup)
+        .and_then(|field| field.value().as_str())
+    {
+        if !header.read_groups().contains_key(id) {
+            errors.push(Error::MissingReadGroup(id.into()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        header::record::value::{
+            map::{ReadGroup, ReferenceSequence},
+            Map,
+        },
+        record::{
+            data::{field::Value, Field},
+            MappingQuality,
+        },
+    };
+
+    fn build_header() -> Result<Header, Box<dyn std::error::Error>> {
+        Ok(Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+            .add_read_group(Map::<ReadGroup>::new("rg0"))
+            .build())
+    }
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let record = Record::builder()
+            .set_flags(Flags::SEGMENTED | Flags::FIRST_SEGMENT | Flags::MATE_UNMAPPED)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(5)?)
+            .set_cigar("4M".parse()?)
+            .set_sequence("ACGT".parse()?)
+            .set_quality_scores("NDLS".parse()?)
+            .set_data("RG:Z:rg0".parse()?)
+            .build();
+        assert!(validate(&header, &record).is_empty());
+
+        let record = Record::builder().set_flags(Flags::UNMAPPED).build();
+        assert!(validate(&header, &record).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_positions() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let record = Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(1)
+            .build();
+        assert_eq!(
+            validate(&header, &record),
+            [
+                Error::InvalidReferenceSequenceId(1),
+                Error::MissingAlignmentStart
+            ]
+        );
+
+        let record = Record::builder().set_flags(Flags::empty()).build();
+        assert_eq!(
+            validate(&header, &record),
+            [
+                Error::MissingReferenceSequenceId,
+                Error::MissingAlignmentStart
+            ]
+        );
+
+        let record = Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(13)?)
+            .build();
+        assert_eq!(
+            validate(&header, &record),
+            [Error::AlignmentStartOutOfRange {
+                alignment_start: Position::try_from(13)?,
+                reference_sequence_length: 8,
+            }]
+        );
+
+        let record = Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(6)?)
+            .set_cigar("4M".parse()?)
+            .build();
+        assert_eq!(
+            validate(&header, &record),
+            [Error::AlignmentEndOutOfRange {
+                alignment_end: Position::try_from(9)?,
+                reference_sequence_length: 8,
+            }]
+        );
+
+        let record = Record::builder()
+            .set_flags(Flags::SEGMENTED | Flags::UNMAPPED)
+            .set_mate_reference_sequence_id(0)
+            .set_mate_alignment_start(Position::try_from(13)?)
+            .build();
+        assert_eq!(
+            validate(&header, &record),
+            [Error::MateAlignmentStartOutOfRange {
+                mate_alignment_start: Position::try_from(13)?,
+                reference_sequence_length: 8,
+            }]
+        );
+
+        let record = Record::builder()
+            .set_flags(Flags::SEGMENTED | Flags::UNMAPPED)
+            .set_mate_reference_sequence_id(2)
+            .build();
+        assert_eq!(
+            validate(&header, &record),
+            [Error::InvalidMateReferenceSequenceId(2)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_flags() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let record = Record::builder()
+            .set_flags(Flags::UNMAPPED | Flags::PROPERLY_ALIGNED | Flags::FIRST_SEGMENT)
+            .set_mapping_quality(MappingQuality::try_from(13)?)
+            .set_cigar("4M".parse()?)
+            .set_mate_alignment_start(Position::try_from(5)?)
+            .build();
+
+        assert_eq!(
+            validate(&header, &record),
+            [
+                Error::UnmappedWithCigar,
+                Error::UnmappedWithMappingQuality(13),
+                Error::UnpairedWithMateFlags(Flags::PROPERLY_ALIGNED | Flags::FIRST_SEGMENT),
+                Error::UnpairedWithMateFields,
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_lengths() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let record = Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::MIN)
+            .set_cigar("2M".parse()?)
+            .set_sequence("ACGT".parse()?)
+            .set_quality_scores("NDL".parse()?)
+            .build();
+
+        assert_eq!(
+            validate(&header, &record),
+            [
+                Error::CigarReadLengthMismatch {
+                    cigar_read_length: 2,
+                    sequence_length: 4,
+                },
+                Error::QualityScoresLengthMismatch {
+                    quality_scores_length: 3,
+                    sequence_length: 4,
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_read_group() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let record = Record::builder()
+            .set_flags(Flags::UNMAPPED)
+            .set_data(
+                vec![Field::new(
+                    Tag::ReadGroup,
+                    Value::String(String::from("rg1")),
+                )]
+                .try_into()?,
+            )
+            .build();
+
+        assert_eq!(
+            validate(&header, &record),
+            [Error::MissingReadGroup(String::from("rg1"))]
+        );
+
+        Ok(())
+    }
+}
@@ -64,19 +64,22 @@
 //! ```
 
 mod builder;
+pub mod merge;
 mod parser;
 pub mod record;
+pub mod validate;
 
 use std::{fmt, str::FromStr};
 
 use indexmap::IndexMap;
+use noodles_core::{sequence_dictionary::Sequence, SequenceDictionary};
 
-pub use self::{builder::Builder, parser::ParseError};
+pub use self::{builder::Builder, merge::merge, parser::ParseError, validate::validate};
 
 pub use self::record::Record;
 
 use self::record::value::{
-    map::{self, Program, ReadGroup, ReferenceSequence},
+    map::{self, reference_sequence::TryFromSequenceError, Program, ReadGroup, ReferenceSequence},
     Map,
 };
 
@@ -442,6 +445,65 @@ impl FromStr for Header {
     }
 }
 
+impl From<&Header> for SequenceDictionary {
+    /// Converts the reference sequences of a SAM header to a sequence dictionary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::SequenceDictionary;
+    /// use noodles_sam as sam;
+    ///
+    /// let header: sam::Header = "@SQ\tSN:sq0\tLN:8\n@SQ\tSN:sq1\tLN:13\n".parse()?;
+    /// let dictionary = SequenceDictionary::from(&header);
+    ///
+    /// assert_eq!(dictionary.len(), 2);
+    /// assert_eq!(dictionary.get("sq1").and_then(|sequence| sequence.length()), Some(13));
+    /// # Ok::<_, sam::header::ParseError>(())
+    /// ```
+    fn from(header: &Header) -> Self {
+        header
+            .reference_sequences()
+            .values()
+            .map(Sequence::from)
+            .collect()
+    }
+}
+
+impl TryFrom<&SequenceDictionary> for Header {
+    type Error = TryFromSequenceError;
+
+    /// Converts a sequence dictionary to a SAM header with only reference sequences.
+    ///
+    /// All sequences must have a length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{sequence_dictionary::Sequence, SequenceDictionary};
+    /// use noodles_sam as sam;
+    ///
+    /// let dictionary: SequenceDictionary = [Sequence::new("sq0", Some(8))].into_iter().collect();
+    /// let header = sam::Header::try_from(&dictionary)?;
+    ///
+    /// assert_eq!(header.reference_sequences().len(), 1);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn try_from(dictionary: &SequenceDictionary) -> Result<Self, Self::Error> {
+        let mut header = Self::default();
+
+        for sequence in dictionary {
+            let reference_sequence = Map::<ReferenceSequence>::try_from(sequence)?;
+
+            header
+                .reference_sequences
+                .insert(reference_sequence.name().to_string(), reference_sequence);
+        }
+
+        Ok(header)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,598 @@
+//! SAM header merging.
+
+use std::{
+    collections::{HashMap, HashSet},
+    error, fmt,
+};
+
+use super::{
+    record::value::{
+        map::{self, header::SortOrder, Program},
+        Map,
+    },
+    Header, Programs, ReadGroups, ReferenceSequences,
+};
+use crate::{
+    alignment::Record,
+    record::data::field::{Tag, Value},
+};
+
+/// An error returned when SAM headers fail to merge.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The reference sequences of the header at the given index differ from the others.
+    IncompatibleReferenceSequences(usize),
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IncompatibleReferenceSequences(i) => {
+                write!(f, "incompatible reference sequences in header {}", i)
+            }
+        }
+    }
+}
+
+/// The read group and program ID changes made to one of the merged headers.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Remapping {
+    read_group_ids: HashMap<String, String>,
+    program_ids: HashMap<String, String>,
+}
+
+impl Remapping {
+    /// Returns the renamed read group IDs, from the original ID to the merged ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::header::merge::Remapping;
+    /// let remapping = Remapping::default();
+    /// assert!(remapping.read_group_ids().is_empty());
+    /// ```
+    pub fn read_group_ids(&self) -> &HashMap<String, String> {
+        &self.read_group_ids
+    }
+
+    /// Returns the renamed program IDs, from the original ID to the merged ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::header::merge::Remapping;
+    /// let remapping = Remapping::default();
+    /// assert!(remapping.program_ids().is_empty());
+    /// ```
+    pub fn program_ids(&self) -> &HashMap<String, String> {
+        &self.program_ids
+    }
+
+    /// Returns whether no IDs were renamed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::header::merge::Remapping;
+    /// let remapping = Remapping::default();
+    /// assert!(remapping.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.read_group_ids.is_empty() && self.program_ids.is_empty()
+    }
+
+    /// Rewrites the read group (`RG`) and program (`PG`) data fields of a record.
+    ///
+    /// The record must be from the header this remapping was made for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     alignment::Record,
+    ///     header::{self, record::value::{map::ReadGroup, Map}},
+    ///     record::data::field::{Tag, Value},
+    /// };
+    ///
+    /// let header = sam::Header::builder()
+    ///     .add_read_group(Map::<ReadGroup>::builder().set_id("rg0").set_sample("sample0").build()?)
+    ///     .build();
+    ///
+    /// let other_header = sam::Header::builder()
+    ///     .add_read_group(Map::<ReadGroup>::builder().set_id("rg0").set_sample("sample1").build()?)
+    ///     .build();
+    ///
+    /// let (_, remappings) = header::merge(&[header, other_header])?;
+    ///
+    /// let mut record = Record::builder().set_data("RG:Z:rg0".parse()?).build();
+    /// remappings[1].apply(&mut record);
+    ///
+    /// assert_eq!(
+    ///     record.data().get(Tag::ReadGroup).map(|field| field.value()),
+    ///     Some(&Value::String(String::from("rg0.1")))
+    /// );
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn apply(&self, record: &mut Record) {
+        use crate::record::data::Field;
+
+        for (tag, ids) in [
+            (Tag::ReadGroup, &self.read_group_ids),
+            (Tag::Program, &self.program_ids),
+        ] {
+            let new_id = record
+                .data()
+                .get(tag)
+                .and_then(|field| field.value().as_str())
+                .and_then(|id| ids.get(id));
+
+            if let Some(new_id) = new_id.cloned() {
+                record
+                    .data_mut()
+                    .insert(Field::new(tag, Value::String(new_id)));
+            }
+        }
+    }
+}
+
+/// Merges SAM headers.
+///
+/// The reference sequences (`@SQ`) of each header must either be empty or have the same names and
+/// lengths, in the same order, as every other nonempty list. MD5 checksums, when both are set,
+/// must also match. The reference sequences of the first nonempty list are used.
+///
+/// Read groups (`@RG`) and programs (`@PG`) are added in header order. A record that is equal to a
+/// merged record with the same ID is combined with it. Otherwise, a colliding ID is renamed to the
+/// first unused ID of the form `{id}.{n}`, starting at n = 1. Previous program IDs (`PP`) are
+/// renamed with the programs they refer to, which preserves program chains.
+///
+/// The merged sort order (`SO`) is the sort order shared by all headers. If they differ, it is
+/// `unsorted`. The group order (`GO`) and subsort order (`SS`) are kept only if shared by all
+/// headers. Other header (`@HD`) fields are taken from the first header that has one.
+///
+/// Comments (`@CO`) are concatenated, skipping duplicates.
+///
+/// This returns the merged header and, for each input header, the IDs that were renamed. Use
+/// [`Remapping::apply`] to rewrite the records of each input.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{
+///     self as sam,
+///     header::{self, record::value::{map::ReadGroup, Map}},
+/// };
+///
+/// let header = sam::Header::builder()
+///     .add_read_group(Map::<ReadGroup>::builder().set_id("rg0").set_sample("sample0").build()?)
+///     .build();
+///
+/// let other_header = sam::Header::builder()
+///     .add_read_group(Map::<ReadGroup>::builder().set_id("rg0").set_sample("sample1").build()?)
+///     .build();
+///
+/// let (merged_header, remappings) = header::merge(&[header, other_header])?;
+///
+/// assert_eq!(merged_header.read_groups().len(), 2);
+/// assert!(merged_header.read_groups().contains_key("rg0.1"));
+///
+/// assert!(remappings[0].is_empty());
+/// assert_eq!(
+///     remappings[1].read_group_ids().get("rg0").map(|id| id.as_str()),
+///     Some("rg0.1")
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn merge(headers: &[Header]) -> Result<(Header, Vec<Remapping>), Error> {
+    let reference_sequences = merge_reference_sequences(headers)?;
+
+    let mut merged_header = Header::builder()
+        .set_reference_sequences(reference_sequences)
+        .build();
+
+    *merged_header.header_mut() = merge_header_headers(headers);
+
+    let mut remappings = Vec::with_capacity(headers.len());
+
+    for header in headers {
+        let mut remapping = Remapping::default();
+
+        add_read_groups(
+            merged_header.read_groups_mut(),
+            header.read_groups(),
+            &mut remapping,
+        );
+
+        add_programs(
+            merged_header.programs_mut(),
+            header.programs(),
+            &mut remapping,
+        );
+
+        remappings.push(remapping);
+    }
+
+    let comments = merged_header.comments_mut();
+
+    for header in headers {
+        for comment in header.comments() {
+            if !comments.contains(comment) {
+                comments.push(comment.clone());
+            }
+        }
+    }
+
+    Ok((merged_header, remappings))
+}
+
+fn merge_reference_sequences(headers: &[Header]) -> Result<ReferenceSequences, Error> {
+    let mut nonempty_reference_sequences = headers
+        .iter()
+        .map(|header| header.reference_sequences())
+        .enumerate()
+        .filter(|(_, reference_sequences)| !reference_sequences.is_empty());
+
+    let first = match nonempty_reference_sequences.next() {
+        Some((_, reference_sequences)) => reference_sequences,
+        None => return Ok(ReferenceSequences::default()),
+    };
+
+    for (i, reference_sequences) in nonempty_reference_sequences {
+        if !is_compatible(first, reference_sequences) {
+            return Err(Error::IncompatibleReferenceSequences(i));
+        }
+    }
+
+    Ok(first.clone())
+}
+
+fn is_compatible(a: &ReferenceSequences, b: &ReferenceSequences) -> bool {
+    a.len() == b.len()
+        && a.values().zip(b.values()).all(|(a, b)| {
+            let is_md5_checksum_compatible = match (a.md5_checksum(), b.md5_checksum()) {
+                (Some(a_md5_checksum), Some(b_md5_checksum)) => a_md5_checksum == b_md5_checksum,
+                _ => true,
+            };
+
+            a.name() == b.name() && a.length() == b.length() && is_md5_checksum_compatible
+        })
+}
+
+fn merge_header_headers(headers: &[Header]) -> Option<Map<map::Header>> {
+    fn shared<T, F>(headers: &[Header], f: F) -> Option<T>
+    where
+        T: PartialEq,
+        F: Fn(&Map<map::Header>) -> Option<T>,
+    {
+        let mut values = headers.iter().map(|header| header.header().and_then(&f));
+        let first = values.next()??;
+        values
+            .all(|value| value.as_ref() == Some(&first))
+            .then_some(first)
+    }
+
+    let mut merged_header = headers.iter().find_map(|header| header.header())?.clone();
+
+    let sort_order = if headers
+        .iter()
+        .all(|header| header.header().and_then(|hd| hd.sort_order()).is_none())
+    {
+        None
+    } else {
+        shared(headers, |hd| hd.sort_order()).or(Some(SortOrder::Unsorted))
+    };
+
+    *merged_header.sort_order_mut() = sort_order;
+    *merged_header.group_order_mut() = shared(headers, |hd| hd.group_order());
+    *merged_header.subsort_order_mut() = shared(headers, |hd| hd.subsort_order().cloned());
+
+    Some(merged_header)
+}
+
+fn add_read_groups(
+    merged_read_groups: &mut ReadGroups,
+    read_groups: &ReadGroups,
+    remapping: &mut Remapping,
+) {
+    for read_group in read_groups.values() {
+        let id = read_group.id();
+
+        match merged_read_groups.get(id) {
+            None => {
+                merged_read_groups.insert(id.into(), read_group.clone());
+            }
+            Some(merged_read_group) if merged_read_group == read_group => {}
+            Some(_) => {
+                let new_id = next_id(id, |candidate| merged_read_groups.contains_key(candidate));
+
+                let mut read_group = read_group.clone();
+                *read_group.id_mut() = new_id.clone();
+                merged_read_groups.insert(new_id.clone(), read_group);
+
+                remapping.read_group_ids.insert(id.into(), new_id);
+            }
+        }
+    }
+}
+
+fn add_programs(merged_programs: &mut Programs, programs: &Programs, remapping: &mut Remapping) {
+    // Programs are added after the programs they refer to, so their previous program IDs can be
+    // renamed first.
+    let mut ids: HashMap<&str, String> = HashMap::new();
+
+    for program in sort_programs(programs) {
+        let id = program.id();
+
+        let mut program = program.clone();
+
+        if let Some(previous_id) = program.previous_id() {
+            if let Some(new_previous_id) = ids.get(previous_id) {
+                *program.previous_id_mut() = Some(new_previous_id.clone());
+            }
+        }
+
+        let new_id = match merged_programs.get(id) {
+            None => {
+                merged_programs.insert(id.into(), program);
+                id.into()
+            }
+            Some(merged_program) if *merged_program == program => id.into(),
+            Some(_) => {
+                let new_id = next_id(id, |candidate| merged_programs.contains_key(candidate));
+
+                *program.id_mut() = new_id.clone();
+                merged_programs.insert(new_id.clone(), program);
+
+                remapping.program_ids.insert(id.into(), new_id.clone());
+
+                new_id
+            }
+        };
+
+        ids.insert(id, new_id);
+    }
+}
+
+fn sort_programs(programs: &Programs) -> Vec<&Map<Program>> {
+    let mut sorted_programs = Vec::with_capacity(programs.len());
+    let mut visited_ids = HashSet::new();
+
+    loop {
+        let mut is_updated = false;
+
+        for program in programs.values() {
+            if visited_ids.contains(program.id()) {
+                continue;
+            }
+
+            let is_ready = match program.previous_id() {
+                Some(previous_id) => {
+                    visited_ids.contains(previous_id) || !programs.contains_key(previous_id)
+                }
+                None => true,
+            };
+
+            if is_ready {
+                sorted_programs.push(program);
+                visited_ids.insert(program.id());
+                is_updated = true;
+            }
+        }
+
+        if !is_updated {
+            break;
+        }
+    }
+
+    // Programs in a cycle are added in header order.
+    for program in programs.values() {
+        if !visited_ids.contains(program.id()) {
+            sorted_programs.push(program);
+        }
+    }
+
+    sorted_programs
+}
+
+fn next_id<F>(id: &str, is_used: F) -> String
+where
+    F: Fn(&str) -> bool,
+{
+    (1..)
+        .map(|n| format!("{}.{}", id, n))
+        .find(|candidate| !is_used(candidate))
+        .expect("unbounded range")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::record::value::map::{header::Version, ReadGroup, ReferenceSequence};
+
+    fn build_program(
+        id: &str,
+        previous_id: Option<&str>,
+        name: &str,
+    ) -> Result<Map<Program>, map::builder::BuildError> {
+        let mut program = Map::<Program>::builder()
+            .set_id(id)
+            .set_name(name)
+            .build()?;
+        *program.previous_id_mut() = previous_id.map(String::from);
+        Ok(program)
+    }
+
+    #[test]
+    fn test_merge_reference_sequences() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+            .build();
+
+        let headers = [Header::default(), header.clone(), header.clone()];
+        let (merged_header, _) = merge(&headers)?;
+        assert_eq!(
+            merged_header.reference_sequences(),
+            header.reference_sequences()
+        );
+
+        let other_header = Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 13)?)
+            .build();
+
+        assert_eq!(
+            merge(&[header, other_header]),
+            Err(Error::IncompatibleReferenceSequences(1))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_header_headers() {
+        let build_header = |sort_order| {
+            let mut hd = Map::<map::Header>::new(Version::new(1, 6));
+            *hd.sort_order_mut() = sort_order;
+            Header::builder().set_header(hd).build()
+        };
+
+        let headers = [
+            build_header(Some(SortOrder::Coordinate)),
+            build_header(Some(SortOrder::Coordinate)),
+        ];
+        let actual = merge_header_headers(&headers).and_then(|hd| hd.sort_order());
+        assert_eq!(actual, Some(SortOrder::Coordinate));
+
+        let headers = [
+            build_header(Some(SortOrder::Coordinate)),
+            build_header(Some(SortOrder::QueryName)),
+        ];
+        let actual = merge_header_headers(&headers).and_then(|hd| hd.sort_order());
+        assert_eq!(actual, Some(SortOrder::Unsorted));
+
+        let headers = [build_header(Some(SortOrder::Coordinate)), Header::default()];
+        let actual = merge_header_headers(&headers).and_then(|hd| hd.sort_order());
+        assert_eq!(actual, Some(SortOrder::Unsorted));
+
+        let headers = [build_header(None), build_header(None)];
+        let actual = merge_header_headers(&headers).and_then(|hd| hd.sort_order());
+        assert!(actual.is_none());
+
+        assert!(merge_header_headers(&[Header::default()]).is_none());
+    }
+
+    #[test]
+    fn test_merge_read_groups() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_read_group(Map::<ReadGroup>::new("rg0"))
+            .add_read_group(Map::<ReadGroup>::new("rg0.1"))
+            .build();
+
+        let other_header = Header::builder()
+            .add_read_group(Map::<ReadGroup>::new("rg0"))
+            .add_read_group(
+                Map::<ReadGroup>::builder()
+                    .set_id("rg0.1")
+                    .set_sample("sample0")
+                    .build()?,
+            )
+            .build();
+
+        let (merged_header, remappings) = merge(&[header, other_header])?;
+
+        let ids: Vec<_> = merged_header.read_groups().keys().collect();
+        assert_eq!(ids, ["rg0", "rg0.1", "rg0.1.1"]);
+
+        assert!(remappings[0].is_empty());
+        assert_eq!(
+            remappings[1].read_group_ids(),
+            &[(String::from("rg0.1"), String::from("rg0.1.1"))]
+                .into_iter()
+                .collect()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_programs() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_program(build_program("pg0", None, "noodles")?)
+            .add_program(build_program("pg1", Some("pg0"), "noodles")?)
+            .build();
+
+        // pg1 is listed before the program it refers to.
+        let other_header = Header::builder()
+            .add_program(build_program("pg1", Some("pg0"), "noodles")?)
+            .add_program(build_program("pg0", None, "samtools")?)
+            .build();
+
+        let (merged_header, remappings) = merge(&[header, other_header])?;
+
+        let programs: Vec<_> = merged_header
+            .programs()
+            .values()
+            .map(|program| (program.id(), program.previous_id()))
+            .collect();
+
+        assert_eq!(
+            programs,
+            [
+                ("pg0", None),
+                ("pg1", Some("pg0")),
+                ("pg0.1", None),
+                ("pg1.1", Some("pg0.1")),
+            ]
+        );
+
+        assert!(remappings[0].is_empty());
+        assert_eq!(
+            remappings[1].program_ids(),
+            &[
+                (String::from("pg0"), String::from("pg0.1")),
+                (String::from("pg1"), String::from("pg1.1")),
+            ]
+            .into_iter()
+            .collect()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_comments() -> Result<(), Error> {
+        let header = Header::builder().add_comment("a").add_comment("b").build();
+        let other_header = Header::builder().add_comment("b").add_comment("c").build();
+        let (merged_header, _) = merge(&[header, other_header])?;
+        assert_eq!(merged_header.comments(), ["a", "b", "c"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply() -> Result<(), Box<dyn std::error::Error>> {
+        let remapping = Remapping {
+            read_group_ids: [(String::from("rg0"), String::from("rg0.1"))]
+                .into_iter()
+                .collect(),
+            program_ids: [(String::from("pg0"), String::from("pg0.1"))]
+                .into_iter()
+                .collect(),
+        };
+
+        let mut record = Record::builder()
+            .set_data("RG:Z:rg0\tPG:Z:pg0\tNM:i:0".parse()?)
+            .build();
+
+        remapping.apply(&mut record);
+
+        assert_eq!(record.data(), &"RG:Z:rg0.1\tPG:Z:pg0.1\tNM:i:0".parse()?);
+
+        let mut record = Record::builder().set_data("RG:Z:rg1".parse()?).build();
+        remapping.apply(&mut record);
+        assert_eq!(record.data(), &"RG:Z:rg1".parse()?);
+
+        Ok(())
+    }
+}
@@ -122,6 +122,20 @@ impl Map<Program> {
         self.inner.previous_id.as_deref()
     }
 
+    /// Returns a mutable reference to the previous program ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::header::record::value::{map::Program, Map};
+    /// let mut program = Map::<Program>::new("pg1");
+    /// *program.previous_id_mut() = Some(String::from("pg0"));
+    /// assert_eq!(program.previous_id(), Some("pg0"));
+    /// ```
+    pub fn previous_id_mut(&mut self) -> &mut Option<String> {
+        &mut self.inner.previous_id
+    }
+
     /// Returns the description.
     ///
     /// # Examples
@@ -10,6 +10,8 @@ mod tag;
 
 use std::{error, fmt, num::NonZeroUsize};
 
+use noodles_core::sequence_dictionary::Sequence;
+
 pub use self::{
     alternative_locus::AlternativeLocus, alternative_names::AlternativeNames,
     md5_checksum::Md5Checksum, molecule_topology::MoleculeTopology, name::Name,
@@ -426,6 +428,92 @@ impl TryFrom<Fields> for Map<ReferenceSequence> {
     }
 }
 
+impl From<&Map<ReferenceSequence>> for Sequence {
+    fn from(reference_sequence: &Map<ReferenceSequence>) -> Self {
+        let mut sequence = Self::new(
+            reference_sequence.name().to_string(),
+            Some(usize::from(reference_sequence.length())),
+        );
+
+        *sequence.md5_checksum_mut() = reference_sequence.md5_checksum().map(<[u8; 16]>::from);
+
+        if let Some(alternative_names) = reference_sequence.alternative_names() {
+            *sequence.alternative_names_mut() = alternative_names.to_vec();
+        }
+
+        sequence
+    }
+}
+
+/// An error returned when a sequence dictionary sequence fails to convert to a SAM header
+/// reference sequence.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TryFromSequenceError {
+    /// The name is invalid.
+    InvalidName(name::ParseError),
+    /// The length is missing.
+    MissingLength,
+    /// The length is invalid.
+    InvalidLength(usize),
+    /// The alternative names are invalid.
+    InvalidAlternativeNames(alternative_names::ParseError),
+}
+
+impl error::Error for TryFromSequenceError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidName(e) => Some(e),
+            Self::InvalidAlternativeNames(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TryFromSequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidName(_) => f.write_str("invalid name"),
+            Self::MissingLength => f.write_str("missing length"),
+            Self::InvalidLength(length) => write!(f, "invalid length: {}", length),
+            Self::InvalidAlternativeNames(_) => f.write_str("invalid alternative names"),
+        }
+    }
+}
+
+impl TryFrom<&Sequence> for Map<ReferenceSequence> {
+    type Error = TryFromSequenceError;
+
+    fn try_from(sequence: &Sequence) -> Result<Self, Self::Error> {
+        let name = sequence
+            .name()
+            .parse()
+            .map_err(TryFromSequenceError::InvalidName)?;
+
+        let length = sequence
+            .length()
+            .ok_or(TryFromSequenceError::MissingLength)?;
+
+        let mut reference_sequence = Self::new(name, length).map_err(|e| match e {
+            NewError::InvalidName => TryFromSequenceError::InvalidName(name::ParseError::Invalid),
+            NewError::InvalidLength(n) => TryFromSequenceError::InvalidLength(n),
+        })?;
+
+        *reference_sequence.md5_checksum_mut() = sequence.md5_checksum().map(Md5Checksum::from);
+
+        if !sequence.alternative_names().is_empty() {
+            let alternative_names = sequence
+                .alternative_names()
+                .join(",")
+                .parse()
+                .map_err(TryFromSequenceError::InvalidAlternativeNames)?;
+
+            reference_sequence.inner.alternative_names = Some(alternative_names);
+        }
+
+        Ok(reference_sequence)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -517,4 +605,45 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_sequence_conversions() -> Result<(), Box<dyn std::error::Error>> {
+        let md5_checksum: Md5Checksum = "d7eba311421bbc9d3ada44709dd61534".parse()?;
+
+        let reference_sequence = Map::<ReferenceSequence>::builder()
+            .set_name("chr1".parse()?)
+            .set_length(13)
+            .set_md5_checksum(md5_checksum)
+            .set_alternative_names("1,NC_000001".parse()?)
+            .build()?;
+
+        let sequence = Sequence::from(&reference_sequence);
+        assert_eq!(sequence.name(), "chr1");
+        assert_eq!(sequence.length(), Some(13));
+        assert_eq!(
+            sequence.md5_checksum(),
+            Some(<[u8; 16]>::from(md5_checksum))
+        );
+        assert_eq!(sequence.alternative_names(), ["1", "NC_000001"]);
+
+        assert_eq!(
+            Map::<ReferenceSequence>::try_from(&sequence)?,
+            reference_sequence
+        );
+
+        assert_eq!(
+            Map::<ReferenceSequence>::try_from(&Sequence::new("sq0", None)),
+            Err(TryFromSequenceError::MissingLength)
+        );
+        assert_eq!(
+            Map::<ReferenceSequence>::try_from(&Sequence::new("sq0", Some(0))),
+            Err(TryFromSequenceError::InvalidLength(0))
+        );
+        assert!(matches!(
+            Map::<ReferenceSequence>::try_from(&Sequence::new("*", Some(13))),
+            Err(TryFromSequenceError::InvalidName(_))
+        ));
+
+        Ok(())
+    }
 }
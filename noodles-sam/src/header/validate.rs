@@ -0,0 +1,493 @@
+//! SAM header validation.
+
+use std::{
+    collections::{HashMap, HashSet},
+    error, fmt,
+};
+
+use super::{
+    record::value::map::header::{GroupOrder, SortOrder, SubsortOrder},
+    Header,
+};
+
+/// A SAM header validation error.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// A reference sequence's name (`SN`) does not match its key in the header.
+    ReferenceSequenceNameMismatch {
+        /// The key.
+        key: String,
+        /// The reference sequence name.
+        name: String,
+    },
+    /// A reference sequence name or alternative name is used more than once.
+    DuplicateReferenceSequenceName(String),
+    /// A read group's ID does not match its key in the header.
+    ReadGroupIdMismatch {
+        /// The key.
+        key: String,
+        /// The read group ID.
+        id: String,
+    },
+    /// A read group ID is used more than once.
+    DuplicateReadGroupId(String),
+    /// A program's ID does not match its key in the header.
+    ProgramIdMismatch {
+        /// The key.
+        key: String,
+        /// The program ID.
+        id: String,
+    },
+    /// A program ID is used more than once.
+    DuplicateProgramId(String),
+    /// A program's previous program ID (`PP`) is not a program ID.
+    MissingPreviousProgram {
+        /// The program ID.
+        id: String,
+        /// The previous program ID.
+        previous_id: String,
+    },
+    /// A program's chain of previous program IDs (`PP`) loops back on itself.
+    ///
+    /// This is reported once per cycle, for the first program visited in it.
+    ProgramCycle(String),
+    /// The primary order of the subsort order (`SS`) does not match the sort order (`SO`).
+    InvalidSubsortOrder {
+        /// The sort order, if set.
+        sort_order: Option<SortOrder>,
+        /// The subsort order.
+        subsort_order: SubsortOrder,
+    },
+    /// The group order (`GO`) contradicts the sort order (`SO`).
+    InvalidGroupOrder {
+        /// The sort order.
+        sort_order: SortOrder,
+        /// The group order.
+        group_order: GroupOrder,
+    },
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReferenceSequenceNameMismatch { key, name } => write!(
+                f,
+                "reference sequence name ({}) does not match its key ({})",
+                name, key
+            ),
+            Self::DuplicateReferenceSequenceName(name) => {
+                write!(f, "duplicate reference sequence name: {}", name)
+            }
+            Self::ReadGroupIdMismatch { key, id } => {
+                write!(f, "read group ID ({}) does not match its key ({})", id, key)
+            }
+            Self::DuplicateReadGroupId(id) => write!(f, "duplicate read group ID: {}", id),
+            Self::ProgramIdMismatch { key, id } => {
+                write!(f, "program ID ({}) does not match its key ({})", id, key)
+            }
+            Self::DuplicateProgramId(id) => write!(f, "duplicate program ID: {}", id),
+            Self::MissingPreviousProgram { id, previous_id } => write!(
+                f,
+                "program {} has a previous program ID ({}) that is not in the header",
+                id, previous_id
+            ),
+            Self::ProgramCycle(id) => write!(f, "program chain cycle at {}", id),
+            Self::InvalidSubsortOrder {
+                sort_order,
+                subsort_order,
+            } => match sort_order {
+                Some(sort_order) => write!(
+                    f,
+                    "subsort order ({}) does not match sort order ({})",
+                    subsort_order, sort_order
+                ),
+                None => write!(
+                    f,
+                    "subsort order ({}) is set without a sort order",
+                    subsort_order
+                ),
+            },
+            Self::InvalidGroupOrder {
+                sort_order,
+                group_order,
+            } => write!(
+                f,
+                "group order ({}) contradicts sort order ({})",
+                group_order, sort_order
+            ),
+        }
+    }
+}
+
+/// Validates a SAM header.
+///
+/// This checks the semantics of a header as a whole, which parsing a header record by record does
+/// not, i.e.,
+///
+///   * each reference sequence name (`SN`), read group ID, and program ID matches its key in the
+///     header;
+///   * reference sequence names (`SN`) and alternative names (`AN`) are unique;
+///   * read group and program IDs are unique;
+///   * each program's previous program ID (`PP`) refers to a program in the header, and program
+///     chains are acyclic; and
+///   * the subsort order (`SS`) and group order (`GO`) agree with the sort order (`SO`).
+///
+/// Reference sequence names and alternative names are not checked against the allowed character
+/// set, and read group platforms (`PL`) are not checked against the controlled vocabulary. Their
+/// types, [`ReferenceSequenceName`], [`AlternativeNames`], and [`Platform`], can only be created
+/// by parsing, which already rejects invalid values.
+///
+/// [`ReferenceSequenceName`]: crate::record::ReferenceSequenceName
+/// [`AlternativeNames`]: super::record::value::map::reference_sequence::AlternativeNames
+/// [`Platform`]: super::record::value::map::read_group::Platform
+///
+/// All errors are returned. The header is valid if the list is empty.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{
+///     self as sam,
+///     header::{self, record::value::{map::Program, Map}},
+/// };
+///
+/// let header = sam::Header::builder()
+///     .add_program(Map::<Program>::builder().set_id("pg0").set_previous_id("pg1").build()?)
+///     .build();
+///
+/// assert_eq!(
+///     header::validate(&header),
+///     [header::validate::Error::MissingPreviousProgram {
+///         id: String::from("pg0"),
+///         previous_id: String::from("pg1"),
+///     }]
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn validate(header: &Header) -> Vec<Error> {
+    let mut errors = Vec::new();
+
+    validate_header(header, &mut errors);
+    validate_reference_sequences(header, &mut errors);
+    validate_read_groups(header, &mut errors);
+    validate_programs(header, &mut errors);
+
+    errors
+}
+
+fn validate_header(header: &Header, errors: &mut Vec<Error>) {
+    let hd = match header.header() {
+        Some(hd) => hd,
+        None => return,
+    };
+
+    let sort_order = hd.sort_order();
+
+    if let Some(subsort_order) = hd.subsort_order() {
+        let primary_order = match subsort_order {
+            SubsortOrder::Unsorted(_) => SortOrder::Unsorted,
+            SubsortOrder::QueryName(_) => SortOrder::QueryName,
+            SubsortOrder::Coordinate(_) => SortOrder::Coordinate,
+        };
+
+        if sort_order != Some(primary_order) {
+            errors.push(Error::InvalidSubsortOrder {
+                sort_order,
+                subsort_order: subsort_order.clone(),
+            });
+        }
+    }
+
+    if let (Some(sort_order), Some(group_order)) = (sort_order, hd.group_order()) {
+        let is_contradictory = matches!(
+            (sort_order, group_order),
+            (SortOrder::Coordinate, GroupOrder::Query)
+                | (SortOrder::QueryName, GroupOrder::Reference)
+        );
+
+        if is_contradictory {
+            errors.push(Error::InvalidGroupOrder {
+                sort_order,
+                group_order,
+            });
+        }
+    }
+}
+
+fn validate_reference_sequences(header: &Header, errors: &mut Vec<Error>) {
+    let mut names = HashSet::new();
+
+    for (key, reference_sequence) in header.reference_sequences() {
+        if reference_sequence.name().as_str() != key {
+            errors.push(Error::ReferenceSequenceNameMismatch {
+                key: key.clone(),
+                name: reference_sequence.name().to_string(),
+            });
+        }
+
+        let alternative_names = reference_sequence
+            .alternative_names()
+            .map(|names| &names[..])
+            .unwrap_or_default();
+
+        for name in std::iter::once(&**reference_sequence.name()).chain(alternative_names.iter()) {
+            if !names.insert(name.as_str()) {
+                errors.push(Error::DuplicateReferenceSequenceName(name.clone()));
+            }
+        }
+    }
+}
+
+fn validate_read_groups(header: &Header, errors: &mut Vec<Error>) {
+    let mut ids = HashSet::new();
+
+    for (key, read_group) in header.read_groups() {
+        if read_group.id() != key {
+            errors.push(Error::ReadGroupIdMismatch {
+                key: key.clone(),
+                id: read_group.id().into(),
+            });
+        }
+
+        if !ids.insert(read_group.id()) {
+            errors.push(Error::DuplicateReadGroupId(read_group.id().into()));
+        }
+    }
+}
+
+fn validate_programs(header: &Header, errors: &mut Vec<Error>) {
+    let mut previous_ids = HashMap::new();
+
+    for (key, program) in header.programs() {
+        if program.id() != key {
+            errors.push(Error::ProgramIdMismatch {
+                key: key.clone(),
+                id: program.id().into(),
+            });
+        }
+
+        if previous_ids.contains_key(program.id()) {
+            errors.push(Error::DuplicateProgramId(program.id().into()));
+        } else {
+            previous_ids.insert(program.id(), program.previous_id());
+        }
+    }
+
+    for program in header.programs().values() {
+        if let Some(previous_id) = program.previous_id() {
+            if !previous_ids.contains_key(previous_id) {
+                errors.push(Error::MissingPreviousProgram {
+                    id: program.id().into(),
+                    previous_id: previous_id.into(),
+                });
+            }
+        }
+    }
+
+    // Each program is visited once. A walk that reaches a program on its own path has found a
+    // cycle; one that reaches a program from an earlier walk has not.
+    let mut visited: HashMap<&str, usize> = HashMap::new();
+
+    for (i, program) in header.programs().values().enumerate() {
+        let mut id = program.id();
+
+        loop {
+            if let Some(&j) = visited.get(id) {
+                if j == i {
+                    errors.push(Error::ProgramCycle(id.into()));
+                }
+
+                break;
+            }
+
+            visited.insert(id, i);
+
+            match previous_ids.get(id).copied().flatten() {
+                Some(previous_id) if previous_ids.contains_key(previous_id) => id = previous_id,
+                _ => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::record::value::{
+        map::{self, Program, ReadGroup, ReferenceSequence},
+        Map,
+    };
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+            .add_read_group(Map::<ReadGroup>::new("rg0"))
+            .add_program(Map::<Program>::new("pg0"))
+            .add_program(
+                Map::<Program>::builder()
+                    .set_id("pg1")
+                    .set_previous_id("pg0")
+                    .build()?,
+            )
+            .build();
+
+        assert!(validate(&header).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_header() -> Result<(), Box<dyn std::error::Error>> {
+        use map::header::Version;
+
+        let mut hd = Map::<map::Header>::new(Version::new(1, 6));
+        *hd.sort_order_mut() = Some(SortOrder::QueryName);
+        *hd.subsort_order_mut() = Some("coordinate:MI".parse()?);
+        *hd.group_order_mut() = Some(GroupOrder::Reference);
+
+        let header = Header::builder().set_header(hd).build();
+
+        assert_eq!(
+            validate(&header),
+            [
+                Error::InvalidSubsortOrder {
+                    sort_order: Some(SortOrder::QueryName),
+                    subsort_order: "coordinate:MI".parse()?,
+                },
+                Error::InvalidGroupOrder {
+                    sort_order: SortOrder::QueryName,
+                    group_order: GroupOrder::Reference,
+                },
+            ]
+        );
+
+        let mut hd = Map::<map::Header>::new(Version::new(1, 6));
+        *hd.subsort_order_mut() = Some("queryname:MI".parse()?);
+        let header = Header::builder().set_header(hd).build();
+
+        assert_eq!(
+            validate(&header),
+            [Error::InvalidSubsortOrder {
+                sort_order: None,
+                subsort_order: "queryname:MI".parse()?,
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_reference_sequences() -> Result<(), Box<dyn std::error::Error>> {
+        let mut header = Header::builder()
+            .add_reference_sequence(
+                Map::<ReferenceSequence>::builder()
+                    .set_name("sq0".parse()?)
+                    .set_length(8)
+                    .set_alternative_names("0,sq1".parse()?)
+                    .build()?,
+            )
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq1".parse()?, 13)?)
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq2".parse()?, 21)?)
+            .build();
+
+        if let Some(reference_sequence) = header.reference_sequences_mut().get_mut("sq2") {
+            *reference_sequence.name_mut() = "sq0".parse()?;
+        }
+
+        assert_eq!(
+            validate(&header),
+            [
+                Error::DuplicateReferenceSequenceName(String::from("sq1")),
+                Error::ReferenceSequenceNameMismatch {
+                    key: String::from("sq2"),
+                    name: String::from("sq0"),
+                },
+                Error::DuplicateReferenceSequenceName(String::from("sq0")),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_read_groups() {
+        let mut header = Header::builder()
+            .add_read_group(Map::<ReadGroup>::new("rg0"))
+            .add_read_group(Map::<ReadGroup>::new("rg1"))
+            .build();
+
+        if let Some(read_group) = header.read_groups_mut().get_mut("rg1") {
+            *read_group.id_mut() = String::from("rg0");
+        }
+
+        assert_eq!(
+            validate(&header),
+            [
+                Error::ReadGroupIdMismatch {
+                    key: String::from("rg1"),
+                    id: String::from("rg0"),
+                },
+                Error::DuplicateReadGroupId(String::from("rg0")),
+            ]
+        );
+
+        if let Some(read_group) = header.read_groups_mut().get_mut("rg1") {
+            *read_group.id_mut() = String::from("rg2");
+        }
+
+        assert_eq!(
+            validate(&header),
+            [Error::ReadGroupIdMismatch {
+                key: String::from("rg1"),
+                id: String::from("rg2"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validate_programs() -> Result<(), Box<dyn std::error::Error>> {
+        fn build_program(
+            id: &str,
+            previous_id: &str,
+        ) -> Result<Map<Program>, map::builder::BuildError> {
+            Map::<Program>::builder()
+                .set_id(id)
+                .set_previous_id(previous_id)
+                .build()
+        }
+
+        let mut header = Header::builder()
+            .add_program(build_program("pg0", "pg2")?)
+            .add_program(build_program("pg1", "pg0")?)
+            .add_program(build_program("pg2", "pg1")?)
+            .add_program(build_program("pg3", "pg2")?)
+            .add_program(build_program("pg4", "pg5")?)
+            .add_program(Map::<Program>::new("pg6"))
+            .build();
+
+        if let Some(program) = header.programs_mut().get_mut("pg6") {
+            *program.id_mut() = String::from("pg3");
+        }
+
+        assert_eq!(
+            validate(&header),
+            [
+                Error::ProgramIdMismatch {
+                    key: String::from("pg6"),
+                    id: String::from("pg3"),
+                },
+                Error::DuplicateProgramId(String::from("pg3")),
+                Error::MissingPreviousProgram {
+                    id: String::from("pg4"),
+                    previous_id: String::from("pg5"),
+                },
+                Error::ProgramCycle(String::from("pg0")),
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+//! Builds and writes a tabix index from a bgzipped tab-delimited file.
+//!
+//! The preset is one of `bed`, `gff`, `sam`, or `vcf` (default: `bed`). This writes the output to
+//! stdout rather than `<src>.tbi`.
+//!
+//! The output is similar to the output of `tabix --preset <preset> <src>`.
+
+use std::{env, io};
+
+use noodles_tabix::{self as tabix, index::header::Builder};
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let preset = args.next();
+
+    let header = match preset.as_deref() {
+        None | Some("bed") => Builder::bed(),
+        Some("gff") => Builder::gff(),
+        Some("sam") => Builder::sam(),
+        Some("vcf") => Builder::vcf(),
+        Some(s) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid preset: {}", s),
+            ))
+        }
+    }
+    .build();
+
+    let index = tabix::index(src, header)?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = tabix::Writer::new(stdout);
+
+    writer.write_index(&index)?;
+
+    Ok(())
+}
@@ -3,10 +3,13 @@
 pub mod builder;
 pub mod header;
 mod indexer;
+mod merge;
+pub mod record;
 pub mod reference_sequence;
 
 pub use self::{
-    builder::Builder, header::Header, indexer::Indexer, reference_sequence::ReferenceSequence,
+    builder::Builder, header::Header, indexer::Indexer, merge::merge,
+    reference_sequence::ReferenceSequence,
 };
 
 #[deprecated(
@@ -25,7 +28,7 @@ use noodles_csi::{
 const MIN_SHIFT: u8 = 14;
 const DEPTH: u8 = 5;
 
-const MAX_POSITION: Position = match Position::new((1 << (MIN_SHIFT + 3 * DEPTH)) - 1) {
+pub(crate) const MAX_POSITION: Position = match Position::new((1 << (MIN_SHIFT + 3 * DEPTH)) - 1) {
     Some(position) => position,
     None => panic!(),
 };
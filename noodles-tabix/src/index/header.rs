@@ -6,6 +6,7 @@ pub mod format;
 pub use self::{builder::Builder, format::Format};
 
 use indexmap::IndexSet;
+use noodles_core::{sequence_dictionary::Sequence, SequenceDictionary};
 
 /// An ordered set of reference sequence names.
 pub type ReferenceSequenceNames = IndexSet<String>;
@@ -169,3 +170,36 @@ impl Default for Header {
         Builder::default().build()
     }
 }
+
+impl From<&Header> for SequenceDictionary {
+    /// Converts the reference sequence names of a tabix index header to a sequence dictionary.
+    ///
+    /// A tabix index does not store reference sequence lengths, so the lengths of the sequences
+    /// are not set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::SequenceDictionary;
+    /// use noodles_tabix::{self as tabix, index::header::ReferenceSequenceNames};
+    ///
+    /// let reference_sequence_names: ReferenceSequenceNames =
+    ///     [String::from("sq0"), String::from("sq1")].into_iter().collect();
+    ///
+    /// let header = tabix::index::Header::builder()
+    ///     .set_reference_sequence_names(reference_sequence_names)
+    ///     .build();
+    ///
+    /// let dictionary = SequenceDictionary::from(&header);
+    ///
+    /// assert_eq!(dictionary.get_index_of("sq1"), Some(1));
+    /// assert!(dictionary.get("sq1").and_then(|sequence| sequence.length()).is_none());
+    /// ```
+    fn from(header: &Header) -> Self {
+        header
+            .reference_sequence_names()
+            .iter()
+            .map(|name| Sequence::new(name.as_str(), None))
+            .collect()
+    }
+}
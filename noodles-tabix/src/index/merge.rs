@@ -0,0 +1,324 @@
+use std::{cmp, collections::BTreeMap, io};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{
+    binning_index::{
+        merge::{merge_metadata, shift, shift_chunks, sum_counts},
+        ReferenceSequenceExt,
+    },
+    index::reference_sequence::bin::Chunk,
+    BinningIndex,
+};
+
+use super::{header::ReferenceSequenceNames, reference_sequence::Bin, Index, ReferenceSequence};
+
+/// Merges tabix indexes of BGZF streams that were concatenated.
+///
+/// `offsets` is the compressed position at which each indexed stream starts in the concatenated
+/// stream, e.g., as returned by [`bgzf::concat`]. Chunk, linear index, and metadata virtual
+/// positions are shifted by their stream's offset, and record counts are summed.
+///
+/// The indexes must be given in the order their streams were concatenated and have the same
+/// header fields, except for reference sequence names. Reference sequences are matched by name,
+/// and the merged reference sequence names are in order of first appearance (see
+/// [`noodles_csi::binning_index::merge`]).
+///
+/// Unset linear index entries are ignored when merging the linear indices.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_tabix as tabix;
+///
+/// let indexes = [tabix::Index::default(), tabix::Index::default()];
+/// let index = tabix::index::merge(&indexes, &[0, 8])?;
+///
+/// assert!(index.header().reference_sequence_names().is_empty());
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn merge(indexes: &[Index], offsets: &[u64]) -> io::Result<Index> {
+    if indexes.len() != offsets.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "index and offset counts mismatch",
+        ));
+    }
+
+    let first = match indexes.first() {
+        Some(index) => index,
+        None => return Ok(Index::default()),
+    };
+
+    let mut reference_sequence_names = ReferenceSequenceNames::new();
+
+    for index in indexes {
+        if !is_header_compatible(index, first) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "index header mismatch",
+            ));
+        }
+
+        for name in index.header().reference_sequence_names() {
+            reference_sequence_names.insert(name.clone());
+        }
+    }
+
+    let mut shards = vec![Vec::new(); reference_sequence_names.len()];
+
+    for (index, &offset) in indexes.iter().zip(offsets) {
+        let names = index.header().reference_sequence_names();
+
+        for (name, reference_sequence) in names.iter().zip(index.reference_sequences()) {
+            // SAFETY: `reference_sequence_names` contains all names of all indexes.
+            let i = reference_sequence_names.get_index_of(name).unwrap();
+            shards[i].push((reference_sequence, offset));
+        }
+    }
+
+    let reference_sequences = shards
+        .into_iter()
+        .map(|s| merge_reference_sequences(s.into_iter()))
+        .collect::<io::Result<_>>()?;
+
+    let mut header = first.header().clone();
+    header.reference_sequence_names = reference_sequence_names;
+
+    let mut builder = Index::builder()
+        .set_header(header)
+        .set_reference_sequences(reference_sequences);
+
+    if let Some(n) = sum_counts(
+        indexes
+            .iter()
+            .map(|index| index.unplaced_unmapped_record_count()),
+    ) {
+        builder = builder.set_unplaced_unmapped_record_count(n);
+    }
+
+    Ok(builder.build())
+}
+
+fn is_header_compatible(a: &Index, b: &Index) -> bool {
+    let (a, b) = (a.header(), b.header());
+
+    a.format() == b.format()
+        && a.reference_sequence_name_index() == b.reference_sequence_name_index()
+        && a.start_position_index() == b.start_position_index()
+        && a.end_position_index() == b.end_position_index()
+        && a.line_comment_prefix() == b.line_comment_prefix()
+        && a.line_skip_count() == b.line_skip_count()
+}
+
+fn merge_reference_sequences<'a, I>(shards: I) -> io::Result<ReferenceSequence>
+where
+    I: Iterator<Item = (&'a ReferenceSequence, u64)>,
+{
+    let mut bins: BTreeMap<usize, Vec<Chunk>> = BTreeMap::new();
+    let mut intervals: Vec<Option<bgzf::VirtualPosition>> = Vec::new();
+    let mut metadata = None;
+
+    for (reference_sequence, offset) in shards {
+        // An empty reference sequence has no linear index entries that are set.
+        if reference_sequence.bins().is_empty() {
+            continue;
+        }
+
+        for bin in reference_sequence.bins() {
+            let chunks = shift_chunks(bin.chunks(), offset)?;
+            bins.entry(bin.id()).or_default().extend(chunks);
+        }
+
+        if intervals.len() < reference_sequence.intervals().len() {
+            intervals.resize(reference_sequence.intervals().len(), None);
+        }
+
+        let min_start = min_chunk_start(reference_sequence);
+
+        for (merged_interval, &interval) in intervals.iter_mut().zip(reference_sequence.intervals())
+        {
+            // Unset linear index entries are 0. They are dropped rather than shifted to the start
+            // of the stream, which would lower the minimum of the windows that are set in later
+            // streams.
+            if is_unset(interval, min_start) {
+                continue;
+            }
+
+            let interval = shift(interval, offset)?;
+
+            *merged_interval = Some(match *merged_interval {
+                Some(m) => cmp::min(m, interval),
+                None => interval,
+            });
+        }
+
+        if let Some(m) = reference_sequence.metadata() {
+            metadata = Some(merge_metadata(metadata, m, offset)?);
+        }
+    }
+
+    let bins = bins
+        .into_iter()
+        .map(|(id, chunks)| Bin::new(id, chunks))
+        .collect();
+
+    let intervals = intervals
+        .into_iter()
+        .map(|interval| interval.unwrap_or_default())
+        .collect();
+
+    Ok(ReferenceSequence::new(bins, intervals, metadata))
+}
+
+fn min_chunk_start(reference_sequence: &ReferenceSequence) -> Option<bgzf::VirtualPosition> {
+    reference_sequence
+        .bins()
+        .iter()
+        .flat_map(|bin| bin.chunks())
+        .map(|chunk| chunk.start())
+        .min()
+}
+
+// A linear index entry of 0 is only a record position when a chunk starts at the start of the
+// stream, i.e., the stream has no header. Otherwise, it is unset.
+fn is_unset(
+    interval: bgzf::VirtualPosition,
+    min_chunk_start: Option<bgzf::VirtualPosition>,
+) -> bool {
+    let start = bgzf::VirtualPosition::default();
+    interval == start && min_chunk_start != Some(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_csi::index::reference_sequence::Metadata;
+
+    use super::*;
+    use crate::index::Header;
+
+    fn vp(compressed: u64, uncompressed: u16) -> bgzf::VirtualPosition {
+        bgzf::VirtualPosition::try_from((compressed, uncompressed)).unwrap()
+    }
+
+    fn build_index(names: &[&str], end: u64) -> Index {
+        let reference_sequence_names = names.iter().map(|name| name.to_string()).collect();
+
+        let reference_sequences = names
+            .iter()
+            .map(|_| {
+                ReferenceSequence::new(
+                    vec![Bin::new(4681, vec![Chunk::new(vp(0, 5), vp(end, 0))])],
+                    vec![vp(0, 5)],
+                    Some(Metadata::new(vp(0, 5), vp(end, 0), 1, 0)),
+                )
+            })
+            .collect();
+
+        Index::builder()
+            .set_header(
+                Header::builder()
+                    .set_reference_sequence_names(reference_sequence_names)
+                    .build(),
+            )
+            .set_reference_sequences(reference_sequences)
+            .build()
+    }
+
+    #[test]
+    fn test_merge() -> io::Result<()> {
+        let indexes = [build_index(&["sq0"], 8), build_index(&["sq1", "sq0"], 5)];
+        let index = merge(&indexes, &[0, 8])?;
+
+        let expected_names: ReferenceSequenceNames = [String::from("sq0"), String::from("sq1")]
+            .into_iter()
+            .collect();
+        assert_eq!(index.header().reference_sequence_names(), &expected_names);
+
+        let reference_sequences = index.reference_sequences();
+        assert_eq!(reference_sequences.len(), 2);
+
+        assert_eq!(
+            reference_sequences[0],
+            ReferenceSequence::new(
+                vec![Bin::new(
+                    4681,
+                    vec![
+                        Chunk::new(vp(0, 5), vp(8, 0)),
+                        Chunk::new(vp(8, 5), vp(13, 0))
+                    ]
+                )],
+                vec![vp(0, 5)],
+                Some(Metadata::new(vp(0, 5), vp(13, 0), 2, 0)),
+            )
+        );
+
+        assert_eq!(
+            reference_sequences[1],
+            ReferenceSequence::new(
+                vec![Bin::new(4681, vec![Chunk::new(vp(8, 5), vp(13, 0))])],
+                vec![vp(8, 5)],
+                Some(Metadata::new(vp(8, 5), vp(13, 0), 1, 0)),
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_with_unset_intervals() -> io::Result<()> {
+        let build_reference_sequence = |intervals, end| {
+            ReferenceSequence::new(
+                vec![Bin::new(4681, vec![Chunk::new(vp(0, 5), vp(end, 0))])],
+                intervals,
+                None,
+            )
+        };
+
+        let header = Header::builder()
+            .set_reference_sequence_names([String::from("sq0")].into_iter().collect())
+            .build();
+
+        let indexes = [
+            Index::builder()
+                .set_header(header.clone())
+                .set_reference_sequences(vec![build_reference_sequence(
+                    vec![vp(0, 5), bgzf::VirtualPosition::default()],
+                    8,
+                )])
+                .build(),
+            Index::builder()
+                .set_header(header)
+                .set_reference_sequences(vec![build_reference_sequence(
+                    vec![vp(0, 5), vp(3, 0)],
+                    5,
+                )])
+                .build(),
+        ];
+
+        let index = merge(&indexes, &[0, 8])?;
+
+        assert_eq!(
+            index.reference_sequences()[0].intervals(),
+            [vp(0, 5), vp(11, 0)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_with_mismatched_headers() {
+        let indexes = [
+            Index::builder()
+                .set_header(Header::builder().build())
+                .build(),
+            Index::builder()
+                .set_header(Header::builder().set_line_skip_count(1).build())
+                .build(),
+        ];
+
+        assert!(matches!(
+            merge(&indexes, &[0, 8]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+}
@@ -0,0 +1,272 @@
+//! Tabix-indexed record interval parsing.
+
+use std::io;
+
+use noodles_core::{
+    position::ZeroBasedPosition,
+    region::{HalfOpenInterval, Interval},
+    Position,
+};
+
+use super::{
+    header::{format::CoordinateSystem, Format},
+    Header,
+};
+
+const DELIMITER: char = '\t';
+
+// VCF columns (1-based).
+const VCF_REFERENCE_BASES_INDEX: usize = 4;
+const VCF_INFO_INDEX: usize = 8;
+
+// SAM columns (1-based).
+const SAM_CIGAR_INDEX: usize = 6;
+
+/// Returns whether a line is a header or comment line and not a record.
+///
+/// `line_number` is 1-based.
+pub(crate) fn is_skipped(header: &Header, line_number: u64, line: &str) -> bool {
+    line_number <= u64::from(header.line_skip_count())
+        || line.as_bytes().first() == Some(&header.line_comment_prefix())
+}
+
+/// Parses the reference sequence name and 0-based, half-open interval of a record.
+///
+/// The columns and coordinate system are given by the index header. With
+/// [`CoordinateSystem::Bed`], the start and end columns are read as 0-based, half-open
+/// coordinates. With [`CoordinateSystem::Gff`], they are read as 1-based, closed coordinates and
+/// converted. When the header has no end column, the end is derived from the format.
+///
+/// The line must be a record and not a header or comment line.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::{position::ZeroBasedPosition, region::HalfOpenInterval};
+/// use noodles_tabix::index::{header, record::parse_interval};
+///
+/// let header = header::Builder::bed().build();
+/// let (name, interval) = parse_interval(&header, "sq0\t7\t13")?;
+///
+/// assert_eq!(name, "sq0");
+/// assert_eq!(
+///     interval,
+///     HalfOpenInterval::new(ZeroBasedPosition::try_from(7)?, ZeroBasedPosition::try_from(13)?)
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn parse_interval<'a>(
+    header: &Header,
+    line: &'a str,
+) -> io::Result<(&'a str, HalfOpenInterval)> {
+    let fields: Vec<_> = line.split(DELIMITER).collect();
+
+    let reference_sequence_name = get_field(&fields, header.reference_sequence_name_index())?;
+
+    let coordinate_system = header.format().coordinate_system();
+    let raw_start = get_field(&fields, header.start_position_index()).and_then(parse_int)?;
+
+    let start = match coordinate_system {
+        CoordinateSystem::Bed => ZeroBasedPosition::new(raw_start),
+        CoordinateSystem::Gff => Position::new(raw_start).map(ZeroBasedPosition::from),
+    }
+    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid start position"))?;
+
+    // A 0-based, exclusive end and a 1-based, inclusive end have the same value.
+    let raw_end = match header.end_position_index() {
+        Some(i) => get_field(&fields, i).and_then(parse_int)?,
+        None => match header.format() {
+            Format::Vcf => parse_vcf_end(&fields, Position::from(start))?,
+            Format::Sam => parse_sam_end(&fields, Position::from(start))?,
+            Format::Generic(_) => usize::from(Position::from(start)),
+        },
+    };
+
+    let end = ZeroBasedPosition::new(raw_end)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid end position"))?;
+
+    Ok((reference_sequence_name, HalfOpenInterval::new(start, end)))
+}
+
+/// Parses the reference sequence name and 1-based, inclusive interval of a record.
+///
+/// An empty interval, e.g., a BED record where start = end, covers its start position.
+pub(crate) fn parse_record<'a>(
+    header: &Header,
+    line: &'a str,
+) -> io::Result<(&'a str, Position, Position)> {
+    let (reference_sequence_name, interval) = parse_interval(header, line)?;
+
+    let start = Position::from(interval.start());
+    let end = Interval::try_from(interval)
+        .ok()
+        .and_then(|interval| interval.end())
+        .unwrap_or(start);
+
+    Ok((reference_sequence_name, start, end))
+}
+
+fn get_field<'a>(fields: &[&'a str], i: usize) -> io::Result<&'a str> {
+    i.checked_sub(1)
+        .and_then(|j| fields.get(j))
+        .copied()
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("missing field at column {}", i),
+            )
+        })
+}
+
+fn parse_int(s: &str) -> io::Result<usize> {
+    s.parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// The end is the `END` INFO field, if set, or the end of the reference bases.
+fn parse_vcf_end(fields: &[&str], start: Position) -> io::Result<usize> {
+    if let Ok(info) = get_field(fields, VCF_INFO_INDEX) {
+        for field in info.split(';') {
+            if let Some(value) = field.strip_prefix("END=") {
+                return parse_int(value);
+            }
+        }
+    }
+
+    let reference_bases = get_field(fields, VCF_REFERENCE_BASES_INDEX)?;
+    Ok(usize::from(start) + reference_bases.len().max(1) - 1)
+}
+
+// The end is the start plus the number of reference bases consumed by the CIGAR operations.
+fn parse_sam_end(fields: &[&str], start: Position) -> io::Result<usize> {
+    let cigar = get_field(fields, SAM_CIGAR_INDEX)?;
+
+    if cigar == "*" {
+        return Ok(usize::from(start));
+    }
+
+    let mut len = 0;
+    let mut n = 0;
+
+    for b in cigar.bytes() {
+        match b {
+            b'0'..=b'9' => n = n * 10 + usize::from(b - b'0'),
+            b'M' | b'D' | b'N' | b'=' | b'X' => {
+                len += n;
+                n = 0;
+            }
+            b'I' | b'S' | b'H' | b'P' => n = 0,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid CIGAR string",
+                ))
+            }
+        }
+    }
+
+    Ok(usize::from(start) + len.max(1) - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::header::Builder;
+
+    #[test]
+    fn test_is_skipped() {
+        let header = Builder::vcf().set_line_skip_count(1).build();
+
+        assert!(is_skipped(&header, 1, "sq0\t8"));
+        assert!(is_skipped(&header, 2, "#CHROM"));
+        assert!(!is_skipped(&header, 2, "sq0\t8"));
+    }
+
+    #[test]
+    fn test_parse_interval() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Builder::bed().build();
+        assert_eq!(
+            parse_interval(&header, "sq0\t7\t13")?,
+            (
+                "sq0",
+                HalfOpenInterval::new(
+                    ZeroBasedPosition::try_from(7)?,
+                    ZeroBasedPosition::try_from(13)?
+                )
+            )
+        );
+        assert_eq!(
+            parse_interval(&header, "sq0\t0\t0")?,
+            (
+                "sq0",
+                HalfOpenInterval::new(ZeroBasedPosition::MIN, ZeroBasedPosition::MIN)
+            )
+        );
+
+        let header = Builder::gff().build();
+        assert_eq!(
+            parse_interval(&header, "sq0\tnoodles\tgene\t8\t13\t.\t+\t.\tID=g0")?,
+            (
+                "sq0",
+                HalfOpenInterval::new(
+                    ZeroBasedPosition::try_from(7)?,
+                    ZeroBasedPosition::try_from(13)?
+                )
+            )
+        );
+
+        assert!(matches!(
+            parse_interval(&header, "sq0\tnoodles\tgene\t0\t13\t.\t+\t.\tID=g0"),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_record() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Builder::bed().build();
+        assert_eq!(
+            parse_record(&header, "sq0\t7\t13\tr0")?,
+            ("sq0", Position::try_from(8)?, Position::try_from(13)?)
+        );
+        assert_eq!(
+            parse_record(&header, "sq0\t7\t7")?,
+            ("sq0", Position::try_from(8)?, Position::try_from(8)?)
+        );
+
+        let header = Builder::gff().build();
+        assert_eq!(
+            parse_record(&header, "sq0\tnoodles\tgene\t8\t13\t.\t+\t.\tID=g0")?,
+            ("sq0", Position::try_from(8)?, Position::try_from(13)?)
+        );
+
+        let header = Builder::vcf().build();
+        assert_eq!(
+            parse_record(&header, "sq0\t8\t.\tACGT\tA\t.\tPASS\t.")?,
+            ("sq0", Position::try_from(8)?, Position::try_from(11)?)
+        );
+        assert_eq!(
+            parse_record(&header, "sq0\t8\t.\tA\t<DEL>\t.\tPASS\tSVTYPE=DEL;END=21")?,
+            ("sq0", Position::try_from(8)?, Position::try_from(21)?)
+        );
+
+        let header = Builder::sam().build();
+        assert_eq!(
+            parse_record(&header, "r0\t0\tsq0\t8\t255\t2S4M1I2D\t*\t0\t0\tACGTACG\t*")?,
+            ("sq0", Position::try_from(8)?, Position::try_from(13)?)
+        );
+
+        let header = Builder::bed().build();
+        assert!(matches!(
+            parse_record(&header, "sq0\tndls\t13"),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+        assert!(matches!(
+            parse_record(&header, "sq0\t7"),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -11,8 +11,11 @@ use std::io;
 
 use bit_vec::BitVec;
 use noodles_bgzf as bgzf;
-use noodles_core::{region::Interval, Position};
-use noodles_csi::{binning_index::ReferenceSequenceExt, index::reference_sequence::Metadata};
+use noodles_core::{position::ZeroBasedPosition, region::Interval, Position};
+use noodles_csi::{
+    binning_index::ReferenceSequenceExt,
+    index::reference_sequence::{bin::Chunk, Metadata},
+};
 
 use super::{resolve_interval, MIN_SHIFT};
 
@@ -135,12 +138,16 @@ impl ReferenceSequence {
     /// # Ok::<_, noodles_core::position::TryFromIntError>(())
     /// ```
     pub fn min_offset(&self, start: Position) -> bgzf::VirtualPosition {
-        let i = (usize::from(start) - 1) / WINDOW_SIZE;
+        let i = usize::from(ZeroBasedPosition::from(start)) / WINDOW_SIZE;
         self.intervals.get(i).copied().unwrap_or_default()
     }
 }
 
 impl ReferenceSequenceExt for ReferenceSequence {
+    fn bin_chunks(&self) -> Box<dyn Iterator<Item = (usize, &[Chunk])> + '_> {
+        Box::new(self.bins().iter().map(|bin| (bin.id(), bin.chunks())))
+    }
+
     /// Returns the optional metadata for the reference sequence.
     ///
     /// Metadata is parsed from the optional pseudo-bin 37450.
@@ -188,12 +195,44 @@ impl ReferenceSequenceExt for ReferenceSequence {
     fn first_record_in_last_linear_bin_start_position(&self) -> Option<bgzf::VirtualPosition> {
         self.intervals().last().copied()
     }
+
+    /// Returns the linear index as a list of window indices and minimum offsets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::binning_index::ReferenceSequenceExt;
+    /// use noodles_tabix::index::ReferenceSequence;
+    ///
+    /// let intervals = vec![
+    ///     bgzf::VirtualPosition::from(8),
+    ///     bgzf::VirtualPosition::default(),
+    ///     bgzf::VirtualPosition::from(13),
+    /// ];
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), intervals, None);
+    ///
+    /// assert_eq!(
+    ///     reference_sequence.linear_index(14, 5),
+    ///     [
+    ///         (0, bgzf::VirtualPosition::from(8)),
+    ///         (2, bgzf::VirtualPosition::from(13)),
+    ///     ]
+    /// );
+    /// ```
+    fn linear_index(&self, _: u8, _: u8) -> Vec<(usize, bgzf::VirtualPosition)> {
+        self.intervals()
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, pos)| *pos != bgzf::VirtualPosition::default())
+            .collect()
+    }
 }
 
 fn region_to_bins(start: Position, end: Position) -> BitVec {
-    // 0-based, [start, end)
-    let start = usize::from(start) - 1;
-    let end = usize::from(end) - 1;
+    let start = usize::from(ZeroBasedPosition::from(start));
+    let end = usize::from(ZeroBasedPosition::from(end));
 
     let mut bins = BitVec::from_elem(bin::MAX_ID, false);
     bins.set(0, true);
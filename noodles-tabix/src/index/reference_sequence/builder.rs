@@ -1,12 +1,12 @@
 use std::{cmp, collections::HashMap, io};
 
 use noodles_bgzf as bgzf;
-use noodles_core::Position;
+use noodles_core::{position::ZeroBasedPosition, Position};
 use noodles_csi::index::reference_sequence::bin::Chunk;
 
 use super::{bin, Bin, Metadata, ReferenceSequence, WINDOW_SIZE};
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Builder {
     bin_builders: HashMap<usize, bin::Builder>,
     intervals: Vec<Option<bgzf::VirtualPosition>>,
@@ -15,6 +15,18 @@ pub struct Builder {
     mapped_record_count: u64,
 }
 
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            bin_builders: HashMap::new(),
+            intervals: Vec::new(),
+            start_position: bgzf::VirtualPosition::max(),
+            end_position: bgzf::VirtualPosition::default(),
+            mapped_record_count: 0,
+        }
+    }
+}
+
 impl Builder {
     pub fn add_record(&mut self, start: Position, end: Position, chunk: Chunk) -> &mut Self {
         self.update_bins(start, end, chunk);
@@ -63,8 +75,8 @@ impl Builder {
     }
 
     fn update_linear_index(&mut self, start: Position, end: Position, chunk: Chunk) {
-        let linear_index_start_offset = (usize::from(start) - 1) / WINDOW_SIZE;
-        let linear_index_end_offset = (usize::from(end) - 1) / WINDOW_SIZE;
+        let linear_index_start_offset = usize::from(ZeroBasedPosition::from(start)) / WINDOW_SIZE;
+        let linear_index_end_offset = usize::from(ZeroBasedPosition::from(end)) / WINDOW_SIZE;
 
         if linear_index_end_offset >= self.intervals.len() {
             self.intervals
@@ -85,9 +97,8 @@ impl Builder {
 
 #[allow(clippy::eq_op)]
 fn region_to_bin(start: Position, end: Position) -> io::Result<u16> {
-    // 0-based, [start, end)
-    let start = usize::from(start) - 1;
-    let end = usize::from(end) - 1;
+    let start = usize::from(ZeroBasedPosition::from(start));
+    let end = usize::from(ZeroBasedPosition::from(end));
 
     let bin = if start >> 14 == end >> 14 {
         ((1 << 15) - 1) / 7 + (start >> 14)
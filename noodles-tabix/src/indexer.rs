@@ -0,0 +1,231 @@
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{self, BufRead, Read},
+    path::Path,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+use noodles_csi::index::reference_sequence::bin::Chunk;
+
+use super::{
+    index::{
+        record::{is_skipped, parse_record},
+        Header, MAX_POSITION,
+    },
+    Index,
+};
+
+/// Indexes a bgzipped tab-delimited file.
+///
+/// The reference sequence name, start, and end columns, coordinate system, line comment prefix,
+/// and line skip count are read from the given index header. When the header has no end column,
+/// the end position is derived from the format: VCF records use the `END` INFO field or the
+/// length of the reference bases, and SAM records use the CIGAR string. This is similar to the
+/// output of `tabix -s <n> -b <n> -e <n> -S <n> -c <char> [-0] <src>`.
+///
+/// Records must be grouped by reference sequence name and sorted by start position.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_tabix as tabix;
+///
+/// let header = tabix::index::header::Builder::bed()
+///     .set_line_skip_count(1)
+///     .build();
+///
+/// let index = tabix::index("sample.tsv.gz", header)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn index<P>(src: P, header: Header) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src).map(bgzf::Reader::new)?;
+    index_records(&mut reader, header)
+}
+
+fn index_records<R>(reader: &mut bgzf::Reader<R>, header: Header) -> io::Result<Index>
+where
+    R: Read,
+{
+    let mut indexer = Index::indexer();
+    indexer.set_header(header.clone());
+
+    let mut reference_sequence_names = HashSet::new();
+    let mut previous_record: Option<(String, Position)> = None;
+
+    let mut line = String::new();
+    let mut line_number = 0;
+    let mut start_position = reader.virtual_position();
+
+    loop {
+        line.clear();
+
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+
+        line_number += 1;
+
+        let end_position = reader.virtual_position();
+        let record = line.trim_end_matches(&['\n', '\r'][..]);
+
+        if !is_skipped(&header, line_number, record) {
+            let (name, start, end) = parse_record(&header, record)?;
+
+            if end > MAX_POSITION {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "line {}: end position ({}) exceeds the max position of a tabix index ({})",
+                        line_number, end, MAX_POSITION
+                    ),
+                ));
+            }
+
+            match previous_record.as_mut() {
+                Some((previous_name, previous_start)) if previous_name == name => {
+                    if start < *previous_start {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("line {}: records are not sorted by position", line_number),
+                        ));
+                    }
+
+                    *previous_start = start;
+                }
+                _ => {
+                    if !reference_sequence_names.insert(name.to_string()) {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!(
+                                "line {}: records are not grouped by reference sequence name ({})",
+                                line_number, name
+                            ),
+                        ));
+                    }
+
+                    previous_record = Some((name.into(), start));
+                }
+            }
+
+            let chunk = Chunk::new(start_position, end_position);
+            indexer.add_record(name, start, end, chunk);
+        }
+
+        start_position = end_position;
+    }
+
+    Ok(indexer.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use noodles_csi::BinningIndex;
+
+    use super::*;
+    use crate::index::header::{format::CoordinateSystem, Builder, Format};
+
+    fn build_data(lines: &[&str]) -> io::Result<Vec<u8>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+
+        for line in lines {
+            writeln!(writer, "{}", line)?;
+        }
+
+        writer.finish()
+    }
+
+    #[test]
+    fn test_index_records() -> Result<(), Box<dyn std::error::Error>> {
+        let data = build_data(&[
+            "chrom\tpos\tend\tname",
+            "sq0\t8\t13\tr0",
+            "#sq0\t1\t1\tr1",
+            "sq0\t21\t34\tr2",
+            "sq1\t55\t89\tr3",
+        ])?;
+
+        let header = Builder::gff()
+            .set_format(Format::Generic(CoordinateSystem::Gff))
+            .set_reference_sequence_name_index(1)
+            .set_start_position_index(2)
+            .set_end_position_index(Some(3))
+            .set_line_skip_count(1)
+            .build();
+
+        let mut reader = bgzf::Reader::new(&data[..]);
+        let index = index_records(&mut reader, header)?;
+
+        let reference_sequence_names: Vec<_> = index
+            .header()
+            .reference_sequence_names()
+            .iter()
+            .map(|name| name.as_str())
+            .collect();
+        assert_eq!(reference_sequence_names, ["sq0", "sq1"]);
+
+        assert_eq!(index.reference_sequences().len(), 2);
+        assert!(index.unplaced_unmapped_record_count().is_none());
+
+        let mut reader = bgzf::Reader::new(&data[..]);
+        assert!(crate::verify(&mut reader, &index)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_records_with_vcf() -> Result<(), Box<dyn std::error::Error>> {
+        let data = build_data(&[
+            "##fileformat=VCFv4.3",
+            "#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO",
+            "sq0\t8\t.\tA\t<DEL>\t.\tPASS\tSVTYPE=DEL;END=65536",
+            "sq0\t34\t.\tACGT\tA\t.\tPASS\t.",
+        ])?;
+
+        let mut reader = bgzf::Reader::new(&data[..]);
+        let index = index_records(&mut reader, Builder::vcf().build())?;
+
+        let chunks = index.query(0, Position::try_from(60000)?..=Position::try_from(60000)?)?;
+        assert_eq!(chunks.len(), 1);
+
+        let mut reader = bgzf::Reader::new(&data[..]);
+        assert!(crate::verify(&mut reader, &index)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_records_with_unsorted_records() -> io::Result<()> {
+        let header = Builder::bed().build();
+
+        let data = build_data(&["sq0\t21\t34", "sq0\t8\t13"])?;
+        let mut reader = bgzf::Reader::new(&data[..]);
+        assert!(matches!(
+            index_records(&mut reader, header.clone()),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let data = build_data(&["sq0\t8\t13", "sq1\t8\t13", "sq0\t21\t34"])?;
+        let mut reader = bgzf::Reader::new(&data[..]);
+        assert!(matches!(
+            index_records(&mut reader, header.clone()),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let data = build_data(&["sq0\t8\t536870912"])?;
+        let mut reader = bgzf::Reader::new(&data[..]);
+        assert!(matches!(
+            index_records(&mut reader, header),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
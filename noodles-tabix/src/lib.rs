@@ -29,10 +29,12 @@
 pub mod r#async;
 
 pub mod index;
+mod indexer;
 mod reader;
+mod verify;
 mod writer;
 
-pub use self::{index::Index, reader::Reader, writer::Writer};
+pub use self::{index::Index, indexer::index, reader::Reader, verify::verify, writer::Writer};
 
 #[cfg(feature = "async")]
 pub use self::r#async::{Reader as AsyncReader, Writer as AsyncWriter};
@@ -0,0 +1,162 @@
+use std::io::{self, BufRead, Read};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{
+    binning_index::verify::{Error, Verifier},
+    index::reference_sequence::bin::Chunk,
+};
+
+use super::{
+    index::record::{is_skipped, parse_record},
+    Index,
+};
+
+/// Verifies a tabix index against the records in a bgzipped file.
+///
+/// The reader must be at the start of the stream. Records are parsed using the columns, coordinate
+/// system, line skip count, and line comment prefix in the index header. See
+/// [`noodles_csi::binning_index::verify`] for what is checked.
+///
+/// A record on a reference sequence that is not in the index is given an ID past the end of the
+/// index's reference sequence names, in order of appearance, and reported as
+/// [`Error::InvalidReferenceSequenceId`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bgzf as bgzf;
+/// use noodles_tabix as tabix;
+///
+/// let mut reader = File::open("sample.vcf.gz").map(bgzf::Reader::new)?;
+/// let index = tabix::read("sample.vcf.gz.tbi")?;
+///
+/// for error in tabix::verify(&mut reader, &index)? {
+///     eprintln!("{}", error);
+/// }
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn verify<R>(reader: &mut bgzf::Reader<R>, index: &Index) -> io::Result<Vec<Error>>
+where
+    R: Read,
+{
+    let header = index.header();
+    let reference_sequence_names = header.reference_sequence_names();
+
+    let mut verifier = Verifier::new(index);
+    let mut unknown_reference_sequence_names = Vec::new();
+
+    let mut line = String::new();
+    let mut line_number = 0;
+    let mut start_position = reader.virtual_position();
+
+    loop {
+        line.clear();
+
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+
+        line_number += 1;
+
+        let end_position = reader.virtual_position();
+        let record = line.trim_end_matches(&['\n', '\r'][..]);
+
+        if !is_skipped(header, line_number, record) {
+            let (name, start, end) = parse_record(header, record)?;
+
+            let reference_sequence_id = match reference_sequence_names.get_index_of(name) {
+                Some(id) => id,
+                None => {
+                    let i = match unknown_reference_sequence_names
+                        .iter()
+                        .position(|n| n == name)
+                    {
+                        Some(i) => i,
+                        None => {
+                            unknown_reference_sequence_names.push(name.to_string());
+                            unknown_reference_sequence_names.len() - 1
+                        }
+                    };
+
+                    reference_sequence_names.len() + i
+                }
+            };
+
+            let chunk = Chunk::new(start_position, end_position);
+            verifier.add_record(Some((reference_sequence_id, start, end, true)), chunk)?;
+        }
+
+        start_position = end_position;
+    }
+
+    Ok(verifier.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::index::header::Builder;
+
+    fn build_data(lines: &[&str]) -> io::Result<Vec<u8>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+
+        for line in lines {
+            writeln!(writer, "{}", line)?;
+        }
+
+        writer.finish()
+    }
+
+    fn build_index(data: &[u8]) -> io::Result<Index> {
+        let header = Builder::bed().build();
+
+        let mut reader = bgzf::Reader::new(data);
+        let mut indexer = Index::indexer();
+        indexer.set_header(header.clone());
+
+        let mut line = String::new();
+        let mut start_position = reader.virtual_position();
+
+        while reader.read_line(&mut line)? != 0 {
+            let end_position = reader.virtual_position();
+
+            if !line.starts_with('#') {
+                let (name, start, end) = parse_record(&header, line.trim_end())?;
+                indexer.add_record(name, start, end, Chunk::new(start_position, end_position));
+            }
+
+            line.clear();
+            start_position = end_position;
+        }
+
+        Ok(indexer.build())
+    }
+
+    #[test]
+    fn test_verify() -> io::Result<()> {
+        let data = build_data(&[
+            "#chrom\tstart\tend",
+            "sq0\t7\t13",
+            "sq0\t20\t34",
+            "sq1\t0\t5",
+        ])?;
+        let index = build_index(&data)?;
+
+        let mut reader = bgzf::Reader::new(&data[..]);
+        assert!(verify(&mut reader, &index)?.is_empty());
+
+        let data = build_data(&["#chrom\tstart\tend", "sq0\t7\t13", "sq2\t0\t5"])?;
+        let mut reader = bgzf::Reader::new(&data[..]);
+        let errors = verify(&mut reader, &index)?;
+
+        assert!(errors.contains(&Error::InvalidReferenceSequenceId {
+            position: bgzf::VirtualPosition::from(26),
+            reference_sequence_id: 2,
+        }));
+
+        Ok(())
+    }
+}
@@ -4,3 +4,6 @@
 
 #[cfg(feature = "alignment")]
 pub mod alignment;
+
+#[cfg(feature = "liftover")]
+pub mod liftover;
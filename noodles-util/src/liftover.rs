@@ -0,0 +1,891 @@
+//! Liftover of BED and VCF records.
+//!
+//! These use a [`noodles_chain::Liftover`] to map records from one assembly to another.
+
+use std::{error, fmt, io};
+
+use noodles_bed as bed;
+use noodles_chain::{liftover::MappedRegion, record::Strand, Liftover};
+use noodles_core::{Position, Region};
+use noodles_fasta as fasta;
+use noodles_vcf::{
+    self as vcf,
+    header::info::Key as InfoKey,
+    record::{
+        alternate_bases::Allele, info::field::Value as InfoValue, reference_bases::Base, Chromosome,
+    },
+};
+
+/// An error returned when a record fails to lift over.
+#[derive(Debug)]
+pub enum Error {
+    /// The record does not map to the destination assembly.
+    Unmapped,
+    /// The record only partially maps to the destination assembly.
+    ///
+    /// This is the mapping from the chain with the highest score.
+    PartiallyMapped(MappedRegion),
+    /// The chromosome is not a reference sequence name.
+    InvalidChromosome,
+    /// An allele cannot be mapped to the reverse strand.
+    ///
+    /// Only alleles with the same length as the reference bases can be reverse complemented.
+    /// Symbolic alleles, breakends, and insertions and deletions, which have padding bases, are
+    /// not supported.
+    UnsupportedAllele,
+    /// The mapped reference sequence is missing in the repository.
+    MissingReferenceSequence(String),
+    /// The reference bases do not match the destination reference sequence.
+    ReferenceBasesMismatch,
+    /// A part of the record does not map along with the record.
+    ///
+    /// This is the thick range or a block of a BED record or the span to the end position
+    /// (`END`) of a VCF record, which must map to the same reference sequence and strand and
+    /// within or, for `END`, from the mapping of the record.
+    InconsistentMapping,
+    /// The end position (`END`) of a VCF record is invalid.
+    InvalidEndPosition,
+    /// A VCF record with an end position (`END`) maps to the reverse strand.
+    ///
+    /// The position of such a record is the padding base before the span, which has no
+    /// counterpart on the reverse strand.
+    UnsupportedEndPosition,
+    /// An I/O error.
+    Io(io::Error),
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unmapped => f.write_str("unmapped"),
+            Self::PartiallyMapped(mapped_region) => write!(
+                f,
+                "partially mapped: {}/{} bases",
+                mapped_region.mapped_len(),
+                mapped_region.len()
+            ),
+            Self::InvalidChromosome => f.write_str("invalid chromosome"),
+            Self::UnsupportedAllele => f.write_str("unsupported allele"),
+            Self::MissingReferenceSequence(name) => {
+                write!(f, "missing reference sequence: {}", name)
+            }
+            Self::ReferenceBasesMismatch => f.write_str("reference bases mismatch"),
+            Self::InconsistentMapping => f.write_str("inconsistent mapping"),
+            Self::InvalidEndPosition => f.write_str("invalid end position"),
+            Self::UnsupportedEndPosition => f.write_str("unsupported end position"),
+            Self::Io(_) => f.write_str("I/O error"),
+        }
+    }
+}
+
+mod sealed {
+    use super::{Error, Liftover, MappedRegion};
+
+    pub trait Sealed {
+        fn lift(&mut self, liftover: &Liftover) -> Result<MappedRegion, Error>;
+    }
+}
+
+/// A BED record that can be lifted over.
+///
+/// This is implemented for BED3, BED4, BED5, BED6, BED8, BED9, and BED12 records. BED7 records are
+/// not supported: they have a thick start but no thick end, which is needed to map the thick range
+/// to the reverse strand.
+pub trait BedRecord: sealed::Sealed {}
+
+/// Lifts over a BED record.
+///
+/// The reference sequence name and the start and end positions are set to the mapping from the
+/// chain with the highest score, which must fully cover the record. The mapped region is
+/// returned, which includes its strand. See [`lift_stranded_bed_record`] to also update the
+/// strand of a BED6+ record.
+///
+/// The thick range of a BED8+ record and the blocks of a BED12 record are mapped the same way
+/// and must map within the record (see [`Error::InconsistentMapping`]). An empty thick range is
+/// moved to the end of the record. The record is only changed when the liftover succeeds.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bed as bed;
+/// use noodles_chain::{self as chain, Liftover};
+/// use noodles_core::Position;
+/// use noodles_util::liftover::lift_bed_record;
+///
+/// let chain: chain::Record = "chain 21 sq0 100 + 8 21 sq1 50 + 5 16 1\n5\t2\t0\n6".parse()?;
+/// let liftover: Liftover = [chain].into_iter().collect();
+///
+/// let mut record = bed::Record::<3>::builder()
+///     .set_reference_sequence_name("sq0")
+///     .set_start_position(Position::try_from(10)?)
+///     .set_end_position(Position::try_from(13)?)
+///     .build()?;
+///
+/// lift_bed_record(&liftover, &mut record)?;
+///
+/// assert_eq!(record.reference_sequence_name(), "sq1");
+/// assert_eq!(usize::from(record.start_position()), 7);
+/// assert_eq!(usize::from(record.end_position()), 10);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn lift_bed_record<const N: u8>(
+    liftover: &Liftover,
+    record: &mut bed::Record<N>,
+) -> Result<MappedRegion, Error>
+where
+    bed::Record<N>: BedRecord,
+{
+    sealed::Sealed::lift(record, liftover)
+}
+
+macro_rules! impl_bed_record {
+    ($n:expr) => {
+        impl BedRecord for bed::Record<$n> {}
+
+        impl sealed::Sealed for bed::Record<$n> {
+            fn lift(&mut self, liftover: &Liftover) -> Result<MappedRegion, Error> {
+                let mapped_region = map_bed_record(liftover, self)?;
+                set_bed_record_region(self, &mapped_region)?;
+                Ok(mapped_region)
+            }
+        }
+    };
+}
+
+impl_bed_record!(3);
+impl_bed_record!(4);
+impl_bed_record!(5);
+impl_bed_record!(6);
+
+macro_rules! impl_thick_bed_record {
+    ($n:expr) => {
+        impl BedRecord for bed::Record<$n> {}
+
+        impl sealed::Sealed for bed::Record<$n> {
+            fn lift(&mut self, liftover: &Liftover) -> Result<MappedRegion, Error> {
+                let mapped_region = map_bed_record(liftover, self)?;
+                let (thick_start, thick_end) = map_thick_range(liftover, self, &mapped_region)?;
+
+                set_bed_record_region(self, &mapped_region)?;
+                *self.thick_start_mut() = thick_start;
+                *self.thick_end_mut() = thick_end;
+
+                Ok(mapped_region)
+            }
+        }
+    };
+}
+
+impl_thick_bed_record!(8);
+impl_thick_bed_record!(9);
+
+impl BedRecord for bed::Record<12> {}
+
+impl sealed::Sealed for bed::Record<12> {
+    fn lift(&mut self, liftover: &Liftover) -> Result<MappedRegion, Error> {
+        let mapped_region = map_bed_record(liftover, self)?;
+        let (thick_start, thick_end) = map_thick_range(liftover, self, &mapped_region)?;
+        let blocks = map_blocks(liftover, self, &mapped_region)?;
+
+        set_bed_record_region(self, &mapped_region)?;
+        *self.thick_start_mut() = thick_start;
+        *self.thick_end_mut() = thick_end;
+        *self.blocks_mut() = blocks;
+
+        Ok(mapped_region)
+    }
+}
+
+fn map_bed_record<const N: u8>(
+    liftover: &Liftover,
+    record: &bed::Record<N>,
+) -> Result<MappedRegion, Error>
+where
+    bed::Record<N>: bed::record::BedN<3>,
+{
+    let region = Region::new(
+        record.reference_sequence_name(),
+        record.start_position()..=record.end_position(),
+    );
+
+    map_region(liftover, &region)
+}
+
+fn set_bed_record_region<const N: u8>(
+    record: &mut bed::Record<N>,
+    mapped_region: &MappedRegion,
+) -> Result<(), Error>
+where
+    bed::Record<N>: bed::record::BedN<3>,
+{
+    let (name, start, end) = resolve_region(mapped_region.region())?;
+
+    *record.reference_sequence_name_mut() = name.into();
+    *record.start_position_mut() = start;
+    *record.end_position_mut() = end;
+
+    Ok(())
+}
+
+fn map_thick_range<const N: u8>(
+    liftover: &Liftover,
+    record: &bed::Record<N>,
+    mapped_region: &MappedRegion,
+) -> Result<(Position, Position), Error>
+where
+    bed::Record<N>: bed::record::BedN<3> + bed::record::BedN<7> + bed::record::BedN<8>,
+{
+    let (thick_start, thick_end) = (record.thick_start(), record.thick_end());
+
+    if thick_start > thick_end {
+        let (_, _, end) = resolve_region(mapped_region.region())?;
+        let thick_start = end.checked_add(1).ok_or(Error::InconsistentMapping)?;
+        Ok((thick_start, end))
+    } else {
+        map_subregion(
+            liftover,
+            record.reference_sequence_name(),
+            thick_start,
+            thick_end,
+            mapped_region,
+        )
+    }
+}
+
+fn map_blocks(
+    liftover: &Liftover,
+    record: &bed::Record<12>,
+    mapped_region: &MappedRegion,
+) -> Result<Vec<(usize, usize)>, Error> {
+    let (_, mapped_start, _) = resolve_region(mapped_region.region())?;
+    let start = record.start_position();
+
+    let mut blocks = record
+        .blocks()
+        .iter()
+        .map(|&(offset, size)| {
+            let block_start = start.checked_add(offset);
+            let block_end = size
+                .checked_sub(1)
+                .and_then(|n| block_start.and_then(|p| p.checked_add(n)));
+
+            let (block_start, block_end) = block_start
+                .zip(block_end)
+                .ok_or(Error::InconsistentMapping)?;
+
+            let (mapped_block_start, mapped_block_end) = map_subregion(
+                liftover,
+                record.reference_sequence_name(),
+                block_start,
+                block_end,
+                mapped_region,
+            )?;
+
+            let mapped_start = usize::from(mapped_block_start) - usize::from(mapped_start);
+            let mapped_size = usize::from(mapped_block_end) - usize::from(mapped_block_start) + 1;
+
+            Ok((mapped_start, mapped_size))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    // Blocks that map to the reverse strand are in reverse order.
+    blocks.sort_unstable();
+
+    Ok(blocks)
+}
+
+/// Lifts over a BED6+ record.
+///
+/// This is the same as [`lift_bed_record`] but also reverses the strand of the record when it
+/// maps to the reverse strand.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bed::{self as bed, record::Strand};
+/// use noodles_chain::{self as chain, Liftover};
+/// use noodles_core::Position;
+/// use noodles_util::liftover::lift_stranded_bed_record;
+///
+/// let chain: chain::Record = "chain 21 sq0 100 + 8 21 sq1 50 - 5 16 1\n5\t2\t0\n6".parse()?;
+/// let liftover: Liftover = [chain].into_iter().collect();
+///
+/// let mut record = bed::Record::<6>::builder()
+///     .set_reference_sequence_name("sq0")
+///     .set_start_position(Position::try_from(10)?)
+///     .set_end_position(Position::try_from(13)?)
+///     .set_strand(Strand::Forward)
+///     .build()?;
+///
+/// lift_stranded_bed_record(&liftover, &mut record)?;
+///
+/// assert_eq!(record.reference_sequence_name(), "sq1");
+/// assert_eq!(usize::from(record.start_position()), 41);
+/// assert_eq!(usize::from(record.end_position()), 44);
+/// assert_eq!(record.strand(), Some(Strand::Reverse));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn lift_stranded_bed_record<const N: u8>(
+    liftover: &Liftover,
+    record: &mut bed::Record<N>,
+) -> Result<MappedRegion, Error>
+where
+    bed::Record<N>: BedRecord + bed::record::BedN<6>,
+{
+    use bed::record::Strand as BedStrand;
+
+    let mapped_region = lift_bed_record(liftover, record)?;
+
+    if mapped_region.strand() == Strand::Reverse {
+        let strand = record.strand_mut();
+
+        *strand = match *strand {
+            Some(BedStrand::Forward) => Some(BedStrand::Reverse),
+            Some(BedStrand::Reverse) => Some(BedStrand::Forward),
+            None => None,
+        };
+    }
+
+    Ok(mapped_region)
+}
+
+/// Lifts over a VCF record.
+///
+/// The chromosome and position are set to the mapping of the reference bases from the chain with
+/// the highest score, which must fully cover them. When the record maps to the reverse strand,
+/// the reference and alternate bases are reverse complemented.
+///
+/// When the record has an end position (`END`), e.g., a structural variant, the span from the
+/// position to the end position is also mapped, and the end position is set to the end of its
+/// mapping. Such records cannot be mapped to the reverse strand.
+///
+/// The lifted reference bases are checked against the destination reference sequence in the
+/// given repository. The record is only changed when the liftover succeeds.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_chain::{self as chain, Liftover};
+/// use noodles_fasta::{self as fasta, record::{Definition, Sequence}};
+/// use noodles_util::liftover::lift_vcf_record;
+/// use noodles_vcf::{self as vcf, record::Position};
+///
+/// let chain: chain::Record = "chain 21 sq0 100 + 8 21 sq1 50 + 5 16 1\n5\t2\t0\n6".parse()?;
+/// let liftover: Liftover = [chain].into_iter().collect();
+///
+/// let repository = fasta::Repository::new(vec![fasta::Record::new(
+///     Definition::new("sq1", None),
+///     Sequence::from(b"NNNNNNACGTNNNNNN".to_vec()),
+/// )]);
+///
+/// let mut record = vcf::Record::builder()
+///     .set_chromosome("sq0".parse()?)
+///     .set_position(Position::from(10))
+///     .set_reference_bases("AC".parse()?)
+///     .set_alternate_bases("TA".parse()?)
+///     .build()?;
+///
+/// lift_vcf_record(&liftover, &repository, &mut record)?;
+///
+/// assert_eq!(record.chromosome().to_string(), "sq1");
+/// assert_eq!(usize::from(record.position()), 7);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn lift_vcf_record(
+    liftover: &Liftover,
+    repository: &fasta::Repository,
+    record: &mut vcf::Record,
+) -> Result<MappedRegion, Error> {
+    let name = match record.chromosome() {
+        Chromosome::Name(name) => name,
+        Chromosome::Symbol(_) => return Err(Error::InvalidChromosome),
+    };
+
+    let start = Position::new(usize::from(record.position())).ok_or(Error::Unmapped)?;
+    let len = record.reference_bases().len();
+    let end = Position::new(usize::from(start) + len - 1).ok_or(Error::Unmapped)?;
+
+    let mapped_region = map_region(liftover, &Region::new(name, start..=end))?;
+    let mapped_end_position = map_end_position(liftover, record, name, start, &mapped_region)?;
+    let (name, start, end) = resolve_region(mapped_region.region())?;
+
+    let (reference_bases, alternate_bases) = match mapped_region.strand() {
+        Strand::Forward => (
+            record.reference_bases().to_vec(),
+            record.alternate_bases().to_vec(),
+        ),
+        Strand::Reverse => {
+            let reference_bases = reverse_complement(record.reference_bases());
+
+            let alternate_bases = record
+                .alternate_bases()
+                .iter()
+                .map(|allele| match allele {
+                    Allele::Bases(bases) if bases.len() == len => {
+                        Ok(Allele::Bases(reverse_complement(bases)))
+                    }
+                    _ => Err(Error::UnsupportedAllele),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            (reference_bases, alternate_bases)
+        }
+    };
+
+    let sequence = repository
+        .get(name)
+        .ok_or_else(|| Error::MissingReferenceSequence(name.into()))?
+        .map_err(Error::Io)?;
+
+    let actual_bases = sequence
+        .get(start..=end)
+        .ok_or(Error::ReferenceBasesMismatch)?;
+
+    if !bases_eq(&reference_bases, actual_bases) {
+        return Err(Error::ReferenceBasesMismatch);
+    }
+
+    *record.chromosome_mut() = Chromosome::Name(name.into());
+    *record.position_mut() = vcf::record::Position::from(usize::from(start));
+    record
+        .reference_bases_mut()
+        .copy_from_slice(&reference_bases);
+    record
+        .alternate_bases_mut()
+        .clone_from_slice(&alternate_bases);
+
+    if let Some(end_position) = mapped_end_position {
+        if let Some(field) = record.info_mut().get_mut(&InfoKey::EndPosition) {
+            *field.value_mut() = Some(InfoValue::Integer(end_position));
+        }
+    }
+
+    Ok(mapped_region)
+}
+
+// Maps the span from the position to the end position (`END`), if the record has one, and returns
+// the lifted end position.
+fn map_end_position(
+    liftover: &Liftover,
+    record: &vcf::Record,
+    name: &str,
+    start: Position,
+    mapped_region: &MappedRegion,
+) -> Result<Option<i32>, Error> {
+    if record.info().get(&InfoKey::EndPosition).is_none() {
+        return Ok(None);
+    }
+
+    let end = record
+        .end()
+        .ok()
+        .and_then(|position| Position::new(usize::from(position)))
+        .filter(|&end| end >= start)
+        .ok_or(Error::InvalidEndPosition)?;
+
+    if mapped_region.strand() == Strand::Reverse {
+        return Err(Error::UnsupportedEndPosition);
+    }
+
+    let mapped_span = map_region(liftover, &Region::new(name, start..=end))
+        .map_err(|_| Error::InconsistentMapping)?;
+
+    let (span_name, span_start, span_end) = resolve_region(mapped_span.region())?;
+    let (mapped_name, mapped_start, _) = resolve_region(mapped_region.region())?;
+
+    if span_name != mapped_name
+        || mapped_span.strand() != Strand::Forward
+        || span_start != mapped_start
+    {
+        return Err(Error::InconsistentMapping);
+    }
+
+    i32::try_from(usize::from(span_end))
+        .map(Some)
+        .map_err(|_| Error::InvalidEndPosition)
+}
+
+fn map_region(liftover: &Liftover, region: &Region) -> Result<MappedRegion, Error> {
+    let mapped_region = liftover
+        .map_region(region)
+        .into_iter()
+        .next()
+        .ok_or(Error::Unmapped)?;
+
+    if mapped_region.is_partial() {
+        Err(Error::PartiallyMapped(mapped_region))
+    } else {
+        Ok(mapped_region)
+    }
+}
+
+// Maps a part of a record and checks that it maps within the mapping of the record.
+fn map_subregion(
+    liftover: &Liftover,
+    name: &str,
+    start: Position,
+    end: Position,
+    mapped_region: &MappedRegion,
+) -> Result<(Position, Position), Error> {
+    let mapped_subregion = map_region(liftover, &Region::new(name, start..=end))
+        .map_err(|_| Error::InconsistentMapping)?;
+
+    let (subregion_name, subregion_start, subregion_end) =
+        resolve_region(mapped_subregion.region())?;
+    let (mapped_name, mapped_start, mapped_end) = resolve_region(mapped_region.region())?;
+
+    if subregion_name != mapped_name
+        || mapped_subregion.strand() != mapped_region.strand()
+        || subregion_start < mapped_start
+        || subregion_end > mapped_end
+    {
+        return Err(Error::InconsistentMapping);
+    }
+
+    Ok((subregion_start, subregion_end))
+}
+
+fn resolve_region(region: &Region) -> Result<(&str, Position, Position), Error> {
+    let interval = region.interval();
+
+    match (interval.start(), interval.end()) {
+        (Some(start), Some(end)) => Ok((region.name(), start, end)),
+        _ => Err(Error::Unmapped),
+    }
+}
+
+fn reverse_complement(bases: &[Base]) -> Vec<Base> {
+    bases
+        .iter()
+        .rev()
+        .map(|base| match base {
+            Base::A => Base::T,
+            Base::C => Base::G,
+            Base::G => Base::C,
+            Base::T => Base::A,
+            Base::N => Base::N,
+        })
+        .collect()
+}
+
+fn bases_eq(bases: &[Base], actual_bases: &[u8]) -> bool {
+    bases.len() == actual_bases.len()
+        && bases.iter().zip(actual_bases).all(|(&a, b)| {
+            let a = u8::try_from(char::from(a)).unwrap_or(b'N');
+            a.eq_ignore_ascii_case(b)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORWARD_CHAIN: &str = "chain 21 sq0 100 + 8 21 sq1 50 + 5 16 1\n5\t2\t0\n6";
+    const REVERSE_CHAIN: &str = "chain 21 sq0 100 + 8 21 sq1 50 - 5 16 1\n5\t2\t0\n6";
+
+    fn build_liftover(s: &str) -> Result<Liftover, noodles_chain::record::ParseError> {
+        let chain: noodles_chain::Record = s.parse()?;
+        Ok([chain].into_iter().collect())
+    }
+
+    fn build_bed_record(
+        reference_sequence_name: &str,
+        start: usize,
+        end: usize,
+        strand: Option<bed::record::Strand>,
+    ) -> Result<bed::Record<6>, Box<dyn std::error::Error>> {
+        let mut builder = bed::Record::<6>::builder()
+            .set_reference_sequence_name(reference_sequence_name)
+            .set_start_position(Position::try_from(start)?)
+            .set_end_position(Position::try_from(end)?);
+
+        if let Some(strand) = strand {
+            builder = builder.set_strand(strand);
+        }
+
+        Ok(builder.build()?)
+    }
+
+    #[test]
+    fn test_lift_bed_record_with_unmapped_record() -> Result<(), Box<dyn std::error::Error>> {
+        let liftover = build_liftover(FORWARD_CHAIN)?;
+
+        // sq0:30-40 is past the end of the chain.
+        let mut record = build_bed_record("sq0", 30, 40, None)?;
+        let expected = record.clone();
+        assert!(matches!(
+            lift_bed_record(&liftover, &mut record),
+            Err(Error::Unmapped)
+        ));
+        assert_eq!(record, expected);
+
+        // sq0:14-15 is in the gap between the two blocks.
+        let mut record = build_bed_record("sq0", 14, 15, None)?;
+        assert!(matches!(
+            lift_bed_record(&liftover, &mut record),
+            Err(Error::Unmapped)
+        ));
+
+        let mut record = build_bed_record("sq2", 10, 13, None)?;
+        assert!(matches!(
+            lift_bed_record(&liftover, &mut record),
+            Err(Error::Unmapped)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lift_bed_record_with_partially_mapped_record() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let liftover = build_liftover(FORWARD_CHAIN)?;
+
+        // sq0:12-17 spans the gap at sq0:14-15.
+        let mut record = build_bed_record("sq0", 12, 17, None)?;
+        let expected = record.clone();
+
+        match lift_bed_record(&liftover, &mut record) {
+            Err(Error::PartiallyMapped(mapped_region)) => {
+                assert_eq!(mapped_region.region().to_string(), "sq1:9-12");
+                assert_eq!(mapped_region.mapped_len(), 4);
+                assert_eq!(mapped_region.len(), 6);
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        assert_eq!(record, expected);
+
+        // sq0:20-23 runs past the end of the chain.
+        let mut record = build_bed_record("sq0", 20, 23, None)?;
+        assert!(matches!(
+            lift_bed_record(&liftover, &mut record),
+            Err(Error::PartiallyMapped(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lift_stranded_bed_record() -> Result<(), Box<dyn std::error::Error>> {
+        use bed::record::Strand as BedStrand;
+
+        let lift = |chain, strand| -> Result<_, Box<dyn std::error::Error>> {
+            let liftover = build_liftover(chain)?;
+            let mut record = build_bed_record("sq0", 10, 13, strand)?;
+            let mapped_region = lift_stranded_bed_record(&liftover, &mut record)?;
+
+            Ok((
+                usize::from(record.start_position()),
+                usize::from(record.end_position()),
+                record.strand(),
+                mapped_region.strand(),
+            ))
+        };
+
+        assert_eq!(
+            lift(FORWARD_CHAIN, Some(BedStrand::Forward))?,
+            (7, 10, Some(BedStrand::Forward), Strand::Forward)
+        );
+        assert_eq!(
+            lift(FORWARD_CHAIN, Some(BedStrand::Reverse))?,
+            (7, 10, Some(BedStrand::Reverse), Strand::Forward)
+        );
+
+        assert_eq!(
+            lift(REVERSE_CHAIN, Some(BedStrand::Forward))?,
+            (41, 44, Some(BedStrand::Reverse), Strand::Reverse)
+        );
+        assert_eq!(
+            lift(REVERSE_CHAIN, Some(BedStrand::Reverse))?,
+            (41, 44, Some(BedStrand::Forward), Strand::Reverse)
+        );
+        assert_eq!(lift(REVERSE_CHAIN, None)?, (41, 44, None, Strand::Reverse));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lift_bed_record_with_thick_range_and_blocks() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let build_record =
+            || "sq0\t8\t13\t.\t0\t.\t9\t12\t0\t2\t1,3\t0,2".parse::<bed::Record<12>>();
+
+        let liftover = build_liftover(FORWARD_CHAIN)?;
+        let mut record = build_record()?;
+        lift_bed_record(&liftover, &mut record)?;
+        assert_eq!(
+            record.to_string(),
+            "sq1\t5\t10\t.\t0\t.\t6\t9\t0\t2\t1,3\t0,2"
+        );
+
+        let liftover = build_liftover(REVERSE_CHAIN)?;
+        let mut record = build_record()?;
+        lift_bed_record(&liftover, &mut record)?;
+        assert_eq!(
+            record.to_string(),
+            "sq1\t40\t45\t.\t0\t.\t41\t44\t0\t2\t3,1\t0,4"
+        );
+
+        // The second block runs past the end of the record.
+        let liftover = build_liftover(FORWARD_CHAIN)?;
+        let mut record = build_record()?;
+        record.blocks_mut()[1] = (2, 8);
+        let expected = record.clone();
+        assert!(matches!(
+            lift_bed_record(&liftover, &mut record),
+            Err(Error::InconsistentMapping)
+        ));
+        assert_eq!(record, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lift_bed_record_with_empty_thick_range() -> Result<(), Box<dyn std::error::Error>> {
+        let liftover = build_liftover(FORWARD_CHAIN)?;
+
+        let mut record: bed::Record<8> = "sq0\t8\t13\t.\t0\t+\t8\t8".parse()?;
+        lift_bed_record(&liftover, &mut record)?;
+        assert_eq!(record.to_string(), "sq1\t5\t10\t.\t0\t+\t10\t10");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lift_vcf_record_with_end_position() -> Result<(), Box<dyn std::error::Error>> {
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            fasta::record::Definition::new("sq1", None),
+            fasta::record::Sequence::from(b"NNNNNNACGTNNNNNN".to_vec()),
+        )]);
+
+        let build_record = |info: &str| {
+            vcf::Record::builder()
+                .set_chromosome("sq0".parse()?)
+                .set_position(vcf::record::Position::from(10))
+                .set_reference_bases("A".parse()?)
+                .set_alternate_bases("<DEL>".parse()?)
+                .set_info(info.parse()?)
+                .build()
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
+        };
+
+        let liftover = build_liftover(FORWARD_CHAIN)?;
+        let mut record = build_record("SVTYPE=DEL;END=12")?;
+        lift_vcf_record(&liftover, &repository, &mut record)?;
+        assert_eq!(usize::from(record.position()), 7);
+        assert_eq!(record.info().to_string(), "SVTYPE=DEL;END=9");
+
+        // sq0:10-17 spans the gap at sq0:14-15.
+        let mut record = build_record("SVTYPE=DEL;END=17")?;
+        let expected = record.clone();
+        assert!(matches!(
+            lift_vcf_record(&liftover, &repository, &mut record),
+            Err(Error::InconsistentMapping)
+        ));
+        assert_eq!(record, expected);
+
+        let mut record = build_record("SVTYPE=DEL;END=8")?;
+        assert!(matches!(
+            lift_vcf_record(&liftover, &repository, &mut record),
+            Err(Error::InvalidEndPosition)
+        ));
+
+        let liftover = build_liftover(REVERSE_CHAIN)?;
+        let mut record = build_record("SVTYPE=DEL;END=12")?;
+        assert!(matches!(
+            lift_vcf_record(&liftover, &repository, &mut record),
+            Err(Error::UnsupportedEndPosition)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lift_vcf_record_with_unmapped_record() -> Result<(), Box<dyn std::error::Error>> {
+        let liftover = build_liftover(FORWARD_CHAIN)?;
+        let repository = fasta::Repository::default();
+
+        let mut record = vcf::Record::builder()
+            .set_chromosome("sq0".parse()?)
+            .set_position(vcf::record::Position::from(14))
+            .set_reference_bases("A".parse()?)
+            .set_alternate_bases("C".parse()?)
+            .build()?;
+
+        let expected = record.clone();
+
+        assert!(matches!(
+            lift_vcf_record(&liftover, &repository, &mut record),
+            Err(Error::Unmapped)
+        ));
+
+        assert_eq!(record, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lift_vcf_record_with_reverse_strand() -> Result<(), Box<dyn std::error::Error>> {
+        use fasta::record::{Definition, Sequence};
+
+        // sq0:9-13 => sq1:42-46 (-)
+        let chain: noodles_chain::Record = "chain 21 sq0 100 + 8 13 sq1 50 - 4 9 1\n5".parse()?;
+        let liftover: Liftover = [chain].into_iter().collect();
+
+        let mut sequence = vec![b'N'; 50];
+        sequence[42..45].copy_from_slice(b"ACG");
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq1", None),
+            Sequence::from(sequence),
+        )]);
+
+        let build_record = |reference_bases: &str, alternate_bases: &str| {
+            vcf::Record::builder()
+                .set_chromosome("sq0".parse()?)
+                .set_position(vcf::record::Position::from(10))
+                .set_reference_bases(reference_bases.parse()?)
+                .set_alternate_bases(alternate_bases.parse()?)
+                .build()
+                .map_err(Box::<dyn std::error::Error>::from)
+        };
+
+        // sq0:10-12 => sq1:43-45 (-)
+        let mut record = build_record("CGT", "GGA")?;
+        lift_vcf_record(&liftover, &repository, &mut record)?;
+        assert_eq!(record.chromosome().to_string(), "sq1");
+        assert_eq!(usize::from(record.position()), 43);
+        assert_eq!(record.reference_bases().to_string(), "ACG");
+        assert_eq!(record.alternate_bases().to_string(), "TCC");
+
+        let mut record = build_record("CGA", "GGA")?;
+        assert!(matches!(
+            lift_vcf_record(&liftover, &repository, &mut record),
+            Err(Error::ReferenceBasesMismatch)
+        ));
+        assert_eq!(record.chromosome().to_string(), "sq0");
+
+        let mut record = build_record("CGT", "C")?;
+        assert!(matches!(
+            lift_vcf_record(&liftover, &repository, &mut record),
+            Err(Error::UnsupportedAllele)
+        ));
+
+        let mut record = build_record("CGTAAAA", "C")?;
+        assert!(matches!(
+            lift_vcf_record(&liftover, &repository, &mut record),
+            Err(Error::PartiallyMapped(_))
+        ));
+
+        Ok(())
+    }
+}
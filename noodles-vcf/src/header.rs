@@ -16,9 +16,10 @@ pub use self::{
 use std::str::FromStr;
 
 use indexmap::{IndexMap, IndexSet};
+use noodles_core::{sequence_dictionary::Sequence, SequenceDictionary};
 
 use self::record::value::{
-    map::{contig, AlternativeAllele, Contig, Filter, Format, Info, Meta},
+    map::{contig, AlternativeAllele, Contig, Filter, Format, Info, Meta, TryFromFieldsError},
     Map,
 };
 
@@ -760,6 +761,63 @@ impl FromStr for Header {
     }
 }
 
+impl From<&Header> for SequenceDictionary {
+    /// Converts the contigs of a VCF header to a sequence dictionary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::SequenceDictionary;
+    /// use noodles_vcf as vcf;
+    ///
+    /// use vcf::header::record::value::{map::Contig, Map};
+    ///
+    /// let mut contig = Map::<Contig>::new("sq1".parse()?);
+    /// *contig.length_mut() = Some(13);
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_contig(Map::<Contig>::new("sq0".parse()?))
+    ///     .add_contig(contig)
+    ///     .build();
+    ///
+    /// let dictionary = SequenceDictionary::from(&header);
+    ///
+    /// assert_eq!(dictionary.len(), 2);
+    /// assert_eq!(dictionary.get("sq1").and_then(|sequence| sequence.length()), Some(13));
+    /// # Ok::<_, vcf::header::record::value::map::contig::name::ParseError>(())
+    /// ```
+    fn from(header: &Header) -> Self {
+        header.contigs().values().map(Sequence::from).collect()
+    }
+}
+
+impl TryFrom<&SequenceDictionary> for Header {
+    type Error = TryFromFieldsError;
+
+    /// Converts a sequence dictionary to a VCF header with only contigs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{sequence_dictionary::Sequence, SequenceDictionary};
+    /// use noodles_vcf as vcf;
+    ///
+    /// let dictionary: SequenceDictionary = [Sequence::new("sq0", Some(8))].into_iter().collect();
+    /// let header = vcf::Header::try_from(&dictionary)?;
+    ///
+    /// assert_eq!(header.contigs().len(), 1);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn try_from(dictionary: &SequenceDictionary) -> Result<Self, Self::Error> {
+        dictionary
+            .iter()
+            .try_fold(Self::builder(), |builder, sequence| {
+                Map::<Contig>::try_from(sequence).map(|contig| builder.add_contig(contig))
+            })
+            .map(|builder| builder.build())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
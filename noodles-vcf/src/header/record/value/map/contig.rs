@@ -9,12 +9,15 @@ pub use self::name::Name;
 use std::fmt;
 
 use indexmap::IndexMap;
+use noodles_core::sequence_dictionary::Sequence;
 
 use super::{Fields, Indexed, Inner, Map, TryFromFieldsError};
 
 type StandardTag = tag::Standard;
 type Tag = super::tag::Tag<StandardTag>;
 
+const MD5: &str = "md5";
+
 /// An inner VCF header contig map value.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Contig {
@@ -141,6 +144,52 @@ impl TryFrom<Fields> for Map<Contig> {
     }
 }
 
+impl From<&Map<Contig>> for Sequence {
+    fn from(map: &Map<Contig>) -> Self {
+        let mut sequence = Sequence::new(map.id().as_ref(), map.length());
+
+        *sequence.md5_checksum_mut() = map
+            .other_fields()
+            .get(MD5)
+            .and_then(|s| parse_md5_checksum(s));
+
+        sequence
+    }
+}
+
+impl TryFrom<&Sequence> for Map<Contig> {
+    type Error = TryFromFieldsError;
+
+    fn try_from(sequence: &Sequence) -> Result<Self, Self::Error> {
+        let mut fields = vec![(String::from("ID"), sequence.name().into())];
+
+        if let Some(length) = sequence.length() {
+            fields.push((String::from("length"), length.to_string()));
+        }
+
+        if let Some(md5_checksum) = sequence.md5_checksum() {
+            let s = md5_checksum.iter().map(|b| format!("{:02x}", b)).collect();
+            fields.push((String::from(MD5), s));
+        }
+
+        Self::try_from(fields)
+    }
+}
+
+fn parse_md5_checksum(s: &str) -> Option<[u8; 16]> {
+    if s.len() != 32 || !s.is_ascii() {
+        return None;
+    }
+
+    let mut checksum = [0; 16];
+
+    for (i, b) in checksum.iter_mut().enumerate() {
+        *b = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).ok()?;
+    }
+
+    Some(checksum)
+}
+
 fn parse_length(s: &str, value: &mut Option<usize>) -> Result<(), TryFromFieldsError> {
     let n = s
         .parse()
@@ -190,6 +239,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sequence_conversions() -> Result<(), TryFromFieldsError> {
+        let map = Map::<Contig>::try_from(vec![
+            (String::from("ID"), String::from("sq0")),
+            (String::from("length"), String::from("8")),
+            (
+                String::from("md5"),
+                String::from("d7eba311421bbc9d3ada44709dd61534"),
+            ),
+        ])?;
+
+        let sequence = Sequence::from(&map);
+        assert_eq!(sequence.name(), "sq0");
+        assert_eq!(sequence.length(), Some(8));
+        assert_eq!(
+            sequence.md5_checksum(),
+            Some([
+                0xd7, 0xeb, 0xa3, 0x11, 0x42, 0x1b, 0xbc, 0x9d, 0x3a, 0xda, 0x44, 0x70, 0x9d, 0xd6,
+                0x15, 0x34,
+            ])
+        );
+
+        assert_eq!(Map::<Contig>::try_from(&sequence)?, map);
+
+        assert_eq!(
+            Map::<Contig>::try_from(&Sequence::new("sq 0", None)),
+            Err(TryFromFieldsError::InvalidValue("ID"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_md5_checksum() {
+        assert_eq!(
+            parse_md5_checksum("d7eba311421bbc9d3ada44709dd61534"),
+            Some([
+                0xd7, 0xeb, 0xa3, 0x11, 0x42, 0x1b, 0xbc, 0x9d, 0x3a, 0xda, 0x44, 0x70, 0x9d, 0xd6,
+                0x15, 0x34,
+            ])
+        );
+        assert!(parse_md5_checksum("d7eba311").is_none());
+        assert!(parse_md5_checksum("z7eba311421bbc9d3ada44709dd61534").is_none());
+    }
+
     #[test]
     fn test_parse_length() -> Result<(), TryFromFieldsError> {
         let mut length = None;
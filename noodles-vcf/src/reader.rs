@@ -5,12 +5,16 @@ mod records;
 
 pub use self::{query::Query, records::Records};
 
-use std::io::{self, BufRead, Read, Seek};
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Read, Seek},
+    slice,
+};
 
 use memchr::memchr;
 use noodles_bgzf as bgzf;
-use noodles_core::Region;
-use noodles_csi::BinningIndex;
+use noodles_core::{region::IntervalSet, Region};
+use noodles_csi::{binning_index::merge_chunks, BinningIndex};
 use noodles_tabix as tabix;
 
 use super::Header;
@@ -268,7 +272,7 @@ where
     /// ```no_run
     /// # use std::fs::File;
     /// use noodles_bgzf as bgzf;;
-    /// use noodles_core::Region;
+    /// use noodles_core::{region::IntervalSet, Region};
     /// use noodles_tabix as tabix;
     /// use noodles_vcf as vcf;
     ///
@@ -294,16 +298,76 @@ where
         index: &tabix::Index,
         region: &Region,
     ) -> io::Result<Query<'r, 'h, R>> {
-        let (reference_sequence_id, reference_sequence_name) = resolve_region(index, region)?;
-        let chunks = index.query(reference_sequence_id, region.interval())?;
-
-        Ok(Query::new(
-            self,
-            chunks,
-            reference_sequence_name,
-            region.interval(),
-            header,
-        ))
+        self.query_many(header, index, slice::from_ref(region))
+    }
+
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// The chunks of all regions are merged, so each part of the file is read at most once.
+    /// Records are returned in file order, and a record that intersects more than one region is
+    /// only returned once.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_core::{region::IntervalSet, Region};
+    /// use noodles_tabix as tabix;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut reader = File::open("sample.vcf.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(vcf::Reader::new)?;
+    ///
+    /// let header = reader.read_header()?.parse()?;
+    ///
+    /// let index = tabix::read("sample.vcf.gz.tbi")?;
+    /// let regions = ["sq0:8-13".parse()?, "sq0:21-34".parse()?];
+    /// let query = reader.query_many(&header, &index, &regions)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_many<'r, 'h>(
+        &'r mut self,
+        header: &'h Header,
+        index: &tabix::Index,
+        regions: &[Region],
+    ) -> io::Result<Query<'r, 'h, R>> {
+        let mut interval_sets: HashMap<usize, IntervalSet> = HashMap::new();
+
+        for region in regions {
+            let (reference_sequence_id, _) = resolve_region(index, region)?;
+
+            interval_sets
+                .entry(reference_sequence_id)
+                .or_default()
+                .insert(region.interval());
+        }
+
+        let mut chunks = Vec::new();
+
+        for (&reference_sequence_id, interval_set) in &interval_sets {
+            for interval in interval_set.iter() {
+                chunks.extend(index.query(reference_sequence_id, interval)?);
+            }
+        }
+
+        let chunks = merge_chunks(&chunks);
+
+        // Records are matched by name, and the IDs were resolved from the index header.
+        let reference_sequence_names = index.header().reference_sequence_names();
+
+        let interval_sets = interval_sets
+            .into_iter()
+            .map(|(id, interval_set)| (reference_sequence_names[id].clone(), interval_set))
+            .collect();
+
+        Ok(Query::new(self, chunks, interval_sets, header))
     }
 }
 
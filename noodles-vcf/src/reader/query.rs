@@ -1,10 +1,11 @@
 use std::{
+    collections::HashMap,
     io::{self, Read, Seek},
     vec,
 };
 
 use noodles_bgzf as bgzf;
-use noodles_core::region::Interval;
+use noodles_core::region::{Interval, IntervalSet};
 use noodles_csi::index::reference_sequence::bin::Chunk;
 
 use super::Reader;
@@ -18,7 +19,7 @@ enum State {
 
 /// An iterator over records of a VCF reader that intersects a given region.
 ///
-/// This is created by calling [`Reader::query`].
+/// This is created by calling [`Reader::query`] or [`Reader::query_many`].
 pub struct Query<'r, 'h, R>
 where
     R: Read + Seek + 'r,
@@ -27,8 +28,7 @@ where
 
     chunks: vec::IntoIter<Chunk>,
 
-    reference_sequence_name: String,
-    interval: Interval,
+    interval_sets: HashMap<String, IntervalSet>,
 
     state: State,
     header: &'h Header,
@@ -42,8 +42,7 @@ where
     pub(super) fn new(
         reader: &'r mut Reader<bgzf::Reader<R>>,
         chunks: Vec<Chunk>,
-        reference_sequence_name: String,
-        interval: Interval,
+        interval_sets: HashMap<String, IntervalSet>,
         header: &'h Header,
     ) -> Self {
         Self {
@@ -51,8 +50,7 @@ where
 
             chunks: chunks.into_iter(),
 
-            reference_sequence_name,
-            interval,
+            interval_sets,
 
             state: State::Seek,
             header,
@@ -101,7 +99,7 @@ where
                             self.state = State::Seek;
                         }
 
-                        match intersects(&record, &self.reference_sequence_name, self.interval) {
+                        match intersects_any(&record, &self.interval_sets) {
                             Ok(true) => return Some(Ok(record)),
                             Ok(false) => {}
                             Err(e) => return Some(Err(e)),
@@ -116,14 +114,31 @@ where
     }
 }
 
+fn intersects_any(
+    record: &Record,
+    interval_sets: &HashMap<String, IntervalSet>,
+) -> io::Result<bool> {
+    let name = record.chromosome().to_string();
+
+    match interval_sets.get(&name) {
+        Some(interval_set) => record_interval(record).map(|i| interval_set.intersects(i)),
+        None => Ok(false),
+    }
+}
+
+#[cfg(feature = "async")]
 pub(crate) fn intersects(
     record: &Record,
     reference_sequence_name: &str,
     region_interval: Interval,
 ) -> io::Result<bool> {
-    use noodles_core::Position;
-
     let name = record.chromosome().to_string();
+    let record_interval = record_interval(record)?;
+    Ok(name == reference_sequence_name && record_interval.intersects(region_interval))
+}
+
+fn record_interval(record: &Record) -> io::Result<Interval> {
+    use noodles_core::Position;
 
     let start = Position::try_from(usize::from(record.position()))
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
@@ -136,7 +151,5 @@ pub(crate) fn intersects(
             Position::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
         })?;
 
-    let record_interval = Interval::from(start..=end);
-
-    Ok(name == reference_sequence_name && record_interval.intersects(region_interval))
+    Ok(Interval::from(start..=end))
 }
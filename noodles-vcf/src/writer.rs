@@ -42,6 +42,7 @@ use super::{Header, Record};
 #[derive(Debug)]
 pub struct Writer<W> {
     inner: W,
+    buf: Vec<u8>,
 }
 
 impl<W> Writer<W>
@@ -57,7 +58,10 @@ where
     /// let writer = vcf::Writer::new(Vec::new());
     /// ```
     pub fn new(inner: W) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            buf: Vec::new(),
+        }
     }
 
     /// Returns a reference to the underlying writer.
@@ -135,7 +139,10 @@ where
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
-        writeln!(self.inner, "{}", record)
+        // See `bgzf::writer::Builder::set_aligned_writes`.
+        self.buf.clear();
+        writeln!(self.buf, "{}", record)?;
+        self.inner.write_all(&self.buf)
     }
 }
 
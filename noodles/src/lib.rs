@@ -1,8 +1,8 @@
 #![warn(missing_docs)]
 
 //! **noodles** is a library for handling various bioinformatics file formats. It currently
-//! includes readers and writers for BAM 1.6, BCF 2.2, BED, BGZF, CRAM 3.0, CSI, FASTA, FASTQ,
-//! GFF3, GTF 2.2, SAM 1.6, tabix, and VCF 4.3.
+//! includes readers and writers for BAM 1.6, BCF 2.2, BED, BGZF, chain, CRAM 3.0, CSI, FASTA,
+//! FASTQ, GFF3, GTF 2.2, SAM 1.6, tabix, and VCF 4.3.
 
 #[cfg(feature = "bam")]
 #[doc(inline)]
@@ -20,6 +20,10 @@ pub use noodles_bed as bed;
 #[doc(inline)]
 pub use noodles_bgzf as bgzf;
 
+#[cfg(feature = "chain")]
+#[doc(inline)]
+pub use noodles_chain as chain;
+
 #[cfg(feature = "core")]
 #[doc(inline)]
 pub use noodles_core as core;